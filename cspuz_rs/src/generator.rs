@@ -312,6 +312,7 @@ mod tests {
     use rand::SeedableRng;
 
     use super::*;
+    use crate::solver::SolveContext;
 
     #[test]
     fn test_generator_slitherlink() {
@@ -324,7 +325,7 @@ mod tests {
             ];
 
         let solver = |problem: &Vec<Vec<Option<i32>>>| {
-            crate::puzzle::slitherlink::solve_slitherlink(problem)
+            crate::puzzle::slitherlink::solve_slitherlink(problem, &mut SolveContext::new())
         };
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let generated = Generator::new(
@@ -336,7 +337,8 @@ mod tests {
         .generate(&mut rng);
         assert!(generated.is_some());
         let generated = generated.unwrap();
-        let ans = crate::puzzle::slitherlink::solve_slitherlink(&generated);
+        let ans =
+            crate::puzzle::slitherlink::solve_slitherlink(&generated, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         assert!(default_uniqueness_checker()(&generated, &ans));
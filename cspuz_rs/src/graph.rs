@@ -342,6 +342,14 @@ where
     solver.add_active_vertices_connected(is_active, &graph.edges);
 }
 
+pub fn active_edges_acyclic<T>(solver: &mut Solver, is_active_edge: T, graph: &Graph)
+where
+    T: IntoIterator,
+    <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+{
+    solver.add_acyclic(is_active_edge, &graph.edges);
+}
+
 pub fn active_vertices_connected_2d<T>(solver: &mut Solver, is_active: T)
 where
     T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
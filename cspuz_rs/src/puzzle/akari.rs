@@ -3,9 +3,12 @@ use crate::serializer::{
     from_base16, problem_to_url, to_base16, url_to_problem, Choice, Combinator, Context, Dict,
     Grid, Spaces,
 };
-use crate::solver::{BoolVar, Solver};
+use crate::solver::{BoolVar, SolveContext, Solver};
 
-pub fn solve_akari(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_akari(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -87,7 +90,7 @@ pub fn solve_akari(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>>
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(has_light))
+    solver.irrefutable_facts(ctx).map(|f| f.get(has_light))
 }
 
 struct AkariClueCombinator;
@@ -168,7 +171,7 @@ mod tests {
     #[test]
     fn test_akari_problem() {
         let problem = problem_for_tests();
-        let ans = solve_akari(&problem);
+        let ans = solve_akari(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_bool_2d([
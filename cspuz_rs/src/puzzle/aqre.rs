@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_aqre(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let h = borders.vertical.len();
     assert!(h > 0);
@@ -37,7 +38,7 @@ pub fn solve_aqre(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
@@ -77,7 +78,7 @@ mod tests {
         assert_eq!(serialize_problem(&problem), Some(String::from(url)));
         let (borders, clues) = problem;
 
-        let ans = solve_aqre(&borders, &clues);
+        let ans = solve_aqre(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
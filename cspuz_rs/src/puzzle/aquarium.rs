@@ -0,0 +1,144 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, OutsideClues,
+    OutsideCluesCombinator, Rooms, Size, Tuple2,
+};
+use crate::solver::{count_true, SolveContext, Solver};
+
+pub fn solve_aquarium(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clue_vertical: &[Option<i32>],
+    clue_horizontal: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_water = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_water);
+
+    // in each room, the cells in the same row have the same water level, and a cell is filled
+    // only if the cells in the rows below are filled
+    let rooms = graph::borders_to_rooms(borders);
+    for room in &rooms {
+        let mut room = room.clone();
+        room.sort();
+        for i in 1..room.len() {
+            let (y1, x1) = room[i - 1];
+            let (y2, x2) = room[i];
+            if y1 == y2 {
+                solver.add_expr(is_water.at((y1, x1)).iff(is_water.at((y2, x2))));
+            } else {
+                solver.add_expr(is_water.at((y1, x1)).imp(is_water.at((y2, x2))));
+            }
+        }
+    }
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(count_true(is_water.slice_fixed_x((.., x))).eq(n));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(count_true(is_water.slice_fixed_y((y, ..))).eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_water))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, OutsideClues);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(Rooms, OutsideCluesCombinator))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "aquarium",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["aquarium"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A B B
+        // A C C B
+        // D C C B
+        // D D E E
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 1, 1, 0], [1, 0, 0, 0], [0, 1, 1, 1]]),
+            vertical: util::tests::to_bool_2d([[0, 1, 0], [1, 0, 1], [1, 0, 1], [0, 1, 0]]),
+        };
+        let clue_vertical = vec![Some(2), Some(2), Some(2), Some(3)];
+        let clue_horizontal = vec![Some(4), Some(2), Some(3), Some(0)];
+        (borders, (clue_vertical, clue_horizontal))
+    }
+
+    #[test]
+    fn test_aquarium_problem() {
+        let (borders, (clue_vertical, clue_horizontal)) = problem_for_tests();
+        let ans = solve_aquarium(
+            &borders,
+            &clue_vertical,
+            &clue_horizontal,
+            &mut SolveContext::new(),
+        );
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [1, 1, 1, 1],
+            [1, 0, 0, 1],
+            [0, 1, 1, 1],
+            [0, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_aquarium_gravity() {
+        // the room of 3 cells has one cell in the top row, which is filled only if the whole
+        // room is filled
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 1]]),
+            vertical: util::tests::to_bool_2d([[1], [0]]),
+        };
+        let ans = solve_aquarium(
+            &borders,
+            &[Some(2), None],
+            &[Some(1), None],
+            &mut SolveContext::new(),
+        );
+        assert_eq!(ans, Some(util::tests::to_option_bool_2d([[1, 0], [1, 1]])));
+
+        let ans = solve_aquarium(
+            &borders,
+            &[Some(1), Some(0)],
+            &[None, None],
+            &mut SolveContext::new(),
+        );
+        assert_eq!(ans, None);
+    }
+
+    #[test]
+    fn test_aquarium_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?aquarium/4/4/aqgd1o22234230";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -3,9 +3,12 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{any, count_true, Solver};
+use crate::solver::{any, count_true, SolveContext, Solver};
 
-pub fn solve_araf(clues: &[Vec<Option<i32>>]) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+pub fn solve_araf(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
     let mut clue_pos = vec![];
@@ -80,7 +83,7 @@ pub fn solve_araf(clues: &[Vec<Option<i32>>]) -> Option<graph::BoolInnerGridEdge
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(border))
+    solver.irrefutable_facts(ctx).map(|f| f.get(border))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -120,7 +123,7 @@ mod tests {
     #[test]
     fn test_araf_problem() {
         let problem = problem_for_tests();
-        let ans = solve_araf(&problem);
+        let ans = solve_araf(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
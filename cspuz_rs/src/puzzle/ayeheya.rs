@@ -1,11 +1,12 @@
 use crate::graph;
 use crate::puzzle::heyawake;
 use crate::serializer::{problem_to_url_with_context, url_to_problem, Context};
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_ayeheya(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     assert!(all_room_symmetry(borders));
     let (h, w) = borders.base_shape();
@@ -29,7 +30,7 @@ pub fn solve_ayeheya(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 pub fn all_room_symmetry(borders: &graph::InnerGridEdges<Vec<Vec<bool>>>) -> bool {
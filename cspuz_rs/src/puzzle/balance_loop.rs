@@ -0,0 +1,154 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+    Tuple2,
+};
+use crate::solver::{any, SolveContext, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BalanceLoopCircle {
+    White,
+    Black,
+}
+
+/// A circle and the sum of the lengths of the two straight lines from it, or -1 if not given.
+pub type BalanceLoopClue = (BalanceLoopCircle, i32);
+
+/// Solves a Balance Loop problem. The two straight lines from a white circle have the same
+/// length, and those from a black circle have different lengths.
+pub fn solve_balance_loop(
+    clues: &[Vec<Option<BalanceLoopClue>>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+    solver.add_expr(is_passed);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some((circle, n)) = clues[y][x] {
+                let up = is_line
+                    .vertical
+                    .slice_fixed_x((..y, x))
+                    .reverse()
+                    .consecutive_prefix_true();
+                let down = is_line
+                    .vertical
+                    .slice_fixed_x((y.., x))
+                    .consecutive_prefix_true();
+                let left = is_line
+                    .horizontal
+                    .slice_fixed_y((y, ..x))
+                    .reverse()
+                    .consecutive_prefix_true();
+                let right = is_line
+                    .horizontal
+                    .slice_fixed_y((y, x..))
+                    .consecutive_prefix_true();
+
+                // exactly two of the four lines are nonzero, so they have the same length iff
+                // each line is either zero or half of the total
+                let total = up.clone() + down.clone() + left.clone() + right.clone();
+                let is_balanced = [up, down, left, right]
+                    .into_iter()
+                    .map(|len| len.eq(0) | (len.clone() + len).eq(total.clone()))
+                    .collect::<Vec<_>>();
+                match circle {
+                    BalanceLoopCircle::White => solver.add_expr(is_balanced),
+                    BalanceLoopCircle::Black => {
+                        solver.add_expr(any(is_balanced.into_iter().map(|b| !b)))
+                    }
+                }
+                if n > 0 {
+                    solver.add_expr(total.eq(n));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<BalanceLoopClue>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(Tuple2::new(
+            Choice::new(vec![
+                Box::new(Dict::new(BalanceLoopCircle::White, "1")),
+                Box::new(Dict::new(BalanceLoopCircle::Black, "2")),
+            ]),
+            Choice::new(vec![Box::new(Dict::new(-1, ".")), Box::new(HexInt)]),
+        ))),
+        Box::new(Spaces::new(None, 'g')),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "balance", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["balance", "balance_loop"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 4];
+        ret[0][4] = Some((BalanceLoopCircle::Black, 4));
+        ret[2][1] = Some((BalanceLoopCircle::White, -1));
+        ret[3][2] = Some((BalanceLoopCircle::White, -1));
+        ret
+    }
+
+    #[test]
+    fn test_balance_loop_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_balance_loop(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [1, 1, 0, 1],
+                [1, 0, 0, 0],
+                [1, 0, 1, 0],
+                [1, 1, 1, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([
+                [1, 0, 1, 1, 1],
+                [0, 1, 1, 1, 1],
+                [1, 0, 0, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+
+        // the black circle at (0, 4) has lines of lengths 1 (left) and 3 (down)
+        assert_eq!(ans.horizontal[0][3], Some(true));
+        assert_eq!(ans.horizontal[0][2], Some(false));
+        assert_eq!(ans.vertical[0][4], Some(true));
+        assert_eq!(ans.vertical[1][4], Some(true));
+        assert_eq!(ans.vertical[2][4], Some(true));
+        // the white circle at (2, 1) has lines of length 1 (left and up)
+        assert_eq!(ans.horizontal[2][0], Some(true));
+        assert_eq!(ans.vertical[1][1], Some(true));
+        assert_eq!(ans.vertical[0][1], Some(false));
+    }
+
+    #[test]
+    fn test_balance_loop_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?balance/5/4/j24l1.k1.h";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -4,11 +4,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
     MultiDigit, Rooms, Size, Tuple2,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_barns(
     icebarn: &[Vec<bool>],
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(icebarn);
 
@@ -66,7 +67,7 @@ pub fn solve_barns(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = (Vec<Vec<bool>>, graph::InnerGridEdges<Vec<Vec<bool>>>);
@@ -134,7 +135,7 @@ mod tests {
     #[test]
     fn test_barns_problem() {
         let (icebarn, borders) = problem_for_tests();
-        let ans = solve_barns(&icebarn, &borders);
+        let ans = solve_barns(&icebarn, &borders, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
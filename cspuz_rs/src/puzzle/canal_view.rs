@@ -0,0 +1,113 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Canal View problem. A clue is the number of shaded cells visible from it in the four
+/// directions, and -1 denotes a numbered cell of unknown value.
+pub fn solve_canal_view(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    solver.add_expr(!is_black.conv2d_and((2, 2)));
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(!is_black.at((y, x)));
+                if n >= 0 {
+                    let up = is_black
+                        .slice_fixed_x((..y, x))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let down = is_black
+                        .slice_fixed_x(((y + 1).., x))
+                        .consecutive_prefix_true();
+                    let left = is_black
+                        .slice_fixed_y((y, ..x))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let right = is_black
+                        .slice_fixed_y((y, (x + 1)..))
+                        .consecutive_prefix_true();
+                    solver.add_expr((up + down + left + right).eq(n));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "canal", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["canal", "canal_view"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 4]; 5];
+        ret[0][1] = Some(3);
+        ret[3][1] = Some(4);
+        ret[3][3] = Some(1);
+        ret[4][3] = Some(0);
+        ret
+    }
+
+    #[test]
+    fn test_canal_view_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_canal_view(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [1, 0, 1, 1],
+            [1, 0, 1, 0],
+            [1, 1, 1, 0],
+            [1, 0, 1, 0],
+            [1, 1, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        // the clue at (3, 1) sees 1 shaded cell above, 1 below, 1 to the left and 1 to the right
+        assert_eq!(ans[2][1], Some(true));
+        assert_eq!(ans[1][1], Some(false));
+        assert_eq!(ans[4][1], Some(true));
+        assert_eq!(ans[3][0], Some(true));
+        assert_eq!(ans[3][2], Some(true));
+        assert_eq!(ans[3][3], Some(false));
+    }
+
+    #[test]
+    fn test_canal_view_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?canal/4/5/g3q4g1i0";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
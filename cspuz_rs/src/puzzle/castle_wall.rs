@@ -5,7 +5,7 @@ use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, NumberedArrowCombinator,
     Optionalize, Spaces, Tuple2,
 };
-use crate::solver::{Solver, FALSE};
+use crate::solver::{SolveContext, Solver, FALSE};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Side {
@@ -16,6 +16,7 @@ pub enum Side {
 
 pub fn solve_castle_wall(
     clues: &[Vec<Option<(Side, NumberedArrow)>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -114,7 +115,7 @@ pub fn solve_castle_wall(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<Option<(Side, NumberedArrow)>>>;
@@ -168,7 +169,7 @@ mod tests {
     #[test]
     fn test_castle_wall_problem() {
         let problem = problem_for_tests();
-        let ans = solve_castle_wall(&problem);
+        let ans = solve_castle_wall(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         assert_eq!(ans.horizontal[4][7], Some(true));
@@ -0,0 +1,161 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Chained Block problem. Orthogonally connected shaded cells form a block, and a clue
+/// is a shaded cell giving the size of its block (-1 if unknown). Blocks of different sizes do not
+/// touch even diagonally, and the blocks of each size are chained into one group by diagonal
+/// contacts.
+pub fn solve_chained_block(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    // the size of the block containing each shaded cell (or of the unshaded area for unshaded
+    // cells)
+    let size = &solver.int_var_2d((h, w), 1, (h * w) as i32);
+    let is_border = graph::BoolInnerGridEdges::new(&mut solver, (h, w));
+    solver.add_expr(
+        (is_black.slice((.., ..(w - 1))) ^ is_black.slice((.., 1..))).iff(&is_border.vertical),
+    );
+    solver.add_expr(
+        (is_black.slice((..(h - 1), ..)) ^ is_black.slice((1.., ..))).iff(&is_border.horizontal),
+    );
+    graph::graph_division_2d(&mut solver, size, &is_border);
+
+    // shaded cells touching diagonally belong to blocks of the same size
+    solver.add_expr(
+        (is_black.slice((..(h - 1), ..(w - 1))) & is_black.slice((1.., 1..))).imp(
+            size.slice((..(h - 1), ..(w - 1)))
+                .eq(size.slice((1.., 1..))),
+        ),
+    );
+    solver.add_expr(
+        (is_black.slice((..(h - 1), 1..)) & is_black.slice((1.., ..(w - 1)))).imp(
+            size.slice((..(h - 1), 1..))
+                .eq(size.slice((1.., ..(w - 1)))),
+        ),
+    );
+
+    // for each size, its shaded cells are connected through orthogonal and diagonal adjacency
+    let mut g = graph::Graph::new(h * w);
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 {
+                g.add_edge(y * w + x, (y + 1) * w + x);
+            }
+            if x < w - 1 {
+                g.add_edge(y * w + x, y * w + x + 1);
+            }
+            if y < h - 1 && x < w - 1 {
+                g.add_edge(y * w + x, (y + 1) * w + x + 1);
+            }
+            if y < h - 1 && x > 0 {
+                g.add_edge(y * w + x, (y + 1) * w + x - 1);
+            }
+        }
+    }
+    let is_black_flat = &is_black.flatten();
+    let size_flat = &size.flatten();
+    // a size needs to be chained only if it can have two blocks, which take 2n cells besides the
+    // blocks of the other sizes given by the clues
+    let mut clue_sizes = clues
+        .iter()
+        .flatten()
+        .filter_map(|&c| c.filter(|&n| n > 0))
+        .collect::<Vec<_>>();
+    clue_sizes.sort();
+    clue_sizes.dedup();
+    let clue_sizes_total = clue_sizes.iter().sum::<i32>();
+    for n in 1..=((h * w) as i32) {
+        let other_sizes_total = clue_sizes_total - if clue_sizes.contains(&n) { n } else { 0 };
+        if 2 * n + other_sizes_total > (h * w) as i32 {
+            continue;
+        }
+        graph::active_vertices_connected(&mut solver, is_black_flat & size_flat.eq(n), &g);
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(is_black.at((y, x)));
+                if n > 0 {
+                    solver.add_expr(size.at((y, x)).eq(n));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "chainedb", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["chainedb", "chained_block"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 4];
+        ret[0][0] = Some(1);
+        ret[1][2] = Some(2);
+        ret[3][0] = Some(2);
+        ret[3][4] = Some(3);
+        ret
+    }
+
+    #[test]
+    fn test_chained_block_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_chained_block(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [1, 0, 0, 0, 0],
+            [0, 0, 1, 0, 1],
+            [0, 0, 1, 0, 1],
+            [1, 1, 0, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+
+        // the two blocks of size 2 are chained at (2, 2) and (3, 1)
+        assert_eq!(ans[2][2], Some(true));
+        assert_eq!(ans[3][1], Some(true));
+        // the block of size 3 does not touch the blocks of size 2, even diagonally
+        assert_eq!(ans[1][3], Some(false));
+        assert_eq!(ans[2][3], Some(false));
+        assert_eq!(ans[3][3], Some(false));
+    }
+
+    #[test]
+    fn test_chained_block_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?chainedb/5/4/1l2m2i3";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -3,9 +3,12 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{any, int_constant, Solver, TRUE};
+use crate::solver::{any, int_constant, SolveContext, Solver, TRUE};
 
-pub fn solve_chocobanana(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_chocobanana(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -107,7 +110,7 @@ pub fn solve_chocobanana(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bo
     aux_graph_v.push(TRUE);
     graph::active_vertices_connected(&mut solver, &aux_graph_v, &aux_graph);
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -146,7 +149,7 @@ mod tests {
     #[test]
     fn test_chocobanana_problem() {
         let problem = problem_for_tests();
-        let ans = solve_chocobanana(&problem);
+        let ans = solve_chocobanana(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_bool_2d([
@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_cocktail(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let h = borders.vertical.len();
     assert!(h > 0);
@@ -62,7 +63,7 @@ pub fn solve_cocktail(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
@@ -119,7 +120,7 @@ mod tests {
     #[test]
     fn test_cocktail_problem() {
         let (borders, clues) = problem_for_tests();
-        let ans = solve_cocktail(&borders, &clues);
+        let ans = solve_cocktail(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
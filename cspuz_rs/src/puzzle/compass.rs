@@ -4,7 +4,7 @@ use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Map, Optionalize, Seq,
     Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct CompassClue {
@@ -16,6 +16,7 @@ pub struct CompassClue {
 
 pub fn solve_compass(
     clues: &[Vec<Option<CompassClue>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -76,7 +77,7 @@ pub fn solve_compass(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(edges))
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
 }
 
 type Problem = Vec<Vec<Option<CompassClue>>>;
@@ -152,7 +153,7 @@ mod tests {
     fn test_compass_problem() {
         let problem = problem_for_tests();
 
-        let ans = solve_compass(&problem);
+        let ans = solve_compass(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
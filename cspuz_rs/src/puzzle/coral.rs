@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context_and_site, url_to_problem, Choice, Combinator, Context, HexInt,
     Optionalize, Seq, Size, Spaces,
 };
-use crate::solver::{any, count_true, BoolVarArray1D, Solver, TRUE};
+use crate::solver::{any, count_true, BoolVarArray1D, SolveContext, Solver, TRUE};
 
 pub fn solve_coral(
     clue_vertical: &[Option<Vec<i32>>],
     clue_horizontal: &[Option<Vec<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let h = clue_horizontal.len();
     let w = clue_vertical.len();
@@ -73,7 +74,7 @@ pub fn solve_coral(
             }
         }
     }
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = (Vec<Option<Vec<i32>>>, Vec<Option<Vec<i32>>>);
@@ -269,7 +270,7 @@ mod tests {
     #[test]
     fn test_coral_problem() {
         let problem = problem_for_tests();
-        let ans = solve_coral(&problem.0, &problem.1);
+        let ans = solve_coral(&problem.0, &problem.1, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
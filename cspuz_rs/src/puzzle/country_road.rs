@@ -0,0 +1,151 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use crate::solver::{count_true, SolveContext, Solver};
+
+pub fn solve_country_road(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    let rooms = graph::borders_to_rooms(borders);
+    assert_eq!(rooms.len(), clues.len());
+
+    let mut room_id = vec![vec![0; w]; h];
+    for (i, room) in rooms.iter().enumerate() {
+        for &(y, x) in room {
+            room_id[y][x] = i;
+        }
+    }
+
+    // the lines crossing the border of each room
+    let mut room_crossing = vec![vec![]; rooms.len()];
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && borders.horizontal[y][x] {
+                let line = is_line.vertical.at((y, x));
+                room_crossing[room_id[y][x]].push(line.clone());
+                room_crossing[room_id[y + 1][x]].push(line);
+                solver.add_expr(is_passed.at((y, x)) | is_passed.at((y + 1, x)));
+            }
+            if x < w - 1 && borders.vertical[y][x] {
+                let line = is_line.horizontal.at((y, x));
+                room_crossing[room_id[y][x]].push(line.clone());
+                room_crossing[room_id[y][x + 1]].push(line);
+                solver.add_expr(is_passed.at((y, x)) | is_passed.at((y, x + 1)));
+            }
+        }
+    }
+
+    for i in 0..rooms.len() {
+        // the loop passes through each room exactly once
+        if room_crossing[i].is_empty() {
+            solver.add_expr(is_passed.select(&rooms[i]).any());
+        } else {
+            solver.add_expr(count_true(&room_crossing[i]).eq(2));
+        }
+        if let Some(n) = clues[i] {
+            solver.add_expr(is_passed.select(&rooms[i]).count_true().eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ])))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "country",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["country", "country_road"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A B B B
+        // A A B B B
+        // A A B C C
+        // D E E C F
+        // E E F F F
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 1, 1],
+                [1, 1, 1, 0, 1],
+                [1, 0, 1, 1, 0],
+            ]),
+            vertical: util::tests::to_bool_2d([
+                [0, 1, 0, 0],
+                [0, 1, 0, 0],
+                [0, 1, 1, 0],
+                [1, 0, 1, 1],
+                [0, 1, 0, 0],
+            ]),
+        };
+        let clues = vec![Some(2), Some(5), None, None, Some(4), None];
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_country_road_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_country_road(&borders, &clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [0, 0, 1, 0],
+                [0, 0, 0, 0],
+                [1, 1, 0, 1],
+                [0, 1, 0, 0],
+                [1, 0, 1, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([
+                [0, 0, 1, 1, 0],
+                [0, 0, 1, 1, 0],
+                [1, 0, 0, 0, 1],
+                [1, 1, 1, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_country_road_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?country/5/5/8hlk03tm25h4g";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -5,9 +5,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
     Size, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
-pub fn solve_creek(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_creek(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h1, w1) = util::infer_shape(clues);
     let h = h1 - 1;
     let w = w1 - 1;
@@ -34,7 +37,7 @@ pub fn solve_creek(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>>
 
     graph::active_vertices_connected_2d(&mut solver, !is_black);
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -84,7 +87,7 @@ mod tests {
     #[test]
     fn test_creek_problem() {
         let problem = problem_for_tests();
-        let ans = solve_creek(&problem);
+        let ans = solve_creek(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -1,10 +1,11 @@
 use super::util;
 use crate::graph;
 use crate::serializer::{get_kudamono_url_info, lexicographic_order, Context, DecInt, Sequencer};
-use crate::solver::{any, int_constant, Solver, TRUE};
+use crate::solver::{any, int_constant, SolveContext, Solver, TRUE};
 
 pub fn solve_crosswall(
     clues: &[Vec<Option<(i32, i32)>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -155,7 +156,7 @@ pub fn solve_crosswall(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<Option<(i32, i32)>>>;
@@ -242,7 +243,7 @@ mod tests {
     #[test]
     fn test_crosswall_problem() {
         let clues = problem_for_tests();
-        let ans = solve_crosswall(&clues);
+        let ans = solve_crosswall(&clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -3,7 +3,7 @@ use crate::graph::{self, BoolGridEdges};
 use crate::serializer::{
     strip_prefix, Choice, Combinator, Context, ContextBasedGrid, Dict, HexInt, Map, Rooms, Spaces,
 };
-use crate::solver::{IntVarArray2D, Solver};
+use crate::solver::{IntVarArray2D, SolveContext, Solver};
 
 type AdjacencyEntry = Option<(usize, usize)>;
 
@@ -36,6 +36,7 @@ pub fn solve_curvedata(
     piece_id: &[Vec<PieceId>],
     borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
     pieces: &[graph::GridEdges<Vec<Vec<bool>>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(piece_id);
 
@@ -46,7 +47,7 @@ pub fn solve_curvedata(
 
     add_constraints(&mut solver, is_line, piece_id, borders, pieces);
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 pub fn enumerate_answers_curvedata(
@@ -417,7 +418,7 @@ mod tests {
     #[test]
     fn test_curvedata_problem() {
         let (piece_id, borders, pieces) = problem_for_tests();
-        let ans = solve_curvedata(&piece_id, &borders, &pieces);
+        let ans = solve_curvedata(&piece_id, &borders, &pieces, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,140 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use crate::solver::{any, SolveContext, Solver};
+
+pub fn solve_detour(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+    solver.add_expr(is_passed);
+
+    // the loop turns at a cell iff it has both a horizontal and a vertical line there
+    let is_turn = &solver.bool_var_2d((h, w));
+    for y in 0..h {
+        for x in 0..w {
+            let mut horizontal = vec![];
+            if x > 0 {
+                horizontal.push(is_line.horizontal.at((y, x - 1)));
+            }
+            if x < w - 1 {
+                horizontal.push(is_line.horizontal.at((y, x)));
+            }
+            let mut vertical = vec![];
+            if y > 0 {
+                vertical.push(is_line.vertical.at((y - 1, x)));
+            }
+            if y < h - 1 {
+                vertical.push(is_line.vertical.at((y, x)));
+            }
+            solver.add_expr(is_turn.at((y, x)).iff(any(horizontal) & any(vertical)));
+        }
+    }
+
+    let rooms = graph::borders_to_rooms(borders);
+    assert_eq!(rooms.len(), clues.len());
+    for (room, &clue) in rooms.iter().zip(clues) {
+        if let Some(n) = clue {
+            solver.add_expr(is_turn.select(room).count_true().eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ])))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "detour",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["detour"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A B B
+        // C C B B
+        // C C C B
+        // C C C B
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[1, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 0]]),
+            vertical: util::tests::to_bool_2d([[0, 1, 0], [0, 1, 0], [0, 0, 1], [0, 0, 1]]),
+        };
+        (borders, vec![None, Some(4), Some(6)])
+    }
+
+    #[test]
+    fn test_detour_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_detour(&borders, &clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [1, 0, 1],
+                [0, 1, 0],
+                [0, 1, 0],
+                [1, 0, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([[1, 1, 1, 1], [1, 0, 0, 1], [1, 1, 1, 1]]),
+        };
+        assert_eq!(ans, expected);
+
+        // count the turns in each room
+        let rooms = graph::borders_to_rooms(&borders);
+        let n_turns = rooms
+            .iter()
+            .map(|room| {
+                room.iter()
+                    .filter(|&&(y, x)| {
+                        let horizontal = (x > 0 && ans.horizontal[y][x - 1] == Some(true))
+                            || (x < 3 && ans.horizontal[y][x] == Some(true));
+                        let vertical = (y > 0 && ans.vertical[y - 1][x] == Some(true))
+                            || (y < 3 && ans.vertical[y][x] == Some(true));
+                        horizontal && vertical
+                    })
+                    .count()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(n_turns, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_detour_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?detour/4/4/928o80g46";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
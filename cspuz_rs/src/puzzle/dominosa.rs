@@ -0,0 +1,121 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{problem_to_url, url_to_problem, Combinator, Grid, HexInt};
+use crate::solver::{count_true, BoolExpr, SolveContext, Solver};
+
+/// Solves a Dominosa problem: the grid is divided into dominoes so that each pair of numbers
+/// `(i, j)` with `0 <= i <= j <= max` appears on exactly one domino, where `max` is the largest
+/// number in the grid.
+pub fn solve_dominosa(
+    clues: &[Vec<i32>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_border = graph::BoolInnerGridEdges::new(&mut solver, (h, w));
+    solver.add_answer_key_bool(&is_border.horizontal);
+    solver.add_answer_key_bool(&is_border.vertical);
+
+    if clues.iter().flatten().any(|&n| n < 0) {
+        return None;
+    }
+    let max_pip = clues.iter().flatten().copied().max().unwrap_or(0);
+    let n_pips = max_pip as usize + 1;
+
+    // the dominoes covering each cell, and the dominoes of each pair of numbers
+    let mut cell_dominoes: Vec<Vec<Vec<BoolExpr>>> = vec![vec![vec![]; w]; h];
+    let mut pair_dominoes: Vec<Vec<Vec<BoolExpr>>> = vec![vec![vec![]; n_pips]; n_pips];
+    for y in 0..h {
+        for x in 0..w {
+            let mut neighbors = vec![];
+            if y < h - 1 {
+                neighbors.push(((y + 1, x), !is_border.horizontal.at((y, x))));
+            }
+            if x < w - 1 {
+                neighbors.push(((y, x + 1), !is_border.vertical.at((y, x))));
+            }
+            for ((y2, x2), domino) in neighbors {
+                cell_dominoes[y][x].push(domino.clone());
+                cell_dominoes[y2][x2].push(domino.clone());
+                let a = clues[y][x].min(clues[y2][x2]) as usize;
+                let b = clues[y][x].max(clues[y2][x2]) as usize;
+                pair_dominoes[a][b].push(domino);
+            }
+        }
+    }
+
+    for dominoes in cell_dominoes.iter().flatten() {
+        solver.add_expr(count_true(dominoes).eq(1));
+    }
+    for (a, row) in pair_dominoes.iter().enumerate() {
+        for dominoes in &row[a..] {
+            solver.add_expr(count_true(dominoes).eq(1));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(&is_border))
+}
+
+type Problem = Vec<Vec<i32>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(HexInt)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "dominosa", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["dominosa"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        vec![vec![1, 2, 2, 0], vec![2, 0, 0, 2], vec![1, 0, 1, 1]]
+    }
+
+    #[test]
+    fn test_dominosa_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_dominosa(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolInnerGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([[0, 1, 1, 0], [1, 1, 1, 1]]),
+            vertical: util::tests::to_option_bool_2d([[1, 0, 1], [1, 0, 1], [0, 1, 0]]),
+        };
+        assert_eq!(ans, expected);
+
+        // each pair of numbers appears exactly once
+        let mut pairs = vec![];
+        for y in 0..3 {
+            for x in 0..4 {
+                if y < 2 && ans.horizontal[y][x] == Some(false) {
+                    pairs.push((problem[y][x], problem[y + 1][x]));
+                }
+                if x < 3 && ans.vertical[y][x] == Some(false) {
+                    pairs.push((problem[y][x], problem[y][x + 1]));
+                }
+            }
+        }
+        let mut pairs = pairs
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<_>>();
+        pairs.sort();
+        assert_eq!(pairs, vec![(0, 0), (0, 1), (0, 2), (1, 1), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_dominosa_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?dominosa/4/3/122020021011";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
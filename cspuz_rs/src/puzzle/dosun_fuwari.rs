@@ -0,0 +1,144 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
+    MultiDigit, Rooms, Size, Tuple2,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Dosun-Fuwari problem. Returns whether each cell has a balloon and whether each cell
+/// has an iron ball.
+pub fn solve_dosun_fuwari(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    is_black: &[Vec<bool>],
+    ctx: &mut SolveContext,
+) -> Option<(Vec<Vec<Option<bool>>>, Vec<Vec<Option<bool>>>)> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_balloon = &solver.bool_var_2d((h, w));
+    let is_iron = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_balloon);
+    solver.add_answer_key_bool(is_iron);
+
+    solver.add_expr(!(is_balloon & is_iron));
+    for y in 0..h {
+        for x in 0..w {
+            if is_black[y][x] {
+                solver.add_expr(!is_balloon.at((y, x)));
+                solver.add_expr(!is_iron.at((y, x)));
+                continue;
+            }
+            // a balloon rests against the top edge, a black cell or another balloon, and an iron
+            // ball against the bottom edge, a black cell or another iron ball
+            if y > 0 && !is_black[y - 1][x] {
+                solver.add_expr(is_balloon.at((y, x)).imp(is_balloon.at((y - 1, x))));
+            }
+            if y < h - 1 && !is_black[y + 1][x] {
+                solver.add_expr(is_iron.at((y, x)).imp(is_iron.at((y + 1, x))));
+            }
+        }
+    }
+
+    let rooms = graph::borders_to_rooms(borders);
+    for room in &rooms {
+        solver.add_expr(is_balloon.select(room).count_true().eq(1));
+        solver.add_expr(is_iron.select(room).count_true().eq(1));
+    }
+
+    solver
+        .irrefutable_facts(ctx)
+        .map(|f| (f.get(is_balloon), f.get(is_iron)))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<bool>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(2, 5),
+            |x: bool| Some(if x { 1 } else { 0 }),
+            |n: i32| Some(n == 1),
+        )),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "dosufuwa",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["dosufuwa", "dosun_fuwari"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A B B
+        // A A A B
+        // C C A A
+        // C C C C
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 0, 1, 0], [1, 1, 0, 1], [0, 0, 1, 1]]),
+            vertical: util::tests::to_bool_2d([[0, 1, 0], [0, 0, 1], [0, 1, 0], [0, 0, 0]]),
+        };
+        let mut is_black = vec![vec![false; 4]; 4];
+        is_black[1][3] = true;
+        (borders, is_black)
+    }
+
+    #[test]
+    fn test_dosun_fuwari_problem() {
+        let (borders, is_black) = problem_for_tests();
+        let ans = solve_dosun_fuwari(&borders, &is_black, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let (is_balloon, is_iron) = ans.unwrap();
+
+        let expected_balloon = util::tests::to_option_bool_2d([
+            [0, 0, 1, 0],
+            [0, 0, 0, 0],
+            [0, 0, 0, 1],
+            [0, 0, 0, 1],
+        ]);
+        let expected_iron = util::tests::to_option_bool_2d([
+            [0, 0, 0, 1],
+            [0, 0, 0, 0],
+            [0, 0, 1, 0],
+            [0, 0, 1, 0],
+        ]);
+        assert_eq!(is_balloon, expected_balloon);
+        assert_eq!(is_iron, expected_iron);
+
+        // each room has exactly one balloon and one iron ball
+        let rooms = graph::borders_to_rooms(&borders);
+        for room in &rooms {
+            let n_balloon = room
+                .iter()
+                .filter(|&&(y, x)| is_balloon[y][x] == Some(true))
+                .count();
+            let n_iron = room
+                .iter()
+                .filter(|&&(y, x)| is_iron[y][x] == Some(true))
+                .count();
+            assert_eq!(n_balloon, 1);
+            assert_eq!(n_iron, 1);
+        }
+    }
+
+    #[test]
+    fn test_dosun_fuwari_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?dosufuwa/4/4/8k05ko0400";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
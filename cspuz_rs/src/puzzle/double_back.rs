@@ -0,0 +1,165 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
+    MultiDigit, Rooms, Size, Tuple2,
+};
+use crate::solver::{count_true, SolveContext, Solver};
+
+pub fn solve_double_back(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    is_blocked: &[Vec<bool>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+    for y in 0..h {
+        for x in 0..w {
+            solver.add_expr(is_passed.at((y, x)) ^ is_blocked[y][x]);
+        }
+    }
+
+    let rooms = graph::borders_to_rooms(borders);
+    let mut room_id = vec![vec![0; w]; h];
+    for (i, room) in rooms.iter().enumerate() {
+        for &(y, x) in room {
+            room_id[y][x] = i;
+        }
+    }
+
+    // the loop enters each room twice, so it crosses the border of the room 4 times
+    let mut room_crossing = vec![vec![]; rooms.len()];
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && borders.horizontal[y][x] {
+                let line = is_line.vertical.at((y, x));
+                room_crossing[room_id[y][x]].push(line.clone());
+                room_crossing[room_id[y + 1][x]].push(line);
+            }
+            if x < w - 1 && borders.vertical[y][x] {
+                let line = is_line.horizontal.at((y, x));
+                room_crossing[room_id[y][x]].push(line.clone());
+                room_crossing[room_id[y][x + 1]].push(line);
+            }
+        }
+    }
+    for crossing in &room_crossing {
+        solver.add_expr(count_true(crossing).eq(4));
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<bool>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(2, 5),
+            |x: bool| Some(if x { 1 } else { 0 }),
+            |n: i32| Some(n == 1),
+        )),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "doubleback",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["doubleback", "double_back"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A A B B
+        // A A B B B
+        // A A C C B
+        // D D D D D
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([
+                [0, 0, 1, 0, 0],
+                [0, 0, 1, 1, 0],
+                [1, 1, 1, 1, 1],
+            ]),
+            vertical: util::tests::to_bool_2d([
+                [0, 0, 1, 0],
+                [0, 1, 0, 0],
+                [0, 1, 0, 1],
+                [0, 0, 0, 0],
+            ]),
+        };
+        let mut is_blocked = vec![vec![false; 5]; 4];
+        is_blocked[0][0] = true;
+        is_blocked[3][2] = true;
+        (borders, is_blocked)
+    }
+
+    #[test]
+    fn test_double_back_problem() {
+        let (borders, is_blocked) = problem_for_tests();
+        let ans = solve_double_back(&borders, &is_blocked, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [0, 1, 1, 1],
+                [1, 0, 1, 0],
+                [0, 1, 0, 0],
+                [1, 0, 0, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([
+                [0, 1, 0, 0, 1],
+                [1, 0, 1, 1, 1],
+                [1, 1, 0, 1, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+
+        // the loop visits each room exactly twice
+        let rooms = graph::borders_to_rooms(&borders);
+        for room in &rooms {
+            let mut n_crossing = 0;
+            for &(y, x) in room {
+                if y > 0 && !room.contains(&(y - 1, x)) && ans.vertical[y - 1][x] == Some(true) {
+                    n_crossing += 1;
+                }
+                if y < 3 && !room.contains(&(y + 1, x)) && ans.vertical[y][x] == Some(true) {
+                    n_crossing += 1;
+                }
+                if x > 0 && !room.contains(&(y, x - 1)) && ans.horizontal[y][x - 1] == Some(true) {
+                    n_crossing += 1;
+                }
+                if x < 4 && !room.contains(&(y, x + 1)) && ans.horizontal[y][x] == Some(true) {
+                    n_crossing += 1;
+                }
+            }
+            assert_eq!(n_crossing, 4);
+        }
+    }
+
+    #[test]
+    fn test_double_back_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?doubleback/5/4/4h8046vg004";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
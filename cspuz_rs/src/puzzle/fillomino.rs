@@ -3,10 +3,11 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_fillomino(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<(
     Vec<Vec<Option<i32>>>,
     graph::BoolInnerGridEdgesIrrefutableFacts,
@@ -42,7 +43,7 @@ pub fn solve_fillomino(
     }
 
     solver
-        .irrefutable_facts()
+        .irrefutable_facts(ctx)
         .map(|f| (f.get(num), f.get(&is_border)))
 }
 
@@ -81,7 +82,7 @@ mod tests {
     #[test]
     fn test_fillomino_problem() {
         let problem = problem_for_tests();
-        let ans = solve_fillomino(&problem);
+        let ans = solve_fillomino(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_2d([
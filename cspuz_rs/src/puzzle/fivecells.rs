@@ -1,10 +1,11 @@
 use super::util;
 use crate::graph;
 use crate::serializer::{problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, Spaces};
-use crate::solver::{count_true, int_constant, Solver};
+use crate::solver::{count_true, int_constant, SolveContext, Solver};
 
 pub fn solve_fivecells(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -80,7 +81,7 @@ pub fn solve_fivecells(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(&is_border))
+    solver.irrefutable_facts(ctx).map(|f| f.get(&is_border))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -124,7 +125,7 @@ mod tests {
     #[test]
     fn test_fivecells_problem() {
         let problem = problem_for_tests();
-        let ans = solve_fivecells(&problem);
+        let ans = solve_fivecells(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         #[rustfmt::skip]
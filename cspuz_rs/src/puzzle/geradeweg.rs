@@ -0,0 +1,157 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Geradeweg problem. A clue of -1 denotes a numbered cell of unknown value, whose
+/// two straight lines have the same length if the loop turns there.
+pub fn solve_geradeweg(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(is_passed.at((y, x)));
+
+                let up = is_line
+                    .vertical
+                    .slice_fixed_x((..y, x))
+                    .reverse()
+                    .consecutive_prefix_true();
+                let down = is_line
+                    .vertical
+                    .slice_fixed_x((y.., x))
+                    .consecutive_prefix_true();
+                let left = is_line
+                    .horizontal
+                    .slice_fixed_y((y, ..x))
+                    .reverse()
+                    .consecutive_prefix_true();
+                let right = is_line
+                    .horizontal
+                    .slice_fixed_y((y, x..))
+                    .consecutive_prefix_true();
+
+                // if the loop goes straight, the line through the clue is counted as a whole;
+                // otherwise exactly one of the two terms in each sum is nonzero
+                let vertical = up + down;
+                let horizontal = left + right;
+                if n > 0 {
+                    solver.add_expr(vertical.eq(0) | vertical.eq(n));
+                    solver.add_expr(horizontal.eq(0) | horizontal.eq(n));
+                } else {
+                    solver.add_expr(
+                        vertical.eq(0) | horizontal.eq(0) | vertical.eq(horizontal.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "geradeweg", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["geradeweg"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[1][1] = Some(2);
+        ret[1][3] = Some(2);
+        ret[2][1] = Some(3);
+        ret
+    }
+
+    #[test]
+    fn test_geradeweg_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_geradeweg(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [0, 0, 1, 0],
+                [1, 1, 0, 0],
+                [1, 1, 1, 0],
+                [0, 0, 0, 0],
+                [0, 0, 0, 0],
+            ]),
+            vertical: util::tests::to_option_bool_2d([
+                [0, 0, 1, 1, 0],
+                [1, 0, 0, 1, 0],
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0],
+            ]),
+        };
+        assert_eq!(ans, expected);
+
+        // the straight lines through (1, 1), (1, 3) and (2, 1) have the clued lengths
+        let horizontal_run = |y: usize, x: usize| {
+            let mut left = x;
+            while left > 0 && ans.horizontal[y][left - 1] == Some(true) {
+                left -= 1;
+            }
+            let mut right = x;
+            while right < 4 && ans.horizontal[y][right] == Some(true) {
+                right += 1;
+            }
+            right - left
+        };
+        let vertical_run = |y: usize, x: usize| {
+            let mut up = y;
+            while up > 0 && ans.vertical[up - 1][x] == Some(true) {
+                up -= 1;
+            }
+            let mut down = y;
+            while down < 4 && ans.vertical[down][x] == Some(true) {
+                down += 1;
+            }
+            down - up
+        };
+        assert_eq!(horizontal_run(1, 1), 2);
+        assert_eq!(vertical_run(1, 1), 0);
+        assert_eq!(horizontal_run(1, 3), 0);
+        assert_eq!(vertical_run(1, 3), 2);
+        assert_eq!(horizontal_run(2, 1), 3);
+        assert_eq!(vertical_run(2, 1), 0);
+    }
+
+    #[test]
+    fn test_geradeweg_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?geradeweg/5/5/l2g2h3s";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,200 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    DecInt, Dict, HexInt, Optionalize, Rooms, Size, Spaces, Tuple2, Tuple3,
+};
+use crate::solver::{any, count_true, BoolExpr, SolveContext, Solver};
+
+/// Solves a Haisu problem. The path goes from `start` to `goal` through every cell, and a clue
+/// `n` in a room means that the path visits the clue cell during its `n`-th entry into the room.
+pub fn solve_haisu(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let rooms = graph::borders_to_rooms(borders);
+    let mut room_id = vec![vec![0; w]; h];
+    for (i, room) in rooms.iter().enumerate() {
+        for &(y, x) in room {
+            room_id[y][x] = i;
+        }
+    }
+
+    // the position of each cell along the path
+    let order = &solver.int_var_2d((h, w), 0, (h * w - 1) as i32);
+    solver.add_expr(order.at(start).eq(0));
+    // whether the path enters a new room at each cell
+    let is_entry = &solver.bool_var_2d((h, w));
+    solver.add_expr(is_entry.at(start));
+
+    for y in 0..h {
+        for x in 0..w {
+            let degree = count_true(is_line.vertex_neighbors((y, x)));
+            if (y, x) == start || (y, x) == goal {
+                solver.add_expr(degree.eq(1));
+            } else {
+                solver.add_expr(degree.eq(2));
+            }
+            if (y, x) == start {
+                continue;
+            }
+
+            // each cell but the start is reached from a neighbor with the preceding position,
+            // which rules out cycles apart from the path
+            let mut preceded_by = vec![];
+            let mut entered_from = vec![];
+            for (y2, x2) in order.four_neighbor_indices((y, x)) {
+                let edge = if y == y2 {
+                    is_line.horizontal.at((y, x.min(x2)))
+                } else {
+                    is_line.vertical.at((y.min(y2), x))
+                };
+                let is_pred: BoolExpr = edge & order.at((y2, x2)).eq(order.at((y, x)) - 1);
+                if room_id[y][x] != room_id[y2][x2] {
+                    entered_from.push(is_pred.clone());
+                }
+                preceded_by.push(is_pred);
+            }
+            solver.add_expr(any(preceded_by));
+            solver.add_expr(is_entry.at((y, x)).iff(any(entered_from)));
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                let room = &rooms[room_id[y][x]];
+                solver.add_expr(
+                    count_true(
+                        room.iter()
+                            .map(|&p| is_entry.at(p) & order.at(p).le(order.at((y, x)))),
+                    )
+                    .eq(n),
+                );
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+/// A cell, serialized as `/` followed by its index in the row-major order.
+struct PositionCombinator;
+
+impl Combinator<(usize, usize)> for PositionCombinator {
+    fn serialize(&self, ctx: &Context, input: &[(usize, usize)]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let width = ctx.width?;
+        let (y, x) = input[0];
+        let (_, mut ret) = DecInt.serialize(ctx, &[(y * width + x) as i32])?;
+        ret.insert(0, b'/');
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<(usize, usize)>)> {
+        let width = ctx.width?;
+        let (n_read, _) = Dict::new((), "/").deserialize(ctx, input)?;
+        let (n_read2, idx) = DecInt.deserialize(ctx, &input[n_read..])?;
+        if idx[0] < 0 || idx[0] as usize >= ctx.height? * width {
+            return None;
+        }
+        let idx = idx[0] as usize;
+        Some((n_read + n_read2, vec![(idx / width, idx % width)]))
+    }
+}
+
+type Problem = (
+    graph::InnerGridEdges<Vec<Vec<bool>>>,
+    Vec<Vec<Option<i32>>>,
+    ((usize, usize), (usize, usize)),
+);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple3::new(
+        Rooms,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+        Tuple2::new(PositionCombinator, PositionCombinator),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "haisu",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["haisu"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A A B
+        // A A A B
+        // C C D B
+        // C C B B
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 0, 0, 0], [1, 1, 1, 0], [0, 0, 1, 0]]),
+            vertical: util::tests::to_bool_2d([[0, 0, 1], [0, 0, 1], [0, 1, 1], [0, 1, 0]]),
+        };
+        let mut clues = vec![vec![None; 4]; 4];
+        clues[2][0] = Some(1);
+        clues[2][1] = Some(2);
+        (borders, clues, ((0, 3), (1, 3)))
+    }
+
+    #[test]
+    fn test_haisu_problem() {
+        let (borders, clues, (start, goal)) = problem_for_tests();
+        let ans = solve_haisu(&borders, start, goal, &clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [1, 1, 1],
+                [0, 1, 1],
+                [0, 1, 1],
+                [1, 1, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([[1, 0, 0, 0], [1, 1, 0, 0], [1, 0, 0, 1]]),
+        };
+        assert_eq!(ans, expected);
+
+        // the path enters room C first at (2, 0) and again at (2, 1), coming from room D
+        assert_eq!(ans.vertical[1][0], Some(true));
+        assert_eq!(ans.horizontal[2][0], Some(false));
+        assert_eq!(ans.horizontal[2][1], Some(true));
+        assert_eq!(ans.vertical[2][1], Some(false));
+    }
+
+    #[test]
+    fn test_haisu_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?haisu/4/4/4mg1ogn12l/3/7";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -3,9 +3,12 @@ use crate::graph::{self, GridEdges};
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{sum, Solver};
+use crate::solver::{sum, SolveContext, Solver};
 
-pub fn solve_hashi(clues: &[Vec<Option<i32>>]) -> Option<GridEdges<Vec<Vec<Option<i32>>>>> {
+pub fn solve_hashi(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<GridEdges<Vec<Vec<Option<i32>>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -77,7 +80,7 @@ pub fn solve_hashi(clues: &[Vec<Option<i32>>]) -> Option<GridEdges<Vec<Vec<Optio
     let (edges, g) = is_connected.representation();
     graph::active_vertices_connected(&mut solver, edges, &g.line_graph());
 
-    solver.irrefutable_facts().map(|f| GridEdges {
+    solver.irrefutable_facts(ctx).map(|f| GridEdges {
         horizontal: f.get(answer_horizontal),
         vertical: f.get(answer_vertical),
     })
@@ -119,7 +122,7 @@ mod tests {
     #[test]
     fn test_hashi_problem() {
         let problem = problem_for_tests();
-        let ans = solve_hashi(&problem);
+        let ans = solve_hashi(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -4,11 +4,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
     Dict, HexInt, Map, MultiDigit, Optionalize, Size, Spaces, Tuple2,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_herugolf(
     pond: &[Vec<bool>],
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(&clues);
 
@@ -157,7 +158,7 @@ pub fn solve_herugolf(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = (Vec<Vec<bool>>, Vec<Vec<Option<i32>>>);
@@ -221,7 +222,7 @@ mod tests {
     #[test]
     fn test_herugolf_problem() {
         let (pond, clues) = problem_for_tests();
-        let ans = solve_herugolf(&pond, &clues);
+        let ans = solve_herugolf(&pond, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         
@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, BoolVarArray2D, Solver};
+use crate::solver::{count_true, BoolVarArray2D, SolveContext, Solver};
 
 pub fn solve_heyawake(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = borders.base_shape();
 
@@ -17,7 +18,7 @@ pub fn solve_heyawake(
 
     add_constraints(&mut solver, is_black, borders, clues);
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 pub fn enumerate_answers_heyawake(
@@ -42,7 +43,7 @@ pub fn enumerate_answers_heyawake(
         .collect()
 }
 
-pub(super) fn add_constraints(
+pub fn add_constraints(
     solver: &mut Solver,
     is_black: &BoolVarArray2D,
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
@@ -131,7 +132,7 @@ mod tests {
         assert_eq!(serialize_problem(&problem), Some(String::from(url)));
         let (borders, clues) = problem;
 
-        let ans = solve_heyawake(&borders, &clues);
+        let ans = solve_heyawake(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
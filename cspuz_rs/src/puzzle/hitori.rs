@@ -0,0 +1,92 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+pub fn solve_hitori(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    solver.add_expr(!(is_black.slice((1.., ..)) & is_black.slice((..(h - 1), ..))));
+    solver.add_expr(!(is_black.slice((.., 1..)) & is_black.slice((.., ..(w - 1)))));
+    graph::active_vertices_connected_2d(&mut solver, !is_black);
+
+    // among the cells with the same number in a row or a column, at most one is unshaded
+    for y in 0..h {
+        for x in 0..w {
+            let n = match clues[y][x] {
+                Some(n) if n > 0 => n,
+                _ => continue,
+            };
+            for y2 in (y + 1)..h {
+                if clues[y2][x] == Some(n) {
+                    solver.add_expr(is_black.at((y, x)) | is_black.at((y2, x)));
+                }
+            }
+            for x2 in (x + 1)..w {
+                if clues[y][x2] == Some(n) {
+                    solver.add_expr(is_black.at((y, x)) | is_black.at((y, x2)));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "hitori", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["hitori"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        util::tests::to_option_2d([[2, 4, 1, 1], [4, 1, 2, 3], [2, 1, 4, 1], [1, 3, 2, 3]])
+    }
+
+    #[test]
+    fn test_hitori_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_hitori(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::puzzle::util::tests::to_option_bool_2d([
+            [1, 0, 0, 1],
+            [0, 0, 1, 0],
+            [0, 1, 0, 0],
+            [0, 0, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_hitori_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?hitori/4/4/2411412321411323";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -4,11 +4,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
     HexInt, Map, MultiDigit, Optionalize, Size, Spaces, Tuple2,
 };
-use crate::solver::{count_true, BoolExpr, Solver};
+use crate::solver::{count_true, BoolExpr, SolveContext, Solver};
 
 pub fn solve_icewalk(
     icebarn: &[Vec<bool>],
     num: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(icebarn);
 
@@ -128,7 +129,7 @@ pub fn solve_icewalk(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = (Vec<Vec<bool>>, Vec<Vec<Option<i32>>>);
@@ -191,7 +192,7 @@ mod tests {
     #[test]
     fn test_icewalk_problem() {
         let (icebarn, num) = problem_for_tests();
-        let ans = solve_icewalk(&icebarn, &num);
+        let ans = solve_icewalk(&icebarn, &num, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,102 @@
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, OutsideClues,
+    OutsideCluesCombinator, Size,
+};
+use crate::solver::{sum, SolveContext, Solver};
+
+/// Solves a Kakurasu problem. The clue of a column is the sum of the (1-based) row indices of its
+/// shaded cells, and the clue of a row is the sum of the column indices of its shaded cells.
+pub fn solve_kakurasu(
+    clue_vertical: &[Option<i32>],
+    clue_horizontal: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let h = clue_horizontal.len();
+    let w = clue_vertical.len();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(sum((0..h).map(|y| is_black.at((y, x)).ite(y as i32 + 1, 0))).eq(n));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(sum((0..w).map(|x| is_black.at((y, x)).ite(x as i32 + 1, 0))).eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = OutsideClues;
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(OutsideCluesCombinator)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.1.len();
+    let width = problem.0.len();
+    problem_to_url_with_context(
+        combinator(),
+        "kakurasu",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["kakurasu"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let clue_vertical = vec![Some(6), Some(5), Some(8), Some(2)];
+        let clue_horizontal = vec![Some(3), Some(7), Some(5), Some(4)];
+        (clue_vertical, clue_horizontal)
+    }
+
+    #[test]
+    fn test_kakurasu_problem() {
+        let (clue_vertical, clue_horizontal) = problem_for_tests();
+        let ans = solve_kakurasu(&clue_vertical, &clue_horizontal, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 0, 1, 0],
+            [1, 1, 0, 1],
+            [0, 1, 1, 0],
+            [1, 0, 1, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        for i in 0..4 {
+            let column_sum = (0..4)
+                .filter(|&y| ans[y][i] == Some(true))
+                .map(|y| y as i32 + 1)
+                .sum::<i32>();
+            assert_eq!(Some(column_sum), clue_vertical[i]);
+            let row_sum = (0..4)
+                .filter(|&x| ans[i][x] == Some(true))
+                .map(|x| x as i32 + 1)
+                .sum::<i32>();
+            assert_eq!(Some(row_sum), clue_horizontal[i]);
+        }
+    }
+
+    #[test]
+    fn test_kakurasu_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?kakurasu/4/4/65823754";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
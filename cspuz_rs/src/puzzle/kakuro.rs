@@ -0,0 +1,137 @@
+use super::util;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+    Tuple2,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// A black cell of a Kakuro problem, holding the clues of the run below it and of the run to its
+/// right, in this order.
+pub type KakuroClue = (Option<i32>, Option<i32>);
+
+pub fn solve_kakuro(
+    clues: &[Vec<Option<KakuroClue>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((h, w), 0, 9);
+    solver.add_answer_key_int(num);
+
+    for y in 0..h {
+        for x in 0..w {
+            if clues[y][x].is_some() {
+                solver.add_expr(num.at((y, x)).eq(0));
+            } else {
+                solver.add_expr(num.at((y, x)).ne(0));
+            }
+        }
+    }
+
+    // horizontal runs
+    for y in 0..h {
+        let mut x = 0;
+        while x < w {
+            if clues[y][x].is_some() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < w && clues[y][x].is_none() {
+                x += 1;
+            }
+            let run = num.slice_fixed_y((y, start..x));
+            solver.all_different(&run);
+            if start > 0 {
+                if let Some((_, Some(n))) = clues[y][start - 1] {
+                    solver.add_expr(run.sum().eq(n));
+                }
+            }
+        }
+    }
+
+    // vertical runs
+    for x in 0..w {
+        let mut y = 0;
+        while y < h {
+            if clues[y][x].is_some() {
+                y += 1;
+                continue;
+            }
+            let start = y;
+            while y < h && clues[y][x].is_none() {
+                y += 1;
+            }
+            let run = num.slice_fixed_x((start..y, x));
+            solver.all_different(&run);
+            if start > 0 {
+                if let Some((Some(n), _)) = clues[start - 1][x] {
+                    solver.add_expr(run.sum().eq(n));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = Vec<Vec<Option<KakuroClue>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    let clue = || {
+        Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Dict::new(None, ".")),
+        ])
+    };
+    Grid::new(Choice::new(vec![
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Optionalize::new(Tuple2::new(clue(), clue()))),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "kakuro", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["kakuro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        vec![
+            vec![
+                Some((None, None)),
+                Some((Some(4), None)),
+                Some((Some(3), None)),
+            ],
+            vec![Some((None, Some(3))), None, None],
+            vec![Some((None, Some(4))), None, None],
+        ]
+    }
+
+    #[test]
+    fn test_kakuro_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_kakuro(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_2d([[0, 0, 0], [0, 1, 2], [0, 3, 1]]);
+        assert_eq!(ans, expected);
+        assert_eq!(ans[1][1].unwrap() + ans[1][2].unwrap(), 3);
+        assert_eq!(ans[1][2].unwrap() + ans[2][2].unwrap(), 3);
+    }
+
+    #[test]
+    fn test_kakuro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?kakuro/3/3/..4.3..3h.4h";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
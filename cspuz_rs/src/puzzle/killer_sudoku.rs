@@ -0,0 +1,117 @@
+use super::sudoku;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Killer Sudoku problem. Every room of `borders` is a cage, whose digits are distinct
+/// and sum up to the clue, if any.
+pub fn solve_killer_sudoku(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    sums: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+    if h != w {
+        return None;
+    }
+    let n = h;
+    let block_shape = sudoku::block_shape(n)?;
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((n, n), 1, n as i32);
+    solver.add_answer_key_int(num);
+
+    sudoku::add_constraints(&mut solver, num, &vec![vec![None; n]; n], block_shape);
+
+    let cages = graph::borders_to_rooms(borders);
+    assert_eq!(cages.len(), sums.len());
+    for (cage, &sum) in cages.iter().zip(sums) {
+        let cells = num.select(cage);
+        solver.all_different(&cells);
+        if let Some(s) = sum {
+            solver.add_expr(cells.sum().eq(s));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ])))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "killersudoku",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["killersudoku", "killer_sudoku"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // cages:
+        // A B B C
+        // A A D C
+        // A E F C
+        // E E F G
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 1, 1, 0], [0, 1, 1, 0], [1, 0, 0, 1]]),
+            vertical: util::tests::to_bool_2d([[1, 0, 1], [0, 1, 1], [1, 1, 1], [0, 1, 1]]),
+        };
+        let sums = vec![None, Some(7), Some(6), Some(1), None, None, None];
+        (borders, sums)
+    }
+
+    #[test]
+    fn test_killer_sudoku_problem() {
+        let (borders, sums) = problem_for_tests();
+        let ans = solve_killer_sudoku(&borders, &sums, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected =
+            util::tests::to_option_2d([[1, 3, 4, 2], [4, 2, 1, 3], [3, 4, 2, 1], [2, 1, 3, 4]]);
+        assert_eq!(ans, expected);
+
+        // the digits in each cage are distinct and sum up to the clue
+        let cages = graph::borders_to_rooms(&borders);
+        for (cage, &sum) in cages.iter().zip(&sums) {
+            let mut digits = cage
+                .iter()
+                .map(|&(y, x)| ans[y][x].unwrap())
+                .collect::<Vec<_>>();
+            if let Some(s) = sum {
+                assert_eq!(digits.iter().sum::<i32>(), s);
+            }
+            digits.sort();
+            digits.dedup();
+            assert_eq!(digits.len(), cage.len());
+        }
+    }
+
+    #[test]
+    fn test_killer_sudoku_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?killersudoku/4/4/luocq8g761i";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
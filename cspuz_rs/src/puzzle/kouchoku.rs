@@ -4,11 +4,14 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, AlphaToNum, Choice, Combinator, Context,
     ContextBasedGrid, Dict, Optionalize, Size, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub type Pt = (usize, usize);
 
-pub fn solve_kouchoku(clues: &[Vec<Option<i32>>]) -> Option<(Vec<(Pt, Pt)>, Vec<(Pt, Pt)>)> {
+pub fn solve_kouchoku(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<(Vec<(Pt, Pt)>, Vec<(Pt, Pt)>)> {
     let (h, w) = util::infer_shape(clues);
 
     let mut points = vec![];
@@ -88,7 +91,7 @@ pub fn solve_kouchoku(clues: &[Vec<Option<i32>>]) -> Option<(Vec<(Pt, Pt)>, Vec<
         }
     }
 
-    solver.irrefutable_facts().map(|f| {
+    solver.irrefutable_facts(ctx).map(|f| {
         let mut fixed_edges = vec![];
         let mut undet_edges = vec![];
 
@@ -203,7 +206,7 @@ mod tests {
     #[test]
     fn test_kouchoku_problem() {
         let problem = problem_for_tests();
-        let ans = solve_kouchoku(&problem);
+        let ans = solve_kouchoku(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let mut ans = ans.unwrap();
         ans.0.sort();
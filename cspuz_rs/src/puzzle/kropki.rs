@@ -3,7 +3,7 @@ use crate::serializer::{
     map_2d, problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid,
     MultiDigit, Sequencer, Size,
 };
-use crate::solver::{IntVar, Solver};
+use crate::solver::{IntVar, SolveContext, Solver};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum KropkiClue {
@@ -25,7 +25,10 @@ fn add_kropki_constraint(solver: &mut Solver, a: &IntVar, b: &IntVar, clue: Krop
     }
 }
 
-pub fn solve_kropki(clues: &InnerGridEdges<Vec<Vec<KropkiClue>>>) -> Option<Vec<Vec<Option<i32>>>> {
+pub fn solve_kropki(
+    clues: &InnerGridEdges<Vec<Vec<KropkiClue>>>,
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
     let (h, w) = clues.base_shape();
     assert_eq!(h, w);
     let n = h;
@@ -60,7 +63,7 @@ pub fn solve_kropki(clues: &InnerGridEdges<Vec<Vec<KropkiClue>>>) -> Option<Vec<
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(num))
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
 }
 
 fn kropi_clue_to_i32(clue: &KropkiClue) -> i32 {
@@ -206,7 +209,7 @@ mod tests {
     #[test]
     fn test_kropki_problem() {
         let problem = problem_for_tests();
-        let ans = solve_kropki(&problem);
+        let ans = solve_kropki(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,120 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Kuromasu (Kurodoko) problem. A clue of -1 denotes a numbered cell of unknown value.
+pub fn solve_kuromasu(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    solver.add_expr(!(is_black.slice((..(h - 1), ..)) & is_black.slice((1.., ..))));
+    solver.add_expr(!(is_black.slice((.., ..(w - 1))) & is_black.slice((.., 1..))));
+    graph::active_vertices_connected_2d(&mut solver, !is_black);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(!is_black.at((y, x)));
+                if n > 0 {
+                    let up = (!is_black.slice_fixed_x((..y, x)))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let down = (!is_black.slice_fixed_x(((y + 1).., x))).consecutive_prefix_true();
+                    let left = (!is_black.slice_fixed_y((y, ..x)))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let right = (!is_black.slice_fixed_y((y, (x + 1)..))).consecutive_prefix_true();
+                    solver.add_expr((up + down + left + right + 1).eq(n));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "kurodoko", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["kurodoko", "kuromasu"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[0][1] = Some(7);
+        ret[0][2] = Some(7);
+        ret[1][2] = Some(7);
+        ret[3][4] = Some(6);
+        ret
+    }
+
+    #[test]
+    fn test_kuromasu_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_kuromasu(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [1, 0, 0, 0, 1],
+            [0, 0, 0, 1, 0],
+            [0, 0, 0, 0, 0],
+            [1, 0, 0, 0, 0],
+            [0, 0, 0, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+
+        // each clue sees exactly that many white cells, including itself
+        for (y, row) in problem.iter().enumerate() {
+            for (x, &clue) in row.iter().enumerate() {
+                if let Some(n) = clue {
+                    let mut seen = 1;
+                    for (dy, dx) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                        let (mut y2, mut x2) = (y as i32 + dy, x as i32 + dx);
+                        while (0..5).contains(&y2)
+                            && (0..5).contains(&x2)
+                            && ans[y2 as usize][x2 as usize] == Some(false)
+                        {
+                            seen += 1;
+                            y2 += dy;
+                            x2 += dx;
+                        }
+                    }
+                    assert_eq!(seen, n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kuromasu_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?kurodoko/5/5/g77j7q6k";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -3,9 +3,12 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
-pub fn solve_kurotto(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_kurotto(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -54,7 +57,7 @@ pub fn solve_kurotto(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -96,7 +99,7 @@ mod tests {
     #[test]
     fn test_kurotto_problem() {
         let problem = problem_for_tests();
-        let ans = solve_kurotto(&problem);
+        let ans = solve_kurotto(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_bool_2d([
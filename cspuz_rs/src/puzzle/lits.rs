@@ -2,10 +2,11 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, Rooms, Size,
 };
-use crate::solver::{any, count_true, Solver, FALSE};
+use crate::solver::{any, count_true, SolveContext, Solver, FALSE};
 
 pub fn solve_lits(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let h = borders.vertical.len();
     assert!(h > 0);
@@ -134,7 +135,7 @@ pub fn solve_lits(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = graph::InnerGridEdges<Vec<Vec<bool>>>;
@@ -201,7 +202,7 @@ mod tests {
     #[test]
     fn test_lits_problem() {
         let problem = problem_for_tests();
-        let ans = solve_lits(&problem);
+        let ans = solve_lits(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
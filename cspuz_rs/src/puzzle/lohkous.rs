@@ -1,10 +1,11 @@
 use super::util;
 use crate::graph;
 use crate::serializer::strip_prefix;
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_lohkous(
     clues: &[Vec<Option<Vec<i32>>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -99,7 +100,7 @@ pub fn solve_lohkous(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(edges))
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
 }
 
 type Problem = Vec<Vec<Option<Vec<i32>>>>;
@@ -174,7 +175,7 @@ mod tests {
     fn test_lohkous_problem() {
         let problem = problem_for_tests();
 
-        let ans = solve_lohkous(&problem);
+        let ans = solve_lohkous(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -3,7 +3,7 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Map, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LoopSpecialClue {
@@ -20,6 +20,7 @@ pub enum LoopSpecialClue {
 
 pub fn solve_loop_special(
     clues: &[Vec<LoopSpecialClue>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -131,7 +132,7 @@ pub fn solve_loop_special(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<LoopSpecialClue>>;
@@ -191,7 +192,7 @@ mod tests {
     #[test]
     fn test_loop_special_problem() {
         let problem = problem_for_tests();
-        let ans = solve_loop_special(&problem);
+        let ans = solve_loop_special(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
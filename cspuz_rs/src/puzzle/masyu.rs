@@ -1,7 +1,7 @@
 use super::util;
 use crate::graph;
 use crate::serializer::{problem_to_url, url_to_problem, Combinator, Grid, Map, MultiDigit};
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MasyuClue {
@@ -10,7 +10,10 @@ pub enum MasyuClue {
     Black,
 }
 
-pub fn solve_masyu(clues: &[Vec<MasyuClue>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+pub fn solve_masyu(
+    clues: &[Vec<MasyuClue>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -55,7 +58,7 @@ pub fn solve_masyu(clues: &[Vec<MasyuClue>]) -> Option<graph::BoolGridEdgesIrref
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<MasyuClue>>;
@@ -117,7 +120,7 @@ mod tests {
     #[test]
     fn test_masyu_problem() {
         let problem = problem_for_tests();
-        let ans = solve_masyu(&problem);
+        let ans = solve_masyu(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
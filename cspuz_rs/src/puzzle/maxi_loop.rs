@@ -0,0 +1,164 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use crate::solver::{any, SolveContext, Solver};
+
+/// Solves a Maxi Loop problem. The clue of a room is the number of cells of the longest straight
+/// segment of the loop within the room.
+pub fn solve_maxi_loop(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+    solver.add_expr(is_passed);
+
+    let rooms = graph::borders_to_rooms(borders);
+    assert_eq!(rooms.len(), clues.len());
+
+    let mut room_id = vec![vec![0; w]; h];
+    for (i, room) in rooms.iter().enumerate() {
+        for &(y, x) in room {
+            room_id[y][x] = i;
+        }
+    }
+
+    // the number of cells of the straight segment starting at each cell and going right or
+    // down, without leaving the room
+    let mut room_runs = vec![vec![]; rooms.len()];
+    for y in 0..h {
+        for x in 0..w {
+            let mut x_end = x;
+            while x_end < w - 1 && !borders.vertical[y][x_end] {
+                x_end += 1;
+            }
+            let right = is_line
+                .horizontal
+                .slice_fixed_y((y, x..x_end))
+                .consecutive_prefix_true();
+            room_runs[room_id[y][x]].push(right + 1);
+
+            let mut y_end = y;
+            while y_end < h - 1 && !borders.horizontal[y_end][x] {
+                y_end += 1;
+            }
+            let down = is_line
+                .vertical
+                .slice_fixed_x((y..y_end, x))
+                .consecutive_prefix_true();
+            room_runs[room_id[y][x]].push(down + 1);
+        }
+    }
+
+    for i in 0..rooms.len() {
+        if let Some(n) = clues[i] {
+            for run in &room_runs[i] {
+                solver.add_expr(run.le(n));
+            }
+            solver.add_expr(any(room_runs[i].iter().map(|run| run.eq(n))));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ])))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "maxi",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["maxi", "maxi_loop"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A A B B
+        // A C C D D
+        // C C C C D
+        // C C C C C
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([
+                [0, 1, 1, 1, 1],
+                [1, 0, 0, 1, 0],
+                [0, 0, 0, 0, 1],
+            ]),
+            vertical: util::tests::to_bool_2d([
+                [0, 0, 1, 0],
+                [1, 0, 1, 0],
+                [0, 0, 0, 1],
+                [0, 0, 0, 0],
+            ]),
+        };
+        (borders, vec![Some(2), Some(2), Some(3), Some(2)])
+    }
+
+    #[test]
+    fn test_maxi_loop_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_maxi_loop(&borders, &clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([
+                [1, 0, 1, 1],
+                [0, 1, 0, 1],
+                [0, 1, 0, 1],
+                [1, 0, 1, 1],
+            ]),
+            vertical: util::tests::to_option_bool_2d([
+                [1, 1, 1, 0, 1],
+                [1, 0, 0, 1, 0],
+                [1, 1, 1, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+
+        // the longest straight segment in room C is (3, 2)-(3, 4) in the bottom row
+        let mut longest = 0;
+        for x in 0..4 {
+            let mut n = 1;
+            while x + n < 5 && ans.horizontal[3][x + n - 1] == Some(true) {
+                n += 1;
+            }
+            longest = longest.max(n);
+        }
+        assert_eq!(longest, 3);
+    }
+
+    #[test]
+    fn test_maxi_loop_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?maxi/5/4/5880fi12232";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
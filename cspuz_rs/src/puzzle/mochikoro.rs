@@ -0,0 +1,131 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{any, SolveContext, Solver};
+
+/// Solves a Mochikoro problem. A clue of -1 denotes a numbered cell of unknown value.
+pub fn solve_mochikoro(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+
+    // unshaded cells form rectangles iff no 2x2 block has exactly one shaded cell
+    for y in 0..(h - 1) {
+        for x in 0..(w - 1) {
+            solver.add_expr(is_black.slice((y..(y + 2), x..(x + 2))).count_true().ne(1));
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(!is_black.at((y, x)));
+                if n > 0 {
+                    let up = (!is_black.slice_fixed_x((..y, x)))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let down = (!is_black.slice_fixed_x(((y + 1).., x))).consecutive_prefix_true();
+                    let left = (!is_black.slice_fixed_y((y, ..x)))
+                        .reverse()
+                        .consecutive_prefix_true();
+                    let right = (!is_black.slice_fixed_y((y, (x + 1)..))).consecutive_prefix_true();
+                    let height = up + down + 1;
+                    let width = left + right + 1;
+
+                    let mut shapes = vec![];
+                    for a in 1..=n {
+                        if n % a == 0 {
+                            shapes.push(height.eq(a) & width.eq(n / a));
+                        }
+                    }
+                    solver.add_expr(any(shapes));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "mochikoro", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["mochikoro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[0][0] = Some(1);
+        ret[0][4] = Some(10);
+        ret[2][0] = Some(1);
+        ret[4][0] = Some(2);
+        ret
+    }
+
+    #[test]
+    fn test_mochikoro_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_mochikoro(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 1, 1, 0, 0],
+            [1, 1, 1, 0, 0],
+            [0, 1, 1, 0, 0],
+            [1, 1, 1, 0, 0],
+            [0, 0, 1, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        // unshaded cells form rectangles, so no 2x2 block has exactly one shaded cell
+        for y in 0..4 {
+            for x in 0..4 {
+                let n = [(y, x), (y, x + 1), (y + 1, x), (y + 1, x + 1)]
+                    .iter()
+                    .filter(|&&(y2, x2)| ans[y2][x2] == Some(true))
+                    .count();
+                assert_ne!(n, 1);
+            }
+        }
+        // the rectangle of the clue 10 is columns 3 and 4, and it is separated from the
+        // rectangle of the clue 2 by shaded cells
+        for y in 0..5 {
+            assert_eq!(ans[y][2], Some(true));
+            assert_eq!(ans[y][3], Some(false));
+            assert_eq!(ans[y][4], Some(false));
+        }
+    }
+
+    #[test]
+    fn test_mochikoro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?mochikoro/5/5/1iak1o2j";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
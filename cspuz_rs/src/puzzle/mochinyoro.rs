@@ -0,0 +1,152 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Mochinyoro problem. Unlike Nurikabe, unshaded regions without a clue are allowed.
+/// A clue of -1 denotes a numbered cell of unknown value.
+pub fn solve_mochinyoro(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    let mut clue_pos = vec![];
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                clue_pos.push((y, x, n));
+            }
+        }
+    }
+
+    // 0 for shaded cells, i for the region of the i-th clue and `clue_pos.len() + 1` for
+    // unshaded cells in regions without a clue
+    let group_id = solver.int_var_2d((h, w), 0, clue_pos.len() as i32 + 1);
+    solver.add_expr(is_black.iff(group_id.eq(0)));
+
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+    for i in 1..=clue_pos.len() {
+        graph::active_vertices_connected_2d(&mut solver, group_id.eq(i as i32));
+    }
+
+    solver.add_expr(
+        (!is_black.conv2d_or((2, 1))).imp(
+            group_id
+                .slice((..(h - 1), ..))
+                .eq(group_id.slice((1.., ..))),
+        ),
+    );
+    solver.add_expr(
+        (!is_black.conv2d_or((1, 2))).imp(
+            group_id
+                .slice((.., ..(w - 1)))
+                .eq(group_id.slice((.., 1..))),
+        ),
+    );
+    solver.add_expr(!is_black.conv2d_and((2, 2)));
+
+    for (i, &(y, x, n)) in clue_pos.iter().enumerate() {
+        solver.add_expr(group_id.at((y, x)).eq((i + 1) as i32));
+        if n > 0 {
+            solver.add_expr(group_id.eq((i + 1) as i32).count_true().eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "mochinyoro", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["mochinyoro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[0][0] = Some(2);
+        ret[0][3] = Some(11);
+        ret[1][2] = Some(2);
+        ret
+    }
+
+    #[test]
+    fn test_mochinyoro_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_mochinyoro(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 1, 1, 0, 0],
+            [0, 1, 0, 1, 0],
+            [1, 1, 0, 1, 0],
+            [0, 1, 1, 1, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        // no 2x2 block is fully shaded
+        for y in 0..4 {
+            for x in 0..4 {
+                assert!(
+                    ans[y][x] != Some(true)
+                        || ans[y][x + 1] != Some(true)
+                        || ans[y + 1][x] != Some(true)
+                        || ans[y + 1][x + 1] != Some(true)
+                );
+            }
+        }
+        // the unshaded region of each clue has the clued size
+        for &(y, x) in &[(0, 0), (0, 3), (1, 2)] {
+            let mut visited = vec![vec![false; 5]; 5];
+            let mut stack = vec![(y, x)];
+            visited[y][x] = true;
+            let mut size = 0;
+            while let Some((y, x)) = stack.pop() {
+                size += 1;
+                for (y2, x2) in [
+                    (y.wrapping_sub(1), x),
+                    (y + 1, x),
+                    (y, x.wrapping_sub(1)),
+                    (y, x + 1),
+                ] {
+                    if y2 < 5 && x2 < 5 && !visited[y2][x2] && ans[y2][x2] == Some(false) {
+                        visited[y2][x2] = true;
+                        stack.push((y2, x2));
+                    }
+                }
+            }
+            assert_eq!(Some(size), problem[y][x]);
+        }
+    }
+
+    #[test]
+    fn test_mochinyoro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?mochinyoro/5/5/2hbi2w";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -4,11 +4,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, MultiDigit,
     Rooms, Size, Tuple2,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_moonsun(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Vec<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -83,7 +84,7 @@ pub fn solve_moonsun(
         solver.add_expr(count_true(&room_entrance[i]).eq(2));
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<i32>>);
@@ -148,7 +149,7 @@ mod tests {
     #[test]
     fn test_moonsun_problem() {
         let (borders, clues) = problem_for_tests();
-        let ans = solve_moonsun(&borders, &clues);
+        let ans = solve_moonsun(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
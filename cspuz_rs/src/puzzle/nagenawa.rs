@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, Solver, FALSE};
+use crate::solver::{count_true, SolveContext, Solver, FALSE};
 
 pub fn solve_nagenawa(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = borders.base_shape();
 
@@ -91,7 +92,7 @@ pub fn solve_nagenawa(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
@@ -158,7 +159,7 @@ mod tests {
     #[test]
     fn test_nagenawa_problem() {
         let (borders, clues) = problem_for_tests();
-        let ans = solve_nagenawa(&borders, &clues);
+        let ans = solve_nagenawa(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
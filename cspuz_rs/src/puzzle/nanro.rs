@@ -0,0 +1,129 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, Rooms, Size, Spaces, Tuple2,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Nanro problem. In the answer, 0 denotes an empty cell.
+pub fn solve_nanro(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+
+    let rooms = graph::borders_to_rooms(borders);
+    let max_room_size = rooms.iter().map(|room| room.len()).max().unwrap_or(0) as i32;
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((h, w), 0, max_room_size);
+    solver.add_answer_key_int(num);
+    let is_filled = &num.ne(0);
+
+    // each room has a label, which is the number of filled cells in it
+    for room in &rooms {
+        let label = solver.int_var(1, room.len() as i32);
+        for &pt in room {
+            solver.add_expr(num.at(pt).eq(0) | num.at(pt).eq(&label));
+        }
+        solver.add_expr(is_filled.select(room).count_true().eq(label));
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && borders.horizontal[y][x] {
+                solver.add_expr(
+                    is_filled
+                        .at((y, x))
+                        .imp(num.at((y, x)).ne(num.at((y + 1, x)))),
+                );
+            }
+            if x < w - 1 && borders.vertical[y][x] {
+                solver.add_expr(
+                    is_filled
+                        .at((y, x))
+                        .imp(num.at((y, x)).ne(num.at((y, x + 1)))),
+                );
+            }
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(num.at((y, x)).eq(n));
+            }
+        }
+    }
+
+    solver.add_expr(!is_filled.conv2d_and((2, 2)));
+    graph::active_vertices_connected_2d(&mut solver, is_filled);
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<Option<i32>>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (h, w) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(
+        combinator(),
+        "nanro",
+        problem.clone(),
+        &Context::sized(h, w),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["nanro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A B B
+        // A A B B
+        // A A C C
+        // D D C C
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([[0, 0, 0, 0], [0, 0, 1, 1], [1, 1, 0, 0]]),
+            vertical: util::tests::to_bool_2d([[0, 1, 0], [0, 1, 0], [0, 1, 0], [0, 1, 0]]),
+        };
+        let clues = vec![
+            vec![None, None, Some(1), None],
+            vec![None, None, None, None],
+            vec![None, Some(3), Some(2), None],
+            vec![Some(2), None, None, None],
+        ];
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_nanro_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_nanro(&borders, &clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected =
+            util::tests::to_option_2d([[0, 3, 1, 0], [0, 3, 0, 0], [0, 3, 2, 2], [2, 2, 0, 0]]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_nanro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?nanro/4/4/94g0f0h1l32g2i";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
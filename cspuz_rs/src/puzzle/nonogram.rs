@@ -0,0 +1,158 @@
+use crate::serializer::{strip_prefix, Combinator, Context, HexInt, Sequencer};
+use crate::solver::{any, BoolVarArray1D, SolveContext, Solver};
+
+/// Adds the constraint that the runs of true cells in `cells` have the lengths `clue`, in order.
+/// The position of each run is an integer variable, and each cell is true iff some run covers it.
+fn add_line_constraints(solver: &mut Solver, cells: BoolVarArray1D, clue: &[i32]) {
+    let n = cells.len() as i32;
+    if clue.is_empty() {
+        solver.add_expr(!cells.any());
+        return;
+    }
+
+    let start = &solver.int_var_1d(clue.len(), 0, n);
+    for i in 0..clue.len() {
+        solver.add_expr((start.at(i) + clue[i]).le(n));
+        if i + 1 < clue.len() {
+            solver.add_expr((start.at(i) + (clue[i] + 1)).le(start.at(i + 1)));
+        }
+    }
+    for j in 0..n {
+        let covered = clue
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| start.at(i).le(j) & start.at(i).gt(j - c));
+        solver.add_expr(cells.at(j as usize).iff(any(covered)));
+    }
+}
+
+pub fn solve_nonogram(
+    row_clues: &[Vec<i32>],
+    column_clues: &[Vec<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let h = row_clues.len();
+    let w = column_clues.len();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    for (y, clue) in row_clues.iter().enumerate() {
+        add_line_constraints(&mut solver, is_black.slice_fixed_y((y, ..)), clue);
+    }
+    for (x, clue) in column_clues.iter().enumerate() {
+        add_line_constraints(&mut solver, is_black.slice_fixed_x((.., x)), clue);
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+/// The clues of the rows and the clues of the columns.
+type Problem = (Vec<Vec<i32>>, Vec<Vec<i32>>);
+
+// The URL is `nonogram/<width>/<height>/<clues>`, where `<clues>` lists the clues of the rows and
+// then those of the columns, separated by '.'. The numbers of a clue are written in HexInt.
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (row_clues, column_clues) = problem;
+    let ctx = Context::new();
+
+    let mut lines = vec![];
+    for clue in row_clues.iter().chain(column_clues.iter()) {
+        let mut line = vec![];
+        for &n in clue {
+            let (_, app) = HexInt.serialize(&ctx, &[n])?;
+            line.extend(app);
+        }
+        lines.push(String::from_utf8(line).ok()?);
+    }
+
+    Some(format!(
+        "https://puzz.link/p?nonogram/{}/{}/{}",
+        column_clues.len(),
+        row_clues.len(),
+        lines.join(".")
+    ))
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    let serialized = strip_prefix(url)?;
+    let mut parts = serialized.split('/');
+    if parts.next()? != "nonogram" {
+        return None;
+    }
+    let width = parts.next()?.parse::<usize>().ok()?;
+    let height = parts.next()?.parse::<usize>().ok()?;
+    let body = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let ctx = Context::new();
+    let mut clues = vec![];
+    for line in body.split('.') {
+        let line = line.as_bytes();
+        let mut sequencer = Sequencer::new(line);
+        let mut clue = vec![];
+        while sequencer.n_read() < line.len() {
+            clue.push(sequencer.deserialize_one_elem(&ctx, HexInt)?);
+        }
+        clues.push(clue);
+    }
+    if clues.len() != height + width {
+        return None;
+    }
+
+    let column_clues = clues.split_off(height);
+    Some((clues, column_clues))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::util;
+
+    fn problem_for_tests() -> Problem {
+        (
+            vec![vec![1, 1], vec![5], vec![5], vec![3], vec![1]],
+            vec![vec![2], vec![4], vec![4], vec![4], vec![2]],
+        )
+    }
+
+    #[test]
+    fn test_nonogram_problem() {
+        let (row_clues, column_clues) = problem_for_tests();
+        let ans = solve_nonogram(&row_clues, &column_clues, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 1, 0, 1, 0],
+            [1, 1, 1, 1, 1],
+            [1, 1, 1, 1, 1],
+            [0, 1, 1, 1, 0],
+            [0, 0, 1, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_nonogram_empty_line() {
+        let row_clues = vec![vec![], vec![2]];
+        let column_clues = vec![vec![1], vec![1]];
+        let ans = solve_nonogram(&row_clues, &column_clues, &mut SolveContext::new());
+        assert_eq!(ans, Some(util::tests::to_option_bool_2d([[0, 0], [1, 1]])));
+    }
+
+    #[test]
+    fn test_nonogram_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?nonogram/5/5/11.5.5.3.1.2.4.4.4.2";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+
+        let problem = (vec![vec![], vec![17]], vec![vec![1]; 17]);
+        let url = "https://puzz.link/p?nonogram/17/2/.-11.1.1.1.1.1.1.1.1.1.1.1.1.1.1.1.1.1";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -2,10 +2,11 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, Rooms, Size,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_norinori(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = borders.base_shape();
 
@@ -29,7 +30,7 @@ pub fn solve_norinori(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = graph::InnerGridEdges<Vec<Vec<bool>>>;
@@ -81,7 +82,7 @@ mod tests {
     #[test]
     fn test_norinori_problem() {
         let problem = problem_for_tests();
-        let ans = solve_norinori(&problem);
+        let ans = solve_norinori(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,135 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use crate::solver::{count_true, SolveContext, Solver};
+
+/// Solves a Numberlink problem. Each clue cell holds a number, or -1 for an endpoint whose number
+/// is unknown. Each pair of endpoints with the same number must be connected by a path, the paths
+/// must not cross, and every cell must be covered by some path.
+///
+/// Returns the lines and, for each cell, the number of the endpoints its path connects.
+pub fn solve_numberlink(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<(graph::BoolGridEdgesIrrefutableFacts, Vec<Vec<Option<i32>>>)> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let mut max_color = 0;
+    for row in clues {
+        for &clue in row {
+            match clue {
+                Some(n) if n > 0 => max_color = max_color.max(n),
+                Some(_) => max_color += 1,
+                None => (),
+            }
+        }
+    }
+    let color = &solver.int_var_2d((h, w), 0, max_color);
+    solver.add_answer_key_int(color);
+
+    // endpoints connected by a line have the same number
+    solver.add_expr(
+        is_line
+            .horizontal
+            .imp(color.slice((.., ..(w - 1))).eq(color.slice((.., 1..)))),
+    );
+    solver.add_expr(
+        is_line
+            .vertical
+            .imp(color.slice((..(h - 1), ..)).eq(color.slice((1.., ..)))),
+    );
+
+    for y in 0..h {
+        for x in 0..w {
+            let degree = count_true(is_line.vertex_neighbors((y, x)));
+            match clues[y][x] {
+                Some(n) => {
+                    solver.add_expr(degree.eq(1));
+                    if n > 0 {
+                        solver.add_expr(color.at((y, x)).eq(n));
+                    }
+                }
+                None => solver.add_expr(degree.eq(2)),
+            }
+        }
+    }
+
+    // without cycles, each component of the lines is a path between two endpoints
+    let (edges, graph) = is_line.representation();
+    graph::active_edges_acyclic(&mut solver, edges, &graph);
+
+    solver
+        .irrefutable_facts(ctx)
+        .map(|f| (f.get(is_line), f.get(color)))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "numlin", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["numlin", "numberlink"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        vec![
+            vec![Some(3), None, None, None],
+            vec![None, None, Some(1), Some(3)],
+            vec![None, Some(2), None, None],
+            vec![Some(1), None, None, Some(2)],
+        ]
+    }
+
+    #[test]
+    fn test_numberlink_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_numberlink(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let (is_line, color) = ans.unwrap();
+
+        for y in 0..4 {
+            for x in 0..4 {
+                if y < 3 {
+                    assert!(is_line.vertical[y][x].is_some());
+                }
+                if x < 3 {
+                    assert!(is_line.horizontal[y][x].is_some());
+                }
+                assert!(color[y][x].is_some());
+            }
+        }
+        assert_eq!(is_line.horizontal[0][0], Some(true));
+        assert_eq!(is_line.horizontal[1][2], Some(false));
+        assert_eq!(is_line.vertical[1][0], Some(true));
+        assert_eq!(color[1][1], Some(1));
+        assert_eq!(color[2][2], Some(2));
+    }
+
+    #[test]
+    fn test_numberlink_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?numlin/4/4/3k13g2h1h2";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
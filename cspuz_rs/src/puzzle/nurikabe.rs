@@ -3,15 +3,45 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{BoolVarArray2D, SolveContext, Solver};
 
-pub fn solve_nurikabe(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_nurikabe(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
     let is_black = &solver.bool_var_2d((h, w));
     solver.add_answer_key_bool(is_black);
 
+    add_constraints(&mut solver, is_black, clues);
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+pub fn enumerate_answers_nurikabe(
+    clues: &[Vec<Option<i32>>],
+    num_max_answers: usize,
+) -> Vec<Vec<Vec<bool>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    add_constraints(&mut solver, is_black, clues);
+
+    solver
+        .answer_iter()
+        .take(num_max_answers)
+        .map(|f| f.get_unwrap(is_black))
+        .collect()
+}
+
+pub fn add_constraints(solver: &mut Solver, is_black: &BoolVarArray2D, clues: &[Vec<Option<i32>>]) {
+    let (h, w) = util::infer_shape(clues);
+
     let mut clue_pos = vec![];
     for y in 0..h {
         for x in 0..w {
@@ -24,9 +54,9 @@ pub fn solve_nurikabe(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>
     let group_id = solver.int_var_2d((h, w), 0, clue_pos.len() as i32);
     solver.add_expr(is_black.iff(group_id.eq(0)));
 
-    graph::active_vertices_connected_2d(&mut solver, is_black);
+    graph::active_vertices_connected_2d(solver, is_black);
     for i in 1..=clue_pos.len() {
-        graph::active_vertices_connected_2d(&mut solver, group_id.eq(i as i32));
+        graph::active_vertices_connected_2d(solver, group_id.eq(i as i32));
     }
 
     solver.add_expr(
@@ -51,8 +81,6 @@ pub fn solve_nurikabe(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>
             solver.add_expr(group_id.eq((i + 1) as i32).count_true().eq(n));
         }
     }
-
-    solver.irrefutable_facts().map(|f| f.get(is_black))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -104,7 +132,7 @@ mod tests {
             deserialize_problem("https://puzz.link/p?nurikabe/6/6/m8n8i9u"),
             Some(problem.clone())
         );
-        let ans = solve_nurikabe(&problem);
+        let ans = solve_nurikabe(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,184 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    Dict, Rooms, Size, Spaces, Tuple2,
+};
+use crate::solver::{count_true, SolveContext, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NurimazeMarker {
+    Start,
+    Goal,
+    /// A cell which the path from the start to the goal must pass through.
+    Circle,
+    /// A cell which the path from the start to the goal must not pass through.
+    Triangle,
+}
+
+/// Solves a Nurimaze problem. Each room is either entirely shaded or entirely unshaded, and the
+/// unshaded cells form a maze (a tree) in which the path from the start to the goal passes
+/// through all circles and no triangles. There is no 2x2 area of shaded or unshaded cells, and
+/// the shaded cells are connected.
+pub fn solve_nurimaze(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    markers: &[Vec<Option<NurimazeMarker>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    solver.add_expr(!is_black.conv2d_and((2, 2)));
+    solver.add_expr(is_black.conv2d_or((2, 2)));
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && !borders.horizontal[y][x] {
+                solver.add_expr(is_black.at((y, x)).iff(is_black.at((y + 1, x))));
+            }
+            if x < w - 1 && !borders.vertical[y][x] {
+                solver.add_expr(is_black.at((y, x)).iff(is_black.at((y, x + 1))));
+            }
+        }
+    }
+
+    // passages connect adjacent unshaded cells, and they form a tree
+    let is_passage = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_expr(
+        is_passage
+            .horizontal
+            .iff(!(is_black.slice((.., ..(w - 1))) | is_black.slice((.., 1..)))),
+    );
+    solver.add_expr(
+        is_passage
+            .vertical
+            .iff(!(is_black.slice((..(h - 1), ..)) | is_black.slice((1.., ..)))),
+    );
+    // the unshaded cells are connected, so they form a tree iff there is one passage fewer than
+    // unshaded cells
+    graph::active_vertices_connected_2d(&mut solver, !is_black);
+    solver.add_expr(
+        (is_passage.horizontal.count_true() + is_passage.vertical.count_true() + 1)
+            .eq((!is_black).count_true()),
+    );
+
+    // as the maze has no cycles, the path is the unique one from the start to the goal
+    let is_path = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_expr(is_path.horizontal.imp(&is_passage.horizontal));
+    solver.add_expr(is_path.vertical.imp(&is_passage.vertical));
+
+    for y in 0..h {
+        for x in 0..w {
+            let degree = count_true(is_path.vertex_neighbors((y, x)));
+            match markers[y][x] {
+                Some(NurimazeMarker::Start) | Some(NurimazeMarker::Goal) => {
+                    solver.add_expr(degree.eq(1))
+                }
+                Some(NurimazeMarker::Circle) => solver.add_expr(degree.eq(2)),
+                Some(NurimazeMarker::Triangle) => solver.add_expr(degree.eq(0)),
+                None => solver.add_expr(degree.eq(0) | degree.eq(2)),
+            }
+            if markers[y][x].is_some() {
+                solver.add_expr(!is_black.at((y, x)));
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = (
+    graph::InnerGridEdges<Vec<Vec<bool>>>,
+    Vec<Vec<Option<NurimazeMarker>>>,
+);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Dict::new(Some(NurimazeMarker::Start), "1")),
+            Box::new(Dict::new(Some(NurimazeMarker::Goal), "2")),
+            Box::new(Dict::new(Some(NurimazeMarker::Circle), "3")),
+            Box::new(Dict::new(Some(NurimazeMarker::Triangle), "4")),
+            Box::new(Spaces::new(None, '5')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = problem.0.base_shape();
+    problem_to_url_with_context(
+        combinator(),
+        "nurimaze",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["nurimaze"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // rooms:
+        // A A A B B
+        // A C D E E
+        // A F D G E
+        // H I I G E
+        // H H J K K
+        let borders = graph::InnerGridEdges {
+            horizontal: util::tests::to_bool_2d([
+                [0, 1, 1, 1, 1],
+                [0, 1, 0, 1, 0],
+                [1, 1, 1, 0, 0],
+                [0, 1, 1, 1, 1],
+            ]),
+            vertical: util::tests::to_bool_2d([
+                [0, 0, 1, 0],
+                [1, 1, 1, 0],
+                [1, 1, 1, 1],
+                [1, 0, 1, 1],
+                [0, 1, 1, 0],
+            ]),
+        };
+        let mut markers = vec![vec![None; 5]; 5];
+        markers[1][0] = Some(NurimazeMarker::Start);
+        markers[0][1] = Some(NurimazeMarker::Goal);
+        markers[0][0] = Some(NurimazeMarker::Circle);
+        markers[2][0] = Some(NurimazeMarker::Triangle);
+        markers[3][1] = Some(NurimazeMarker::Triangle);
+        (borders, markers)
+    }
+
+    #[test]
+    fn test_nurimaze_problem() {
+        let (borders, markers) = problem_for_tests();
+        let ans = solve_nurimaze(&borders, &markers, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 0, 0, 0, 0],
+            [0, 1, 1, 1, 1],
+            [0, 0, 1, 0, 1],
+            [1, 0, 0, 0, 1],
+            [1, 1, 1, 1, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_nurimaze_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?nurimaze/5/5/5rtmfasf32718494c";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -3,9 +3,12 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{any, Solver};
+use crate::solver::{any, SolveContext, Solver};
 
-pub fn solve_nurimisaki(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_nurimisaki(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -79,7 +82,7 @@ pub fn solve_nurimisaki(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<boo
             }
         }
     }
-    solver.irrefutable_facts().map(|f| f.get(is_white))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_white))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -124,7 +127,7 @@ mod tests {
     #[test]
     fn test_nurimisaki_problem() {
         let problem = problem_for_tests();
-        let ans = solve_nurimisaki(&problem);
+        let ans = solve_nurimisaki(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = [
@@ -4,10 +4,11 @@ use crate::serializer::{
     get_kudamono_url_info, kudamono_url_info_to_problem, problem_to_kudamono_url_grid, AlphaToNum,
     Choice, Combinator, Dict, KudamonoGrid, Optionalize,
 };
-use crate::solver::{any, Solver};
+use crate::solver::{any, SolveContext, Solver};
 
 pub fn solve_parrot_loop(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -66,7 +67,7 @@ pub fn solve_parrot_loop(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -109,7 +110,7 @@ mod tests {
     #[test]
     fn test_parrot_loop_problem() {
         let problem = problem_for_tests();
-        let ans = solve_parrot_loop(&problem);
+        let ans = solve_parrot_loop(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -3,7 +3,7 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Map, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PencilsClue {
@@ -34,6 +34,7 @@ const ID_TO_ANSWER_COMPONENT: [PencilsAnswer; 5] = [
 
 pub fn solve_pencils(
     clues: &[Vec<PencilsClue>],
+    ctx: &mut SolveContext,
 ) -> Option<(
     Vec<Vec<Option<PencilsAnswer>>>,
     graph::BoolGridEdgesIrrefutableFacts,
@@ -290,7 +291,7 @@ pub fn solve_pencils(
         }
     }
 
-    solver.irrefutable_facts().map(|f| {
+    solver.irrefutable_facts(ctx).map(|f| {
         (
             f.get(cell_answer)
                 .into_iter()
@@ -353,7 +354,7 @@ mod tests {
     #[test]
     fn test_pencils_problem() {
         let problem = problem_for_tests();
-        let ans = solve_pencils(&problem);
+        let ans = solve_pencils(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         #[rustfmt::skip]
@@ -4,7 +4,7 @@ use crate::serializer::{
     problem_to_url, url_to_problem, AlphaToNum, Choice, Combinator, Dict, Grid, HexInt, Map,
     Spaces, Tuple2,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum ReflectLinkClue {
@@ -41,6 +41,7 @@ impl ReflectLinkClue {
 
 pub fn solve_reflect_link(
     clues: &[Vec<ReflectLinkClue>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -98,7 +99,7 @@ pub fn solve_reflect_link(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<ReflectLinkClue>>;
@@ -142,7 +143,7 @@ mod tests {
     #[test]
     fn test_reflect_link_problem() {
         let problem = problem_for_tests();
-        let ans = solve_reflect_link(&problem);
+        let ans = solve_reflect_link(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -1,9 +1,12 @@
 use super::util;
 use crate::graph;
 use crate::serializer::strip_prefix;
-use crate::solver::{Solver, FALSE};
+use crate::solver::{SolveContext, Solver, FALSE};
 
-pub fn solve_ringring(is_black: &[Vec<bool>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+pub fn solve_ringring(
+    is_black: &[Vec<bool>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(is_black);
 
     let mut solver = Solver::new();
@@ -79,7 +82,7 @@ pub fn solve_ringring(is_black: &[Vec<bool>]) -> Option<graph::BoolGridEdgesIrre
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<bool>>;
@@ -141,7 +144,7 @@ mod tests {
     #[test]
     fn test_ringring_problem() {
         let is_black = problem_for_tests();
-        let ans = solve_ringring(&is_black);
+        let ans = solve_ringring(&is_black, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,135 @@
+use super::{sudoku, util};
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, OutsideClues, OutsideCluesCombinator, Size, Spaces, Tuple2,
+};
+use crate::solver::{sum, IntVarArray1D, SolveContext, Solver, FALSE};
+
+/// Adds the constraint that the digits strictly between 1 and `n` in `line` sum up to `total`.
+fn add_sandwich_constraint(solver: &mut Solver, line: IntVarArray1D, total: i32, n: i32) {
+    let mut seen_lo = FALSE;
+    let mut seen_hi = FALSE;
+    let mut terms = vec![];
+    for i in 0..line.len() {
+        let is_lo = line.at(i).eq(1);
+        let is_hi = line.at(i).eq(n);
+        // exactly one of 1 and `n` comes before this cell, which is neither of them
+        let is_between = (seen_lo.clone() ^ seen_hi.clone()) & !is_lo.clone() & !is_hi.clone();
+        terms.push(is_between.ite(line.at(i), 0));
+        seen_lo = seen_lo | is_lo;
+        seen_hi = seen_hi | is_hi;
+    }
+    solver.add_expr(sum(terms).eq(total));
+}
+
+/// Solves a Sandwich Sudoku problem. The clue of a column or a row is the sum of the digits
+/// strictly between 1 and the largest digit in it.
+pub fn solve_sandwich_sudoku(
+    clues: &[Vec<Option<i32>>],
+    clue_vertical: &[Option<i32>],
+    clue_horizontal: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = util::infer_shape(clues);
+    if h != w {
+        return None;
+    }
+    let n = h;
+    let block_shape = sudoku::block_shape(n)?;
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((n, n), 1, n as i32);
+    solver.add_answer_key_int(num);
+
+    sudoku::add_constraints(&mut solver, num, clues, block_shape);
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(s) = clue {
+            add_sandwich_constraint(&mut solver, num.slice_fixed_x((.., x)), s, n as i32);
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(s) = clue {
+            add_sandwich_constraint(&mut solver, num.slice_fixed_y((y, ..)), s, n as i32);
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = (OutsideClues, Vec<Vec<Option<i32>>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        OutsideCluesCombinator,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(
+        combinator(),
+        "sandwichsudoku",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["sandwichsudoku", "sandwich_sudoku"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut clues = vec![vec![None; 4]; 4];
+        clues[1][1] = Some(4);
+        let clue_vertical = vec![Some(5), Some(0), Some(0), Some(0)];
+        let clue_horizontal = vec![Some(5), Some(3), Some(0), Some(2)];
+        ((clue_vertical, clue_horizontal), clues)
+    }
+
+    #[test]
+    fn test_sandwich_sudoku_problem() {
+        let ((clue_vertical, clue_horizontal), clues) = problem_for_tests();
+        let ans = solve_sandwich_sudoku(
+            &clues,
+            &clue_vertical,
+            &clue_horizontal,
+            &mut SolveContext::new(),
+        );
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected =
+            util::tests::to_option_2d([[1, 3, 2, 4], [2, 4, 3, 1], [3, 1, 4, 2], [4, 2, 1, 3]]);
+        assert_eq!(ans, expected);
+
+        let sandwich = |line: Vec<i32>| {
+            let lo = line.iter().position(|&v| v == 1).unwrap();
+            let hi = line.iter().position(|&v| v == 4).unwrap();
+            line[(lo.min(hi) + 1)..lo.max(hi)].iter().sum::<i32>()
+        };
+        // row 1 is 2 4 3 1, so 3 is between 4 and 1
+        assert_eq!(sandwich((0..4).map(|x| ans[1][x].unwrap()).collect()), 3);
+        for i in 0..4 {
+            let column = (0..4).map(|y| ans[y][i].unwrap()).collect();
+            assert_eq!(Some(sandwich(column)), clue_vertical[i]);
+            let row = (0..4).map(|x| ans[i][x].unwrap()).collect();
+            assert_eq!(Some(sandwich(row)), clue_horizontal[i]);
+        }
+    }
+
+    #[test]
+    fn test_sandwich_sudoku_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?sandwichsudoku/4/4/50005302k4p";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
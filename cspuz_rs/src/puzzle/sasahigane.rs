@@ -4,7 +4,7 @@ use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Map, Optionalize,
     Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum SashiganeClue {
@@ -17,6 +17,7 @@ pub enum SashiganeClue {
 
 pub fn solve_sashigane(
     clues: &[Vec<Option<SashiganeClue>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -143,7 +144,7 @@ pub fn solve_sashigane(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(edges))
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
 }
 
 type Problem = Vec<Vec<Option<SashiganeClue>>>;
@@ -197,7 +198,7 @@ mod tests {
     fn test_sashigane_problem() {
         let problem = problem_for_tests();
 
-        let ans = solve_sashigane(&problem);
+        let ans = solve_sashigane(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
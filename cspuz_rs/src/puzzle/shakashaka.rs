@@ -2,7 +2,7 @@ use super::util;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, NumSpaces, Spaces,
 };
-use crate::solver::{count_true, Solver, FALSE};
+use crate::solver::{count_true, SolveContext, Solver, FALSE};
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum ShakashakaCell {
@@ -13,7 +13,10 @@ pub enum ShakashakaCell {
     UpperRight,
 }
 
-pub fn solve_shakashaka(problem: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<ShakashakaCell>>>> {
+pub fn solve_shakashaka(
+    problem: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<ShakashakaCell>>>> {
     let (h, w) = util::infer_shape(problem);
 
     // 1   2   3   4
@@ -118,7 +121,7 @@ pub fn solve_shakashaka(problem: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<S
         }
     }
 
-    solver.irrefutable_facts().map(|f| {
+    solver.irrefutable_facts(ctx).map(|f| {
         let model = f.get(ans);
         model
             .into_iter()
@@ -184,7 +187,7 @@ mod tests {
     #[test]
     fn test_shakashaka_problem() {
         let problem = problem_for_tests();
-        let ans = solve_shakashaka(&problem);
+        let ans = solve_shakashaka(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -3,10 +3,11 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{any, Solver};
+use crate::solver::{any, SolveContext, Solver};
 
 pub fn solve_shikaku(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -77,7 +78,7 @@ pub fn solve_shikaku(
             .iff(ids.slice((.., ..(w - 1))).ne(ids.slice((.., 1..)))),
     );
 
-    solver.irrefutable_facts().map(|f| f.get(edges))
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -117,7 +118,7 @@ mod tests {
     #[test]
     fn test_shikaku_problem() {
         let problem = problem_for_tests();
-        let ans = solve_shikaku(&problem);
+        let ans = solve_shikaku(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         #[rustfmt::skip]
@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_shimaguni(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = borders.base_shape();
 
@@ -74,7 +75,7 @@ pub fn solve_shimaguni(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
@@ -131,7 +132,7 @@ mod tests {
     #[test]
     fn test_shimaguni_problem() {
         let problem = problem_for_tests();
-        let ans = solve_shimaguni(&problem.0, &problem.1);
+        let ans = solve_shimaguni(&problem.0, &problem.1, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
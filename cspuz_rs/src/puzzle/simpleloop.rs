@@ -1,9 +1,12 @@
 use super::util;
 use crate::graph;
 use crate::serializer::{problem_to_url, url_to_problem, Combinator, Grid, Map, MultiDigit};
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
-pub fn solve_simpleloop(is_black: &[Vec<bool>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+pub fn solve_simpleloop(
+    is_black: &[Vec<bool>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(is_black);
 
     let mut parity_diff = 0;
@@ -34,7 +37,7 @@ pub fn solve_simpleloop(is_black: &[Vec<bool>]) -> Option<graph::BoolGridEdgesIr
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 type Problem = Vec<Vec<bool>>;
@@ -74,7 +77,7 @@ mod tests {
     #[test]
     fn test_simpleloop_problem() {
         let problem = problem_for_tests();
-        let ans = solve_simpleloop(&problem);
+        let ans = solve_simpleloop(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,192 @@
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    Seq, Size, Spaces,
+};
+use crate::solver::{count_true, IntVarArray1D, SolveContext, Solver};
+
+/// The numbers of visible buildings, seen from above each column, from below each column, from
+/// the left of each row and from the right of each row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkyscrapersClues {
+    pub up: Vec<Option<i32>>,
+    pub down: Vec<Option<i32>>,
+    pub left: Vec<Option<i32>>,
+    pub right: Vec<Option<i32>>,
+}
+
+/// Adds the constraint that exactly `n` buildings in `line` are visible from its first cell.
+/// A building is visible iff it is taller than the running maximum of the buildings before it.
+fn add_visibility_constraint(solver: &mut Solver, line: IntVarArray1D, n: i32, size: i32) {
+    let len = line.len();
+    let running_max = &solver.int_var_1d(len, 1, size);
+    solver.add_expr(running_max.at(0).eq(line.at(0)));
+    // the first building is always visible
+    let mut is_visible = vec![];
+    for i in 1..len {
+        let prev = running_max.at(i - 1);
+        let is_taller = line.at(i).gt(prev.clone());
+        solver.add_expr(running_max.at(i).eq(is_taller.ite(line.at(i), prev)));
+        is_visible.push(is_taller);
+    }
+    solver.add_expr((count_true(is_visible) + 1).eq(n));
+}
+
+pub fn solve_skyscrapers(
+    clues: &SkyscrapersClues,
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let n = clues.up.len();
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((n, n), 1, n as i32);
+    solver.add_answer_key_int(num);
+
+    for i in 0..n {
+        solver.all_different(num.slice_fixed_y((i, ..)));
+        solver.all_different(num.slice_fixed_x((.., i)));
+    }
+
+    for i in 0..n {
+        if let Some(k) = clues.up[i] {
+            add_visibility_constraint(&mut solver, num.slice_fixed_x((.., i)), k, n as i32);
+        }
+        if let Some(k) = clues.down[i] {
+            let line = num.slice_fixed_x((.., i)).reverse();
+            add_visibility_constraint(&mut solver, line, k, n as i32);
+        }
+        if let Some(k) = clues.left[i] {
+            add_visibility_constraint(&mut solver, num.slice_fixed_y((i, ..)), k, n as i32);
+        }
+        if let Some(k) = clues.right[i] {
+            let line = num.slice_fixed_y((i, ..)).reverse();
+            add_visibility_constraint(&mut solver, line, k, n as i32);
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = SkyscrapersClues;
+
+struct SkyscrapersCluesCombinator;
+
+impl SkyscrapersCluesCombinator {
+    fn seq(len: usize) -> impl Combinator<Vec<Option<i32>>> {
+        Seq::new(
+            Choice::new(vec![
+                Box::new(Optionalize::new(HexInt)),
+                Box::new(Spaces::new(None, 'g')),
+            ]),
+            len,
+        )
+    }
+}
+
+impl Combinator<Problem> for SkyscrapersCluesCombinator {
+    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let clues = &input[0];
+        let mut seq = clues.up.clone();
+        seq.extend(clues.down.iter().copied());
+        seq.extend(clues.left.iter().copied());
+        seq.extend(clues.right.iter().copied());
+        let (_, ret) = Self::seq(seq.len()).serialize(ctx, &[seq])?;
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
+        let h = ctx.height?;
+        let w = ctx.width?;
+        if h != w {
+            return None;
+        }
+        let (n_read, mut seq) = Self::seq(w * 4).deserialize(ctx, input)?;
+        assert_eq!(seq.len(), 1);
+        let mut up = seq.pop().unwrap();
+        let mut down = up.split_off(w);
+        let mut left = down.split_off(w);
+        let right = left.split_off(w);
+        Some((
+            n_read,
+            vec![SkyscrapersClues {
+                up,
+                down,
+                left,
+                right,
+            }],
+        ))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(SkyscrapersCluesCombinator)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let n = problem.up.len();
+    problem_to_url_with_context(
+        combinator(),
+        "skyscrapers",
+        problem.clone(),
+        &Context::sized(n, n),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["skyscrapers"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        SkyscrapersClues {
+            up: vec![Some(3), None, Some(3), None],
+            down: vec![None, None, Some(2), Some(2)],
+            left: vec![Some(2), None, None, None],
+            right: vec![None, None, None, None],
+        }
+    }
+
+    #[test]
+    fn test_skyscrapers_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_skyscrapers(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected =
+            util::tests::to_option_2d([[2, 4, 1, 3], [1, 3, 2, 4], [3, 2, 4, 1], [4, 1, 3, 2]]);
+        assert_eq!(ans, expected);
+
+        let n_visible = |line: Vec<i32>| {
+            let mut max = 0;
+            let mut ret = 0;
+            for v in line {
+                if v > max {
+                    ret += 1;
+                    max = v;
+                }
+            }
+            ret
+        };
+        let column = |x: usize| (0..4).map(|y| ans[y][x].unwrap()).collect::<Vec<_>>();
+        let row = |y: usize| (0..4).map(|x| ans[y][x].unwrap()).collect::<Vec<_>>();
+        assert_eq!(n_visible(column(0)), 3);
+        assert_eq!(n_visible(column(2)), 3);
+        assert_eq!(n_visible(column(2).into_iter().rev().collect()), 2);
+        assert_eq!(n_visible(column(3).into_iter().rev().collect()), 2);
+        assert_eq!(n_visible(row(0)), 2);
+    }
+
+    #[test]
+    fn test_skyscrapers_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?skyscrapers/4/4/3g3i222m";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -4,7 +4,7 @@ use crate::serializer::{
     url_to_problem, Choice, Combinator, Context, ContextBasedGrid, DecInt, Dict, FixedLengthHexInt,
     MaybeSkip, Seq, Sequencer, Size, Spaces, Tuple2,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub enum GateDir {
     Horizontal,
@@ -21,6 +21,7 @@ pub fn solve_slalom(
     origin: (usize, usize),
     is_black: &[Vec<bool>],
     gates: &[Gate],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(is_black);
 
@@ -118,7 +119,7 @@ pub fn solve_slalom(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(line))
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -368,7 +369,7 @@ mod tests {
             },
         ];
 
-        let ans = solve_slalom((5, 1), &is_black, &gates);
+        let ans = solve_slalom((5, 1), &is_black, &gates, &mut SolveContext::new());
         assert!(ans.is_some());
 
         let deserialized = deserialize_problem_as_primitive(
@@ -3,13 +3,16 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub const SLASHPACK_EMPTY: i32 = 0;
 pub const SLASHPACK_SLASH: i32 = 1;
 pub const SLASHPACK_BACKSLASH: i32 = 2;
 
-pub fn solve_slashpack(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<i32>>>> {
+pub fn solve_slashpack(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -135,7 +138,7 @@ pub fn solve_slashpack(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<i32>
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(ans))
+    solver.irrefutable_facts(ctx).map(|f| f.get(ans))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -174,7 +177,7 @@ mod tests {
     #[test]
     fn test_slashpack_problem() {
         let problem = problem_for_tests();
-        let ans = solve_slashpack(&problem);
+        let ans = solve_slashpack(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
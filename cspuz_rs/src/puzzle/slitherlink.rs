@@ -4,10 +4,11 @@ use crate::serializer::{
     from_base16, problem_to_url, to_base16, url_to_problem, Choice, Combinator, Context, Grid,
     Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_slitherlink(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -26,7 +27,7 @@ pub fn solve_slitherlink(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_line))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_line))
 }
 
 pub struct SlitherlinkClueCombinator;
@@ -95,7 +96,7 @@ mod tests {
         ];
         assert_eq!(serialize_problem(&problem), Some(String::from("https://puzz.link/p?slither/4/4/dgdh2c71")));
         assert_eq!(problem, deserialize_problem("https://puzz.link/p?slither/4/4/dgdh2c71").unwrap());
-        let ans = solve_slitherlink(&problem);
+        let ans = solve_slitherlink(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = graph::BoolGridEdgesIrrefutableFacts {
@@ -0,0 +1,174 @@
+use crate::graph;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
+    MultiDigit, Size,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Spiral Galaxies (Tentai Show) problem.
+///
+/// `centers` has the shape `(2 * h - 1, 2 * w - 1)`: `centers[y][x]` is true if there is a galaxy
+/// center at the point `(y / 2, x / 2)` in cell units, so that odd coordinates denote centers on
+/// the edges or the vertices of the cells.
+pub fn solve_spiral_galaxies(
+    centers: &[Vec<bool>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    let h = centers.len().div_ceil(2);
+    let w = centers[0].len().div_ceil(2);
+
+    let mut solver = Solver::new();
+    let edges = &graph::BoolInnerGridEdges::new(&mut solver, (h, w));
+    solver.add_answer_key_bool(&edges.horizontal);
+    solver.add_answer_key_bool(&edges.vertical);
+
+    let mut center_pos = vec![];
+    for (cy, row) in centers.iter().enumerate() {
+        for (cx, &c) in row.iter().enumerate() {
+            if c {
+                center_pos.push((cy, cx));
+            }
+        }
+    }
+    if center_pos.is_empty() {
+        return None;
+    }
+
+    // the galaxy ids are only compared with constants, so they are direct-encoded
+    let galaxy_id = &solver.int_var_2d((h, w), 0, center_pos.len() as i32 - 1);
+    solver.add_expr(
+        edges.horizontal.iff(
+            galaxy_id
+                .slice((..(h - 1), ..))
+                .ne(galaxy_id.slice((1.., ..))),
+        ),
+    );
+    solver.add_expr(
+        edges.vertical.iff(
+            galaxy_id
+                .slice((.., ..(w - 1)))
+                .ne(galaxy_id.slice((.., 1..))),
+        ),
+    );
+
+    for (i, &(cy, cx)) in center_pos.iter().enumerate() {
+        let i = i as i32;
+        graph::active_vertices_connected_2d(&mut solver, galaxy_id.eq(i));
+
+        // the cells touching the center belong to its galaxy
+        for y in (cy / 2)..=cy.div_ceil(2) {
+            for x in (cx / 2)..=cx.div_ceil(2) {
+                solver.add_expr(galaxy_id.at((y, x)).eq(i));
+            }
+        }
+
+        // the galaxy is symmetric about the center
+        for y in 0..h {
+            for x in 0..w {
+                if y <= cy && cy - y < h && x <= cx && cx - x < w {
+                    solver.add_expr(
+                        galaxy_id
+                            .at((y, x))
+                            .eq(i)
+                            .imp(galaxy_id.at((cy - y, cx - x)).eq(i)),
+                    );
+                } else {
+                    solver.add_expr(galaxy_id.at((y, x)).ne(i));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
+}
+
+type Problem = Vec<Vec<bool>>;
+
+struct CentersCombinator;
+
+impl CentersCombinator {
+    fn grid() -> impl Combinator<Vec<Vec<bool>>> {
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(2, 5),
+            |x: bool| Some(if x { 1 } else { 0 }),
+            |n: i32| Some(n == 1),
+        ))
+    }
+
+    fn lattice_context(ctx: &Context) -> Option<Context> {
+        let h = ctx.height?;
+        let w = ctx.width?;
+        Some(Context::sized(h * 2 - 1, w * 2 - 1))
+    }
+}
+
+impl Combinator<Problem> for CentersCombinator {
+    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+        Self::grid().serialize(&Self::lattice_context(ctx)?, input)
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
+        Self::grid().deserialize(&Self::lattice_context(ctx)?, input)
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(CentersCombinator)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.len().div_ceil(2);
+    let width = problem[0].len().div_ceil(2);
+    problem_to_url_with_context(
+        combinator(),
+        "tentaisho",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["tentaisho", "spiralgalaxies"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::util;
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // centers at the top-left cell, the edge between (1, 0) and (1, 1), the middle of the
+        // rightmost column and the middle of the bottom row
+        let mut centers = vec![vec![false; 5]; 5];
+        centers[0][0] = true;
+        centers[2][1] = true;
+        centers[2][4] = true;
+        centers[4][2] = true;
+        centers
+    }
+
+    #[test]
+    fn test_spiral_galaxies_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_spiral_galaxies(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        // galaxies:
+        // B A C
+        // A A C
+        // A D C
+        let expected = graph::BoolInnerGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([[1, 0, 0], [0, 1, 0]]),
+            vertical: util::tests::to_option_bool_2d([[1, 1], [0, 1], [1, 1]]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_spiral_galaxies_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?tentaisho/3/3/g0904";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
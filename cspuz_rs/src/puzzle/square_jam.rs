@@ -3,10 +3,11 @@ use crate::graph;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_square_jam(
     clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -79,7 +80,7 @@ pub fn solve_square_jam(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(&is_border))
+    solver.irrefutable_facts(ctx).map(|f| f.get(&is_border))
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -118,7 +119,7 @@ mod tests {
     #[test]
     fn test_square_jam_problem() {
         let problem = problem_for_tests();
-        let ans = solve_square_jam(&problem);
+        let ans = solve_square_jam(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         #[rustfmt::skip]
@@ -1,9 +1,10 @@
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_star_battle(
     n: usize,
     k: i32,
     rooms: &[Vec<(usize, usize)>],
+    ctx: &mut SolveContext,
 ) -> Option<Vec<Vec<Option<bool>>>> {
     let mut solver = Solver::new();
     let has_star = solver.bool_var_2d((n, n));
@@ -22,7 +23,7 @@ pub fn solve_star_battle(
         solver.add_expr(has_star.select(room).count_true().eq(k));
     }
 
-    solver.irrefutable_facts().map(|f| f.get(&has_star))
+    solver.irrefutable_facts(ctx).map(|f| f.get(&has_star))
 }
 
 #[cfg(test)]
@@ -42,7 +43,7 @@ mod tests {
             vec![(4, 1), (4, 2), (4, 4), (5, 2), (5, 3), (5, 4)],
             vec![(4, 5), (5, 5)],
         ];
-        let ans = solve_star_battle(n, k, &rooms);
+        let ans = solve_star_battle(n, k, &rooms, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_bool_2d([
@@ -0,0 +1,288 @@
+use super::util;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Dict,
+    HexInt, Map, MultiDigit, Sequencer, Size, Tuple2,
+};
+use crate::solver::{any, count_true, SolveContext, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatueParkClue {
+    /// A cell which must be covered by a piece.
+    Black,
+    /// A cell which must not be covered by a piece.
+    White,
+}
+
+/// A piece, given as a bitmap of its cells.
+pub type Piece = Vec<Vec<bool>>;
+
+fn rotate(piece: &Piece) -> Piece {
+    let (h, w) = util::infer_shape(piece);
+    (0..w)
+        .map(|x| (0..h).map(|y| piece[h - 1 - y][x]).collect())
+        .collect()
+}
+
+fn flip(piece: &Piece) -> Piece {
+    piece
+        .iter()
+        .map(|row| row.iter().rev().copied().collect())
+        .collect()
+}
+
+/// Returns the distinct orientations of `piece`: only `piece` itself unless `allow_transform`,
+/// in which case all its rotations and reflections.
+fn orientations(piece: &Piece, allow_transform: bool) -> Vec<Piece> {
+    let mut ret = vec![piece.clone()];
+    if allow_transform {
+        let mut p = piece.clone();
+        for i in 0..8 {
+            if i == 4 {
+                p = flip(&p);
+            } else if i > 0 {
+                p = rotate(&p);
+            }
+            if !ret.contains(&p) {
+                ret.push(p.clone());
+            }
+        }
+    }
+    ret
+}
+
+/// Solves a Statue Park problem. Every piece in `pieces` is placed exactly once, so that no two
+/// pieces overlap or touch orthogonally. Pieces may be rotated or reflected iff `allow_transform`.
+/// Returns whether each cell is covered by a piece.
+pub fn solve_statue_park(
+    clues: &[Vec<Option<StatueParkClue>>],
+    pieces: &[Piece],
+    allow_transform: bool,
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    // 0 for uncovered cells and i for cells covered by the i-th piece
+    let piece_id = &solver.int_var_2d((h, w), 0, pieces.len() as i32);
+    solver.add_expr(is_black.iff(piece_id.ne(0)));
+    solver.add_expr(
+        (is_black.slice((..(h - 1), ..)) & is_black.slice((1.., ..))).imp(
+            piece_id
+                .slice((..(h - 1), ..))
+                .eq(piece_id.slice((1.., ..))),
+        ),
+    );
+    solver.add_expr(
+        (is_black.slice((.., ..(w - 1))) & is_black.slice((.., 1..))).imp(
+            piece_id
+                .slice((.., ..(w - 1)))
+                .eq(piece_id.slice((.., 1..))),
+        ),
+    );
+
+    let mut covering = vec![vec![vec![]; w]; h];
+    for (i, piece) in pieces.iter().enumerate() {
+        let mut placements = vec![];
+        for p in orientations(piece, allow_transform) {
+            let (ph, pw) = util::infer_shape(&p);
+            if ph > h || pw > w {
+                continue;
+            }
+            for y in 0..=(h - ph) {
+                for x in 0..=(w - pw) {
+                    let cells = (0..ph)
+                        .flat_map(|dy| (0..pw).map(move |dx| (dy, dx)))
+                        .filter(|&(dy, dx)| p[dy][dx])
+                        .map(|(dy, dx)| (y + dy, x + dx))
+                        .collect::<Vec<_>>();
+                    if cells
+                        .iter()
+                        .any(|&(y, x)| clues[y][x] == Some(StatueParkClue::White))
+                    {
+                        continue;
+                    }
+                    let v = solver.bool_var();
+                    for &(y, x) in &cells {
+                        solver.add_expr(v.imp(piece_id.at((y, x)).eq(i as i32 + 1)));
+                        covering[y][x].push(v.clone());
+                    }
+                    placements.push(v);
+                }
+            }
+        }
+        solver.add_expr(count_true(&placements).eq(1));
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            solver.add_expr(is_black.at((y, x)).iff(any(&covering[y][x])));
+            match clues[y][x] {
+                Some(StatueParkClue::Black) => solver.add_expr(is_black.at((y, x))),
+                Some(StatueParkClue::White) => solver.add_expr(!is_black.at((y, x))),
+                None => (),
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = (Vec<Vec<Option<StatueParkClue>>>, (Vec<Piece>, bool));
+
+fn piece_combinator() -> impl Combinator<Vec<Vec<bool>>> {
+    ContextBasedGrid::new(Map::new(
+        MultiDigit::new(2, 5),
+        |x: bool| Some(if x { 1 } else { 0 }),
+        |n: i32| Some(n == 1),
+    ))
+}
+
+/// Serializes the pieces after a `/`, preceded by `t` if they may be rotated or reflected. Each
+/// piece is its height and width as `HexInt`s followed by its bitmap.
+struct PiecesCombinator;
+
+impl Combinator<(Vec<Piece>, bool)> for PiecesCombinator {
+    fn serialize(&self, ctx: &Context, input: &[(Vec<Piece>, bool)]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let (pieces, allow_transform) = &input[0];
+
+        let mut ret = vec![b'/'];
+        if *allow_transform {
+            ret.push(b't');
+        }
+        for piece in pieces {
+            let (ph, pw) = util::infer_shape(piece);
+            let (_, app) = HexInt.serialize(ctx, &[ph as i32])?;
+            ret.extend(app);
+            let (_, app) = HexInt.serialize(ctx, &[pw as i32])?;
+            ret.extend(app);
+            let (_, app) = piece_combinator()
+                .serialize(&Context::sized(ph, pw), std::slice::from_ref(piece))?;
+            ret.extend(app);
+        }
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<(Vec<Piece>, bool)>)> {
+        let mut sequencer = Sequencer::new(input);
+        sequencer.deserialize(ctx, Dict::new((), "/"))?;
+        let allow_transform = sequencer.deserialize(ctx, Dict::new((), "t")).is_some();
+
+        let mut pieces = vec![];
+        while let Some(ph) = sequencer.deserialize(ctx, HexInt) {
+            let pw = sequencer.deserialize_one_elem(ctx, HexInt)?;
+            if ph[0] <= 0 || pw <= 0 {
+                return None;
+            }
+            let piece = sequencer.deserialize_one_elem(
+                &Context::sized(ph[0] as usize, pw as usize),
+                piece_combinator(),
+            )?;
+            pieces.push(piece);
+        }
+
+        Some((sequencer.n_read(), vec![(pieces, allow_transform)]))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(3, 3),
+            |x: Option<StatueParkClue>| {
+                Some(match x {
+                    None => 0,
+                    Some(StatueParkClue::White) => 1,
+                    Some(StatueParkClue::Black) => 2,
+                })
+            },
+            |n: i32| {
+                Some(match n {
+                    0 => None,
+                    1 => Some(StatueParkClue::White),
+                    2 => Some(StatueParkClue::Black),
+                    _ => return None,
+                })
+            },
+        )),
+        PiecesCombinator,
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = util::infer_shape(&problem.0);
+    problem_to_url_with_context(
+        combinator(),
+        "statuepark",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["statuepark", "statue_park"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut clues = vec![vec![None; 5]; 5];
+        clues[0][3] = Some(StatueParkClue::Black);
+        clues[0][4] = Some(StatueParkClue::Black);
+        clues[3][2] = Some(StatueParkClue::Black);
+        clues[3][3] = Some(StatueParkClue::White);
+        clues[4][4] = Some(StatueParkClue::Black);
+        let pieces = vec![
+            util::tests::to_bool_2d([[1, 1, 1]]),
+            util::tests::to_bool_2d([[1, 0], [1, 1]]),
+            util::tests::to_bool_2d([[1], [1]]),
+        ];
+        (clues, (pieces, false))
+    }
+
+    #[test]
+    fn test_statue_park_problem() {
+        let (clues, (pieces, allow_transform)) = problem_for_tests();
+        let ans = solve_statue_park(&clues, &pieces, allow_transform, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 0, 1, 1, 1],
+            [0, 0, 0, 0, 0],
+            [0, 1, 0, 0, 0],
+            [0, 1, 1, 0, 1],
+            [0, 0, 0, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_statue_park_transform() {
+        // a horizontal I-tromino fits in a 3x1 grid only if it can be rotated
+        let clues = vec![vec![None]; 3];
+        let pieces = vec![util::tests::to_bool_2d([[1, 1, 1]])];
+        assert_eq!(
+            solve_statue_park(&clues, &pieces, false, &mut SolveContext::new()),
+            None
+        );
+        assert_eq!(
+            solve_statue_park(&clues, &pieces, true, &mut SolveContext::new()),
+            Some(vec![vec![Some(true)]; 3])
+        );
+    }
+
+    #[test]
+    fn test_statue_park_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?statuepark/5/5/0o000290i/13s22m21o";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
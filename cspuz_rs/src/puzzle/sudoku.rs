@@ -2,63 +2,38 @@ use super::util;
 use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{IntVarArray2D, SolveContext, Solver};
 
-pub fn solve_sudoku(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<i32>>>> {
+pub fn solve_sudoku(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
     let (h, w) = util::infer_shape(clues);
     if h != w {
         return None;
     }
     let n = h;
-    let (bh, bw) = match n {
-        4 => (2, 2),
-        6 => (2, 3),
-        9 => (3, 3),
-        16 => (4, 4),
-        25 => (5, 5),
-        _ => return None,
-    };
+    let block_shape = block_shape(n)?;
 
     let mut solver = Solver::new();
     let num = &solver.int_var_2d((n, n), 1, n as i32);
     solver.add_answer_key_int(num);
 
-    for i in 0..n {
-        solver.all_different(num.slice_fixed_y((i, ..)));
-        solver.all_different(num.slice_fixed_x((.., i)));
-    }
-    for i in 0..bw {
-        for j in 0..bh {
-            solver.all_different(num.slice((((i * bh)..((i + 1) * bh)), ((j * bw)..((j + 1) * bw)))));
-        }
-    }
-    for y in 0..n {
-        for x in 0..n {
-            if let Some(val) = clues[y][x] {
-                if val > 0 {
-                    solver.add_expr(num.at((y, x)).eq(val));
-                }
-            }
-        }
-    }
+    add_constraints(&mut solver, num, clues, block_shape);
 
-    solver.irrefutable_facts().map(|f| f.get(num))
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
 }
 
-pub fn solve_sudoku_as_cands(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Vec<bool>>>> {
+pub fn solve_sudoku_as_cands(
+    clues: &[Vec<Option<i32>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Vec<bool>>>> {
     let (h, w) = util::infer_shape(clues);
     if h != w {
         return None;
     }
     let n = h;
-    let (bh, bw) = match n {
-        4 => (2, 2),
-        6 => (2, 3),
-        9 => (3, 3),
-        16 => (4, 4),
-        25 => (5, 5),
-        _ => return None,
-    };
+    let block_shape = block_shape(n)?;
 
     let mut solver = Solver::new();
     let num = &solver.int_var_2d((n, n), 1, n as i32);
@@ -81,13 +56,81 @@ pub fn solve_sudoku_as_cands(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Vec<b
         }
     }
 
+    add_constraints(&mut solver, num, clues, block_shape);
+
+    solver.irrefutable_facts(ctx).map(|f| {
+        let mut ret = vec![];
+        for y in 0..n {
+            let mut row = vec![];
+            for x in 0..n {
+                row.push(
+                    f.get(&cands[y][x])
+                        .into_iter()
+                        .map(|x| x.unwrap_or(true))
+                        .collect(),
+                );
+            }
+            ret.push(row);
+        }
+        ret
+    })
+}
+
+pub fn enumerate_answers_sudoku(
+    clues: &[Vec<Option<i32>>],
+    num_max_answers: usize,
+) -> Option<Vec<Vec<Vec<i32>>>> {
+    let (h, w) = util::infer_shape(clues);
+    if h != w {
+        return None;
+    }
+    let n = h;
+    let block_shape = block_shape(n)?;
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((n, n), 1, n as i32);
+    solver.add_answer_key_int(num);
+
+    add_constraints(&mut solver, num, clues, block_shape);
+
+    Some(
+        solver
+            .answer_iter()
+            .take(num_max_answers)
+            .map(|f| f.get_unwrap(num))
+            .collect(),
+    )
+}
+
+/// Returns the shape (height, width) of blocks in a Sudoku of size `n`, if supported.
+pub fn block_shape(n: usize) -> Option<(usize, usize)> {
+    match n {
+        4 => Some((2, 2)),
+        6 => Some((2, 3)),
+        9 => Some((3, 3)),
+        16 => Some((4, 4)),
+        25 => Some((5, 5)),
+        _ => None,
+    }
+}
+
+pub fn add_constraints(
+    solver: &mut Solver,
+    num: &IntVarArray2D,
+    clues: &[Vec<Option<i32>>],
+    block_shape: (usize, usize),
+) {
+    let n = clues.len();
+    let (bh, bw) = block_shape;
+
     for i in 0..n {
         solver.all_different(num.slice_fixed_y((i, ..)));
         solver.all_different(num.slice_fixed_x((.., i)));
     }
     for i in 0..bw {
         for j in 0..bh {
-            solver.all_different(num.slice((((i * bh)..((i + 1) * bh)), ((j * bw)..((j + 1) * bw)))));
+            solver
+                .all_different(num.slice((((i * bh)..((i + 1) * bh)), ((j * bw)..((j + 1) * bw)))));
         }
     }
     for y in 0..n {
@@ -99,23 +142,6 @@ pub fn solve_sudoku_as_cands(clues: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Vec<b
             }
         }
     }
-
-    solver.irrefutable_facts().map(|f| {
-        let mut ret = vec![];
-        for y in 0..n {
-            let mut row = vec![];
-            for x in 0..n {
-                row.push(
-                    f.get(&cands[y][x])
-                        .into_iter()
-                        .map(|x| x.unwrap_or(true))
-                        .collect(),
-                );
-            }
-            ret.push(row);
-        }
-        ret
-    })
 }
 
 type Problem = Vec<Vec<Option<i32>>>;
@@ -159,7 +185,7 @@ mod tests {
     #[test]
     fn test_sudoku_problem() {
         let problem = problem_for_tests();
-        let ans = solve_sudoku(&problem);
+        let ans = solve_sudoku(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_2d([
@@ -176,10 +202,52 @@ mod tests {
         assert_eq!(ans, expected);
     }
 
+    #[test]
+    fn test_sudoku_enumerate_answers() {
+        // the clues 1 and 3 in the top two rows can be swapped
+        let problem = vec![
+            vec![None, Some(2), None, Some(4)],
+            vec![None, Some(4), None, Some(2)],
+            vec![Some(2), Some(1), Some(4), Some(3)],
+            vec![Some(4), Some(3), Some(2), Some(1)],
+        ];
+        let mut ans = enumerate_answers_sudoku(&problem, 10).unwrap();
+        ans.sort();
+        let expected = vec![
+            vec![
+                vec![1, 2, 3, 4],
+                vec![3, 4, 1, 2],
+                vec![2, 1, 4, 3],
+                vec![4, 3, 2, 1],
+            ],
+            vec![
+                vec![3, 2, 1, 4],
+                vec![1, 4, 3, 2],
+                vec![2, 1, 4, 3],
+                vec![4, 3, 2, 1],
+            ],
+        ];
+        assert_eq!(ans, expected);
+
+        assert_eq!(enumerate_answers_sudoku(&problem, 1).unwrap().len(), 1);
+        assert_eq!(enumerate_answers_sudoku(&vec![vec![None; 5]; 5], 10), None);
+    }
+
     #[test]
     fn test_sudoku_serializer() {
         let problem = problem_for_tests();
         let url = "https://puzz.link/p?sudoku/9/9/k8g1g7i2i99o2g3h75q19h5g4o83i4i6g4g5k";
         util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
     }
+
+    #[test]
+    fn test_sudoku_serializer_solved_grid() {
+        let solved = solve_sudoku(&problem_for_tests(), &mut SolveContext::new())
+            .unwrap()
+            .into_iter()
+            .map(|row| row.into_iter().map(|n| Some(n.unwrap())).collect())
+            .collect::<Problem>();
+        let url = serialize_problem(&solved).unwrap();
+        assert_eq!(deserialize_problem(&url), Some(solved));
+    }
 }
@@ -4,7 +4,7 @@ use crate::serializer::{
     from_base36, problem_to_url, to_base36, url_to_problem, Choice, Combinator, Context, Grid,
     Optionalize, Spaces,
 };
-use crate::solver::{all, any, count_true, Solver, FALSE};
+use crate::solver::{all, any, count_true, SolveContext, Solver, FALSE};
 
 const EIGHT_NEIGHBORS: [(i32, i32); 8] = [
     (-1, -1),
@@ -17,7 +17,10 @@ const EIGHT_NEIGHBORS: [(i32, i32); 8] = [
     (0, -1),
 ];
 
-pub fn solve_tapa(clues: &[Vec<Option<[i32; 4]>>]) -> Option<Vec<Vec<Option<bool>>>> {
+pub fn solve_tapa(
+    clues: &[Vec<Option<[i32; 4]>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
     let mut solver = Solver::new();
@@ -99,7 +102,7 @@ pub fn solve_tapa(clues: &[Vec<Option<[i32; 4]>>]) -> Option<Vec<Vec<Option<bool
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
 }
 
 struct TapaClueCombinator;
@@ -240,7 +243,7 @@ mod tests {
     #[test]
     fn test_tapa_problem1() {
         let problem = problem_for_tests1();
-        let ans = solve_tapa(&problem);
+        let ans = solve_tapa(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = [
@@ -267,7 +270,7 @@ mod tests {
     #[test]
     fn test_tapa_problem2() {
         let problem = problem_for_tests2();
-        let ans = solve_tapa(&problem);
+        let ans = solve_tapa(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
         let expected = crate::puzzle::util::tests::to_option_bool_2d([
@@ -0,0 +1,176 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, Spaces};
+use crate::solver::{SolveContext, Solver};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TatamibariClue {
+    /// `+`: the rectangle is a square.
+    Square,
+    /// `-`: the rectangle is wider than it is tall.
+    Horizontal,
+    /// `|`: the rectangle is taller than it is wide.
+    Vertical,
+}
+
+pub fn solve_tatamibari(
+    clues: &[Vec<Option<TatamibariClue>>],
+    ctx: &mut SolveContext,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let edges = &graph::BoolInnerGridEdges::new(&mut solver, (h, w));
+    solver.add_answer_key_bool(&edges.horizontal);
+    solver.add_answer_key_bool(&edges.vertical);
+
+    for y in 1..h {
+        for x in 1..w {
+            let up = edges.vertical.at((y - 1, x - 1));
+            let down = edges.vertical.at((y, x - 1));
+            let left = edges.horizontal.at((y - 1, x - 1));
+            let right = edges.horizontal.at((y - 1, x));
+
+            // every region is a rectangle
+            solver.add_expr(!((left.clone() ^ right.clone()) & (up.clone() ^ down.clone())));
+            // no four rectangles meet at a point
+            solver.add_expr(!(up & down & left & right));
+        }
+    }
+
+    let mut clue_pos = vec![];
+    for (y, row) in clues.iter().enumerate() {
+        for (x, &clue) in row.iter().enumerate() {
+            if let Some(c) = clue {
+                clue_pos.push((y, x, c));
+            }
+        }
+    }
+
+    if clue_pos.is_empty() {
+        return None;
+    }
+
+    let ids = solver.int_var_2d((h, w), 0, clue_pos.len() as i32 - 1);
+    for (i, &(y, x, c)) in clue_pos.iter().enumerate() {
+        graph::active_vertices_connected_2d(&mut solver, ids.eq(i as i32));
+        solver.add_expr(ids.at((y, x)).eq(i as i32));
+
+        let rect_up = (!edges.horizontal.slice_fixed_x((..y, x)))
+            .reverse()
+            .consecutive_prefix_true();
+        let rect_down = (!edges.horizontal.slice_fixed_x((y.., x))).consecutive_prefix_true();
+        let rect_height = rect_up + rect_down + 1;
+
+        let rect_left = (!edges.vertical.slice_fixed_y((y, ..x)))
+            .reverse()
+            .consecutive_prefix_true();
+        let rect_right = (!edges.vertical.slice_fixed_y((y, x..))).consecutive_prefix_true();
+        let rect_width = rect_left + rect_right + 1;
+
+        solver.add_expr(match c {
+            TatamibariClue::Square => rect_height.eq(rect_width),
+            TatamibariClue::Horizontal => rect_height.lt(rect_width),
+            TatamibariClue::Vertical => rect_height.gt(rect_width),
+        });
+    }
+    solver.add_expr(
+        edges
+            .horizontal
+            .iff(ids.slice((..(h - 1), ..)).ne(ids.slice((1.., ..)))),
+    );
+    solver.add_expr(
+        edges
+            .vertical
+            .iff(ids.slice((.., ..(w - 1))).ne(ids.slice((.., 1..)))),
+    );
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
+}
+
+type Problem = Vec<Vec<Option<TatamibariClue>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Dict::new(Some(TatamibariClue::Square), "1")),
+        Box::new(Dict::new(Some(TatamibariClue::Horizontal), "2")),
+        Box::new(Dict::new(Some(TatamibariClue::Vertical), "3")),
+        Box::new(Spaces::new(None, 'g')),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "tatamibari", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["tatamibari"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let s = Some(TatamibariClue::Square);
+        let hr = Some(TatamibariClue::Horizontal);
+        let vr = Some(TatamibariClue::Vertical);
+        vec![
+            vec![hr, None, None, None],
+            vec![vr, s, None, hr],
+            vec![None, None, None, hr],
+            vec![hr, None, s, s],
+        ]
+    }
+
+    #[test]
+    fn test_tatamibari_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_tatamibari(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        // rectangles:
+        // A A A A
+        // B C D D
+        // B E E E
+        // F F G H
+        let expected = graph::BoolInnerGridEdgesIrrefutableFacts {
+            horizontal: util::tests::to_option_bool_2d([[1, 1, 1, 1], [0, 1, 1, 1], [1, 1, 1, 1]]),
+            vertical: util::tests::to_option_bool_2d([[0, 0, 0], [1, 1, 0], [1, 0, 0], [0, 1, 1]]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_tatamibari_shape() {
+        // a 1x2 rectangle is not a square
+        let problem = vec![vec![Some(TatamibariClue::Square), None]];
+        assert_eq!(solve_tatamibari(&problem, &mut SolveContext::new()), None);
+
+        // a 2x1 rectangle is taller than it is wide
+        let problem = vec![vec![Some(TatamibariClue::Vertical)], vec![None]];
+        let ans = solve_tatamibari(&problem, &mut SolveContext::new());
+        assert_eq!(
+            ans,
+            Some(graph::BoolInnerGridEdgesIrrefutableFacts {
+                horizontal: util::tests::to_option_bool_2d([[0]]),
+                vertical: vec![vec![], vec![]],
+            })
+        );
+
+        // four rectangles may not meet at a point
+        let problem = vec![
+            vec![Some(TatamibariClue::Square), Some(TatamibariClue::Square)],
+            vec![Some(TatamibariClue::Square), Some(TatamibariClue::Square)],
+        ];
+        assert_eq!(solve_tatamibari(&problem, &mut SolveContext::new()), None);
+    }
+
+    #[test]
+    fn test_tatamibari_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?tatamibari/4/4/2i31g2i22g11";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,177 @@
+use super::util;
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
+    MultiDigit, OutsideClues, OutsideCluesCombinator, Size, Tuple2,
+};
+use crate::solver::{count_true, BoolVar, SolveContext, Solver};
+
+pub fn solve_tents(
+    is_tree: &[Vec<bool>],
+    clue_vertical: &[Option<i32>],
+    clue_horizontal: &[Option<i32>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(is_tree);
+
+    let mut solver = Solver::new();
+    let is_tent = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_tent);
+
+    // each tree is matched with exactly one adjacent tent, and each tent with exactly one tree
+    let mut tree_matches: Vec<Vec<Vec<BoolVar>>> = vec![vec![vec![]; w]; h];
+    let mut tent_matches: Vec<Vec<Vec<BoolVar>>> = vec![vec![vec![]; w]; h];
+    for y in 0..h {
+        for x in 0..w {
+            if !is_tree[y][x] {
+                continue;
+            }
+            solver.add_expr(!is_tent.at((y, x)));
+            for (y2, x2) in is_tent.four_neighbor_indices((y, x)) {
+                if is_tree[y2][x2] {
+                    continue;
+                }
+                let m = solver.bool_var();
+                solver.add_expr(m.imp(is_tent.at((y2, x2))));
+                tree_matches[y][x].push(m.clone());
+                tent_matches[y2][x2].push(m);
+            }
+        }
+    }
+    for y in 0..h {
+        for x in 0..w {
+            if is_tree[y][x] {
+                solver.add_expr(count_true(&tree_matches[y][x]).eq(1));
+            } else {
+                solver.add_expr(count_true(&tent_matches[y][x]).eq(is_tent.at((y, x)).ite(1, 0)));
+            }
+        }
+    }
+
+    // no two tents touch, even diagonally
+    if h >= 2 && w >= 2 {
+        for y in 0..(h - 1) {
+            for x in 0..(w - 1) {
+                solver.add_expr(is_tent.slice((y..(y + 2), x..(x + 2))).count_true().le(1));
+            }
+        }
+    } else {
+        solver.add_expr(!(is_tent.slice((..(h - 1), ..)) & is_tent.slice((1.., ..))));
+        solver.add_expr(!(is_tent.slice((.., ..(w - 1))) & is_tent.slice((.., 1..))));
+    }
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(is_tent.slice_fixed_x((.., x)).count_true().eq(n));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            solver.add_expr(is_tent.slice_fixed_y((y, ..)).count_true().eq(n));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_tent))
+}
+
+type Problem = (OutsideClues, Vec<Vec<bool>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        OutsideCluesCombinator,
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(2, 5),
+            |x: bool| Some(if x { 1 } else { 0 }),
+            |n: i32| Some(n == 1),
+        )),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (h, w) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(
+        combinator(),
+        "tents",
+        problem.clone(),
+        &Context::sized(h, w),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["tents"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let is_tree = util::tests::to_bool_2d([
+            [0, 0, 0, 1, 0],
+            [0, 1, 0, 1, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 1, 0, 0],
+            [0, 1, 0, 0, 0],
+        ]);
+        let clue_vertical = vec![Some(2), Some(0), Some(1), Some(1), Some(1)];
+        let clue_horizontal = vec![Some(1), Some(1), Some(1), Some(0), Some(2)];
+        ((clue_vertical, clue_horizontal), is_tree)
+    }
+
+    #[test]
+    fn test_tents_problem() {
+        let ((clue_vertical, clue_horizontal), is_tree) = problem_for_tests();
+        let ans = solve_tents(
+            &is_tree,
+            &clue_vertical,
+            &clue_horizontal,
+            &mut SolveContext::new(),
+        );
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 0, 0, 0, 1],
+            [1, 0, 0, 0, 0],
+            [0, 0, 0, 1, 0],
+            [0, 0, 0, 0, 0],
+            [1, 0, 1, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        // no two tents touch
+        for y in 0..4 {
+            for x in 0..4 {
+                let n = (y..(y + 2))
+                    .flat_map(|y2| (x..(x + 2)).map(move |x2| (y2, x2)))
+                    .filter(|&(y2, x2)| ans[y2][x2] == Some(true))
+                    .count();
+                assert!(n <= 1);
+            }
+        }
+
+        // each tree has an adjacent tent, and there are as many tents as trees
+        let mut n_tents = 0;
+        for y in 0..5 {
+            for x in 0..5 {
+                if ans[y][x] == Some(true) {
+                    n_tents += 1;
+                }
+                if is_tree[y][x] {
+                    let has_tent = (y > 0 && ans[y - 1][x] == Some(true))
+                        || (y < 4 && ans[y + 1][x] == Some(true))
+                        || (x > 0 && ans[y][x - 1] == Some(true))
+                        || (x < 4 && ans[y][x + 1] == Some(true));
+                    assert!(has_tent);
+                }
+            }
+        }
+        assert_eq!(n_tents, 5);
+    }
+
+    #[test]
+    fn test_tents_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?tents/5/5/20111111022a048";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
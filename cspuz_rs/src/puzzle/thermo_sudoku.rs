@@ -0,0 +1,199 @@
+use super::{sudoku, util};
+use crate::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    Dict, HexInt, Optionalize, Sequencer, Size, Spaces, Tuple2,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// A thermometer, given as its bulb followed by the rest of its cells in order.
+pub type Thermometer = Vec<(usize, usize)>;
+
+/// Solves a Thermometer Sudoku problem. Digits strictly increase along each thermometer,
+/// starting from its bulb.
+pub fn solve_thermo_sudoku(
+    clues: &[Vec<Option<i32>>],
+    thermometers: &[Thermometer],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = util::infer_shape(clues);
+    if h != w {
+        return None;
+    }
+    let n = h;
+    let block_shape = sudoku::block_shape(n)?;
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((n, n), 1, n as i32);
+    solver.add_answer_key_int(num);
+
+    sudoku::add_constraints(&mut solver, num, clues, block_shape);
+
+    for thermometer in thermometers {
+        for i in 1..thermometer.len() {
+            solver.add_expr(num.at(thermometer[i - 1]).lt(num.at(thermometer[i])));
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(num))
+}
+
+type Problem = (Vec<Vec<Option<i32>>>, Vec<Thermometer>);
+
+/// Steps from a cell of a thermometer to the next one, encoded as `g` to `n` in this order.
+const THERMOMETER_STEPS: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+fn step_combinator() -> impl Combinator<(i32, i32)> {
+    Choice::new(
+        THERMOMETER_STEPS
+            .iter()
+            .enumerate()
+            .map(|(i, &step)| {
+                Box::new(Dict::new(step, vec![b'g' + i as u8])) as Box<dyn Combinator<_>>
+            })
+            .collect(),
+    )
+}
+
+/// Serializes thermometers after a `/`. Each thermometer is the index of its bulb (`y * width + x`)
+/// as a `HexInt`, followed by the steps to the rest of its cells.
+struct ThermometersCombinator;
+
+impl Combinator<Vec<Thermometer>> for ThermometersCombinator {
+    fn serialize(&self, ctx: &Context, input: &[Vec<Thermometer>]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let width = ctx.width?;
+
+        let mut ret = vec![b'/'];
+        for thermometer in &input[0] {
+            if thermometer.is_empty() {
+                return None;
+            }
+            let (y, x) = thermometer[0];
+            let (_, app) = HexInt.serialize(ctx, &[(y * width + x) as i32])?;
+            ret.extend(app);
+            for i in 1..thermometer.len() {
+                let (y1, x1) = thermometer[i - 1];
+                let (y2, x2) = thermometer[i];
+                let step = (y2 as i32 - y1 as i32, x2 as i32 - x1 as i32);
+                let (_, app) = step_combinator().serialize(ctx, &[step])?;
+                ret.extend(app);
+            }
+        }
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Vec<Thermometer>>)> {
+        let height = ctx.height?;
+        let width = ctx.width?;
+
+        let mut sequencer = Sequencer::new(input);
+        sequencer.deserialize(ctx, Dict::new((), "/"))?;
+
+        let mut thermometers = vec![];
+        while let Some(bulb) = sequencer.deserialize(ctx, HexInt) {
+            let bulb = bulb[0] as usize;
+            if bulb >= height * width {
+                return None;
+            }
+            let mut thermometer = vec![(bulb / width, bulb % width)];
+            while let Some(step) = sequencer.deserialize(ctx, step_combinator()) {
+                let (y, x) = *thermometer.last().unwrap();
+                let y = y as i32 + step[0].0;
+                let x = x as i32 + step[0].1;
+                if !(0 <= y && y < height as i32 && 0 <= x && x < width as i32) {
+                    return None;
+                }
+                thermometer.push((y as usize, x as usize));
+            }
+            thermometers.push(thermometer);
+        }
+
+        Some((sequencer.n_read(), vec![thermometers]))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+        ThermometersCombinator,
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = util::infer_shape(&problem.0);
+    problem_to_url_with_context(
+        combinator(),
+        "thermosudoku",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["thermosudoku", "thermo_sudoku"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let clues = vec![vec![None; 4]; 4];
+        let thermometers = vec![
+            vec![(2, 1), (1, 1), (1, 2), (2, 2)],
+            vec![(0, 3), (0, 2)],
+            vec![(1, 0), (0, 0), (0, 1)],
+        ];
+        (clues, thermometers)
+    }
+
+    #[test]
+    fn test_thermo_sudoku_problem() {
+        let (clues, thermometers) = problem_for_tests();
+        let ans = solve_thermo_sudoku(&clues, &thermometers, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected =
+            util::tests::to_option_2d([[3, 4, 2, 1], [1, 2, 3, 4], [2, 1, 4, 3], [4, 3, 1, 2]]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_thermo_sudoku_direction() {
+        let thermometers = vec![vec![(0, 0), (0, 1)]];
+
+        let mut clues = vec![vec![None; 4]; 4];
+        clues[0][0] = Some(2);
+        clues[0][1] = Some(1);
+        assert_eq!(
+            solve_thermo_sudoku(&clues, &thermometers, &mut SolveContext::new()),
+            None
+        );
+
+        clues[0][0] = Some(1);
+        clues[0][1] = Some(2);
+        assert!(solve_thermo_sudoku(&clues, &thermometers, &mut SolveContext::new()).is_some());
+    }
+
+    #[test]
+    fn test_thermo_sudoku_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?thermosudoku/4/4/v/9gjh3i4gj";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -4,10 +4,11 @@ use crate::serializer::{
     get_kudamono_url_info, kudamono_url_info_to_problem, problem_to_kudamono_url_grid, Combinator,
     Dict, KudamonoGrid,
 };
-use crate::solver::{all, count_true, Solver};
+use crate::solver::{all, count_true, SolveContext, Solver};
 
 pub fn solve_tricklayer(
     is_block: &[Vec<bool>],
+    ctx: &mut SolveContext,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(is_block);
 
@@ -91,7 +92,7 @@ pub fn solve_tricklayer(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(edges))
+    solver.irrefutable_facts(ctx).map(|f| f.get(edges))
 }
 
 type Problem = Vec<Vec<bool>>;
@@ -126,7 +127,7 @@ mod tests {
     fn test_tricklayer_problem() {
         let problem = problem_for_tests();
 
-        let ans = solve_tricklayer(&problem);
+        let ans = solve_tricklayer(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
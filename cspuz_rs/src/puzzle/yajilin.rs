@@ -5,10 +5,11 @@ use crate::serializer::{
     problem_to_url, url_to_problem, Choice, Combinator, Grid, MaybeSkip, NumberedArrowCombinator,
     Optionalize, Spaces,
 };
-use crate::solver::Solver;
+use crate::solver::{SolveContext, Solver};
 
 pub fn solve_yajilin(
     clues: &[Vec<Option<NumberedArrow>>],
+    ctx: &mut SolveContext,
 ) -> Option<(graph::BoolGridEdgesIrrefutableFacts, Vec<Vec<Option<bool>>>)> {
     let (h, w) = util::infer_shape(clues);
 
@@ -39,7 +40,7 @@ pub fn solve_yajilin(
     }
 
     solver
-        .irrefutable_facts()
+        .irrefutable_facts(ctx)
         .map(|f| (f.get(is_line), f.get(is_black)))
 }
 
@@ -97,7 +98,7 @@ mod tests {
             Some(problem.clone())
         );
 
-        let ans = solve_yajilin(&problem);
+        let ans = solve_yajilin(&problem, &mut SolveContext::new());
         assert!(ans.is_some());
         let (_, is_black) = ans.unwrap();
 
@@ -3,11 +3,12 @@ use crate::serializer::{
     problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
     RoomsWithValues, Size, Spaces,
 };
-use crate::solver::{count_true, Solver};
+use crate::solver::{count_true, SolveContext, Solver};
 
 pub fn solve_yajilin_regions(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
     clues: &[Option<i32>],
+    ctx: &mut SolveContext,
 ) -> Option<(graph::BoolGridEdgesIrrefutableFacts, Vec<Vec<Option<bool>>>)> {
     let (h, w) = borders.base_shape();
 
@@ -37,7 +38,7 @@ pub fn solve_yajilin_regions(
     }
 
     solver
-        .irrefutable_facts()
+        .irrefutable_facts(ctx)
         .map(|f| (f.get(is_line), f.get(is_black)))
 }
 
@@ -95,7 +96,7 @@ mod tests {
     #[test]
     fn test_yajilin_regions_problem() {
         let (borders, clues) = problem_for_tests();
-        let ans = solve_yajilin_regions(&borders, &clues);
+        let ans = solve_yajilin_regions(&borders, &clues, &mut SolveContext::new());
         assert!(ans.is_some());
         let ans = ans.unwrap();
 
@@ -0,0 +1,100 @@
+use super::util;
+use crate::graph;
+use crate::items::NumberedArrow;
+use crate::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Grid, NumberedArrowCombinator, Optionalize,
+    Spaces,
+};
+use crate::solver::{SolveContext, Solver};
+
+/// Solves a Yajisan-Kazusan problem. Clue cells may be shaded, in which case their clues are
+/// ignored.
+pub fn solve_yajisan_kazusan(
+    clues: &[Vec<Option<NumberedArrow>>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    solver.add_expr(!is_black.conv2d_and((1, 2)));
+    solver.add_expr(!is_black.conv2d_and((2, 1)));
+    graph::active_vertices_connected_2d(&mut solver, !is_black);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some((dir, n)) = clues[y][x] {
+                if let Some(cells) = is_black.pointing_cells((y, x), dir) {
+                    solver.add_expr((!is_black.at((y, x))).imp(cells.count_true().eq(n)));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<Option<NumberedArrow>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(NumberedArrowCombinator)),
+        Box::new(Spaces::new(None, 'a')),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "yajikazu", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["yajikazu", "yajisan_kazusan"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::Arrow;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 4]; 4];
+        ret[0][3] = Some((Arrow::Down, 1));
+        ret[1][0] = Some((Arrow::Right, 2));
+        ret[1][2] = Some((Arrow::Down, 1));
+        ret[1][3] = Some((Arrow::Up, 2));
+        ret[2][1] = Some((Arrow::Left, 1));
+        ret[2][2] = Some((Arrow::Down, 1));
+        ret
+    }
+
+    #[test]
+    fn test_yajisan_kazusan_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_yajisan_kazusan(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [0, 0, 0, 0],
+            [0, 1, 0, 1],
+            [1, 0, 0, 0],
+            [0, 0, 1, 0],
+        ]);
+        assert_eq!(ans, expected);
+
+        // the clue at (1, 3) cannot be satisfied as only one cell is above it, so it is shaded
+        assert_eq!(ans[1][3], Some(true));
+        // the unshaded clue at (1, 0) counts the shaded cells (1, 1) and (1, 3)
+        assert_eq!(ans[1][0], Some(false));
+        assert_eq!((1..4).filter(|&x| ans[1][x] == Some(true)).count(), 2);
+    }
+
+    #[test]
+    fn test_yajisan_kazusan_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?yajikazu/4/4/c2142a2112a3121e";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,106 @@
+use super::util;
+use crate::graph;
+use crate::serializer::{problem_to_url, url_to_problem, Combinator, Grid, Map, MultiDigit};
+use crate::solver::{SolveContext, Solver};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YinYangClue {
+    None,
+    White,
+    Black,
+}
+
+pub fn solve_yin_yang(
+    clues: &[Vec<YinYangClue>],
+    ctx: &mut SolveContext,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+    graph::active_vertices_connected_2d(&mut solver, !is_black);
+    solver.add_expr(!is_black.conv2d_and((2, 2)));
+    solver.add_expr(is_black.conv2d_or((2, 2)));
+
+    for y in 0..h {
+        for x in 0..w {
+            match clues[y][x] {
+                YinYangClue::None => (),
+                YinYangClue::White => solver.add_expr(!is_black.at((y, x))),
+                YinYangClue::Black => solver.add_expr(is_black.at((y, x))),
+            }
+        }
+    }
+
+    solver.irrefutable_facts(ctx).map(|f| f.get(is_black))
+}
+
+type Problem = Vec<Vec<YinYangClue>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Map::new(
+        MultiDigit::new(3, 3),
+        |x: YinYangClue| {
+            Some(match x {
+                YinYangClue::None => 0,
+                YinYangClue::White => 1,
+                YinYangClue::Black => 2,
+            })
+        },
+        |n: i32| match n {
+            0 => Some(YinYangClue::None),
+            1 => Some(YinYangClue::White),
+            2 => Some(YinYangClue::Black),
+            _ => None,
+        },
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "yinyang", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["yinyang"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![YinYangClue::None; 4]; 4];
+        ret[1][3] = YinYangClue::Black;
+        ret[2][0] = YinYangClue::Black;
+        ret[2][1] = YinYangClue::White;
+        ret[3][0] = YinYangClue::White;
+        ret[3][3] = YinYangClue::Black;
+        ret
+    }
+
+    #[test]
+    fn test_yin_yang_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_yin_yang(&problem, &mut SolveContext::new());
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = util::tests::to_option_bool_2d([
+            [1, 1, 1, 1],
+            [1, 0, 0, 1],
+            [1, 0, 1, 1],
+            [0, 0, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_yin_yang_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?yinyang/4/4/00899i";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -1135,6 +1135,46 @@ impl Combinator<NumberedArrow> for NumberedArrowCombinator {
     }
 }
 
+/// The clues outside the grid: the clues of the columns and the clues of the rows.
+pub type OutsideClues = (Vec<Option<i32>>, Vec<Option<i32>>);
+
+pub struct OutsideCluesCombinator;
+
+impl OutsideCluesCombinator {
+    fn seq(len: usize) -> impl Combinator<Vec<Option<i32>>> {
+        Seq::new(
+            Choice::new(vec![
+                Box::new(Optionalize::new(HexInt)),
+                Box::new(Spaces::new(None, 'g')),
+            ]),
+            len,
+        )
+    }
+}
+
+impl Combinator<OutsideClues> for OutsideCluesCombinator {
+    fn serialize(&self, ctx: &Context, input: &[OutsideClues]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let (clue_vertical, clue_horizontal) = &input[0];
+        let mut seq = clue_vertical.clone();
+        seq.extend(clue_horizontal.iter().copied());
+        let (_, ret) = Self::seq(seq.len()).serialize(ctx, &[seq])?;
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<OutsideClues>)> {
+        let h = ctx.height?;
+        let w = ctx.width?;
+        let (n_read, mut seq) = Self::seq(w + h).deserialize(ctx, input)?;
+        assert_eq!(seq.len(), 1);
+        let mut clue_vertical = seq.pop().unwrap();
+        let clue_horizontal = clue_vertical.split_off(w);
+        Some((n_read, vec![(clue_vertical, clue_horizontal)]))
+    }
+}
+
 pub struct Grid<S> {
     base_serializer: S,
 }
@@ -1474,6 +1514,65 @@ where
     problem_to_kudamono_url(combinator, puzzle_kind, problem, height, width)
 }
 
+pub struct Pzprv3Info<'a> {
+    pub height: usize,
+    pub width: usize,
+    pub puzzle_kind: &'a str,
+    /// The lines following the header, split into space-separated tokens.
+    pub body: Vec<Vec<&'a str>>,
+}
+
+/// Parses a problem in the pzprv3 format, the file format of pzpr.js.
+/// Lines may be separated either by newlines or by `/`.
+pub fn parse_pzprv3(serialized: &str) -> Option<Pzprv3Info<'_>> {
+    let separator = if serialized.contains('\n') { '\n' } else { '/' };
+    let mut lines = serialized.split(separator).map(|line| line.trim());
+
+    let version = lines.next()?;
+    if version != "pzprv3" && version != "pzprv3.1" {
+        return None;
+    }
+    let puzzle_kind = lines.next()?;
+    let height = lines.next()?.parse::<usize>().ok()?;
+    let width = lines.next()?.parse::<usize>().ok()?;
+    let body = lines
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    Some(Pzprv3Info {
+        height,
+        width,
+        puzzle_kind,
+        body,
+    })
+}
+
+/// Reads the numbers in cells, which are given in the first `height` lines of the body.
+/// `-` (an unknown number) is read as -1, and any other non-numeric token as `None`.
+pub fn pzprv3_cell_numbers(info: &Pzprv3Info) -> Option<Vec<Vec<Option<i32>>>> {
+    if info.body.len() < info.height {
+        return None;
+    }
+    let mut ret = vec![];
+    for row in &info.body[..info.height] {
+        if row.len() != info.width {
+            return None;
+        }
+        ret.push(
+            row.iter()
+                .map(|&token| {
+                    if token == "-" {
+                        Some(-1)
+                    } else {
+                        token.parse::<i32>().ok()
+                    }
+                })
+                .collect(),
+        );
+    }
+    Some(ret)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2020,4 +2119,28 @@ mod tests {
         assert_eq!(info.puzzle_kind, "tricklayer");
         assert_eq!(info.content, "x3x37x19x18x12");
     }
+    #[test]
+    fn test_pzprv3() {
+        let info = parse_pzprv3("pzprv3/nurikabe/2/3/. 2 # /- + . /");
+        assert!(info.is_some());
+        let info = info.unwrap();
+        assert_eq!(info.puzzle_kind, "nurikabe");
+        assert_eq!(info.height, 2);
+        assert_eq!(info.width, 3);
+        assert_eq!(
+            pzprv3_cell_numbers(&info),
+            Some(vec![vec![None, Some(2), None], vec![Some(-1), None, None]])
+        );
+
+        let info = parse_pzprv3("pzprv3.1\nslither\n1\n2\n3 . \n0 0 0 \n");
+        assert!(info.is_some());
+        let info = info.unwrap();
+        assert_eq!(info.puzzle_kind, "slither");
+        assert_eq!(pzprv3_cell_numbers(&info), Some(vec![vec![Some(3), None]]));
+
+        assert!(parse_pzprv3("https://puzz.link/p?nurikabe/2/3/").is_none());
+        assert!(parse_pzprv3("pzprv3/nurikabe/x/3/").is_none());
+        let info = parse_pzprv3("pzprv3/nurikabe/2/3/. 2 /").unwrap();
+        assert_eq!(pzprv3_cell_numbers(&info), None);
+    }
 }
@@ -1,4 +1,5 @@
 use std::ops::{Add, BitAnd, BitOr, BitXor, Bound, Not, RangeBounds, Sub};
+use std::time::{Duration, Instant};
 
 use crate::items::Arrow;
 pub use enigma_csp::csp::BoolExpr as CSPBoolExpr;
@@ -7,9 +8,10 @@ pub use enigma_csp::csp::IntExpr as CSPIntExpr;
 pub use enigma_csp::csp::IntVar as CSPIntVar;
 use enigma_csp::csp::{Assignment, Stmt};
 use enigma_csp::domain::Domain;
-use enigma_csp::integration::IntegratedSolver;
 use enigma_csp::integration::Model as IntegratedModel;
 pub use enigma_csp::integration::PerfStats;
+pub use enigma_csp::integration::Statistics;
+use enigma_csp::integration::{IntegratedSolver, IrrefutableFactsOutcome};
 
 #[derive(Clone)]
 pub struct Value<T>(T);
@@ -1065,6 +1067,85 @@ macro_rules! impl_deref_var {
 impl_deref_var!(CSPBoolVar);
 impl_deref_var!(CSPIntVar);
 
+/// What `Solver::irrefutable_facts` returns for the answer keys.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum FactsMode {
+    /// The values which are common to all the answers.
+    #[default]
+    Irrefutable,
+    /// The values in an arbitrary answer.
+    AnyAnswer,
+    /// No values. The problem is not solved at all.
+    Nothing,
+}
+
+/// The time budget for solving a problem, and what is found out by solving it.
+/// Puzzle solvers take a context and pass it to `Solver::irrefutable_facts`, so that the caller
+/// can limit and inspect all the solving done for a problem.
+#[derive(Default)]
+pub struct SolveContext {
+    deadline: Option<Instant>,
+    /// Whether the facts proven before the deadline are returned on timeout.
+    partial_on_timeout: bool,
+    timed_out: bool,
+    statistics: Statistics,
+    is_unique: Option<bool>,
+    facts_mode: FactsMode,
+}
+
+impl SolveContext {
+    /// Returns a context without time limit.
+    pub fn new() -> SolveContext {
+        SolveContext::default()
+    }
+
+    /// Returns a context in which `Solver::irrefutable_facts` gives up and returns `None` once
+    /// `timeout` has elapsed.
+    pub fn with_timeout(timeout: Duration) -> SolveContext {
+        SolveContext {
+            deadline: Some(Instant::now() + timeout),
+            ..SolveContext::new()
+        }
+    }
+
+    /// Same as `with_timeout`, but once `timeout` has elapsed, `Solver::irrefutable_facts`
+    /// returns the facts proven before the deadline instead of `None` if there are any.
+    pub fn with_timeout_partial(timeout: Duration) -> SolveContext {
+        SolveContext {
+            partial_on_timeout: true,
+            ..SolveContext::with_timeout(timeout)
+        }
+    }
+
+    /// Returns a context without time limit in which `Solver::irrefutable_facts` returns the
+    /// values specified by `facts_mode` instead of the irrefutable facts.
+    /// This is useful for telling which items of a puzzle's answer are shown from the result.
+    pub fn with_facts_mode(facts_mode: FactsMode) -> SolveContext {
+        SolveContext {
+            facts_mode,
+            ..SolveContext::new()
+        }
+    }
+
+    /// Returns whether any call to `Solver::irrefutable_facts` exceeded the deadline.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Returns the SAT solver statistics accumulated over all the calls to
+    /// `Solver::irrefutable_facts`.
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Returns whether the answer was unique in every call to `Solver::irrefutable_facts`, that
+    /// is, all the answer keys were decided. Calls which found no answer or timed out do not
+    /// affect the result, and `None` is returned if there was no other call.
+    pub fn is_unique(&self) -> Option<bool> {
+        self.is_unique
+    }
+}
+
 pub struct Solver<'a> {
     solver: IntegratedSolver<'a>,
     answer_key_bool: Vec<CSPBoolVar>,
@@ -1162,6 +1243,52 @@ impl<'a> Solver<'a> {
             .add_constraint(Stmt::ActiveVerticesConnected(vertices, graph.to_owned()));
     }
 
+    /// Adds the constraint that every vertex for which `exprs` is true is reachable from
+    /// `source` along the directed edges `(from, to)` in `graph`, passing only through such
+    /// vertices.
+    pub fn add_reachable_from<T>(&mut self, source: usize, exprs: T, graph: &[(usize, usize)])
+    where
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+    {
+        let vertices: Vec<CSPBoolExpr> =
+            exprs.into_iter().map(|x| x.as_expr_array().data).collect();
+        let n_vertices = vertices.len();
+        assert!(source < n_vertices);
+        for &(u, v) in graph {
+            assert!(u < n_vertices);
+            assert!(v < n_vertices);
+        }
+        self.solver
+            .add_constraint(Stmt::DirectedReachable(source, vertices, graph.to_owned()));
+    }
+
+    /// Adds the constraint that the edges `(u, v)` in `graph` for which `exprs` is true form
+    /// exactly one simple cycle.
+    pub fn add_single_cycle<T>(&mut self, exprs: T, graph: &[(usize, usize)])
+    where
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+    {
+        let edges: Vec<CSPBoolExpr> = exprs.into_iter().map(|x| x.as_expr_array().data).collect();
+        assert_eq!(edges.len(), graph.len());
+        self.solver
+            .add_constraint(Stmt::SingleCycle(edges, graph.to_owned()));
+    }
+
+    /// Adds the constraint that the edges `(u, v)` in `graph` for which `exprs` is true contain
+    /// no cycle.
+    pub fn add_acyclic<T>(&mut self, exprs: T, graph: &[(usize, usize)])
+    where
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+    {
+        let edges: Vec<CSPBoolExpr> = exprs.into_iter().map(|x| x.as_expr_array().data).collect();
+        assert_eq!(edges.len(), graph.len());
+        self.solver
+            .add_constraint(Stmt::Acyclic(edges, graph.to_owned()));
+    }
+
     pub fn add_graph_division<T>(
         &mut self,
         sizes: &[Option<Value<Array0DImpl<CSPIntExpr>>>],
@@ -1187,6 +1314,10 @@ impl<'a> Solver<'a> {
         self.solver.set_perf_stats(perf_stats);
     }
 
+    pub fn statistics(&self) -> Statistics {
+        self.solver.statistics()
+    }
+
     pub fn add_answer_key_bool<T>(&mut self, keys: T)
     where
         T: IntoIterator,
@@ -1213,10 +1344,53 @@ impl<'a> Solver<'a> {
         self.solver.solve().map(|model| Model { model })
     }
 
-    pub fn irrefutable_facts(self) -> Option<OwnedPartialModel> {
-        self.solver
-            .decide_irrefutable_facts(&self.answer_key_bool, &self.answer_key_int)
-            .map(|assignment| OwnedPartialModel { assignment })
+    /// Returns `None` also if solving takes longer than the budget of `ctx` (for a context
+    /// given by `SolveContext::with_timeout_partial`, the facts proven by then are returned
+    /// instead, unless nothing has been proven).
+    pub fn irrefutable_facts(mut self, ctx: &mut SolveContext) -> Option<OwnedPartialModel> {
+        match ctx.facts_mode {
+            FactsMode::Irrefutable => (),
+            FactsMode::AnyAnswer => return self.answer_iter().next(),
+            FactsMode::Nothing => {
+                return Some(OwnedPartialModel {
+                    assignment: Assignment::new(),
+                })
+            }
+        }
+        let timeout = ctx
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        let outcome = self.solver.decide_irrefutable_facts_with_timeout(
+            &self.answer_key_bool,
+            &self.answer_key_int,
+            timeout,
+        );
+        ctx.statistics.accumulate(&self.solver.total_statistics());
+        match outcome {
+            IrrefutableFactsOutcome::Facts(assignment) => {
+                let all_decided = self
+                    .answer_key_bool
+                    .iter()
+                    .all(|&v| assignment.get_bool(v).is_some())
+                    && self
+                        .answer_key_int
+                        .iter()
+                        .all(|&v| assignment.get_int(v).is_some());
+                ctx.is_unique = Some(ctx.is_unique.unwrap_or(true) && all_decided);
+                Some(OwnedPartialModel { assignment })
+            }
+            IrrefutableFactsOutcome::Unsat | IrrefutableFactsOutcome::Cancelled => None,
+            IrrefutableFactsOutcome::Timeout(assignment) => {
+                ctx.timed_out = true;
+                let is_empty = assignment.bool_iter().next().is_none()
+                    && assignment.int_iter().next().is_none();
+                if ctx.partial_on_timeout && !is_empty {
+                    Some(OwnedPartialModel { assignment })
+                } else {
+                    None
+                }
+            }
+        }
     }
 
     pub fn answer_iter(self) -> impl Iterator<Item = OwnedPartialModel> + 'a {
@@ -1224,6 +1398,17 @@ impl<'a> Solver<'a> {
             .answer_iter(&self.answer_key_bool, &self.answer_key_int)
             .map(|assignment| OwnedPartialModel { assignment })
     }
+
+    /// Returns at most `num_max_answers` answers, which are distinct when projected to the
+    /// answer keys. Unlike `answer_iter`, the solver is only borrowed, so it can be used
+    /// afterwards (e.g. for `irrefutable_facts`).
+    pub fn enumerate_answers(&mut self, num_max_answers: usize) -> Vec<OwnedPartialModel> {
+        self.solver
+            .enumerate(&self.answer_key_bool, &self.answer_key_int)
+            .take(num_max_answers)
+            .map(|assignment| OwnedPartialModel { assignment })
+            .collect()
+    }
 }
 
 pub trait MapForArray<A, B> {
@@ -1797,6 +1982,133 @@ mod tests {
         assert_eq!(n_ans, 24);
     }
 
+    #[test]
+    fn test_solve_context_timeout() {
+        let solve = |ctx: &mut SolveContext| {
+            let mut solver = Solver::new();
+            let array = &solver.bool_var_1d(5);
+            solver.add_answer_key_bool(array);
+            solver.add_expr(array.at(0) ^ array.at(1));
+            solver.add_expr(!array.at(1));
+            solver.irrefutable_facts(ctx).map(|f| f.get(array))
+        };
+        let expected = Some(vec![Some(true), Some(false), None, None, None]);
+
+        let mut ctx = SolveContext::with_timeout(Duration::from_secs(100));
+        assert_eq!(solve(&mut ctx), expected);
+        assert!(!ctx.timed_out());
+        // the deadline has passed before solving starts
+        let mut ctx = SolveContext::with_timeout(Duration::from_secs(0));
+        assert_eq!(solve(&mut ctx), None);
+        assert!(ctx.timed_out());
+        assert_eq!(solve(&mut SolveContext::new()), expected);
+    }
+
+    #[test]
+    fn test_solve_context_timeout_partial() {
+        let solve = |ctx: &mut SolveContext| {
+            let mut solver = Solver::new();
+            let array = &solver.bool_var_1d(5);
+            solver.add_answer_key_bool(array);
+            solver.add_expr(array.at(0) ^ array.at(1));
+            solver.add_expr(!array.at(1));
+            solver.irrefutable_facts(ctx).map(|f| f.get(array))
+        };
+        let expected = Some(vec![Some(true), Some(false), None, None, None]);
+
+        let mut ctx = SolveContext::with_timeout_partial(Duration::from_secs(100));
+        assert_eq!(solve(&mut ctx), expected);
+        assert!(!ctx.timed_out());
+        // the deadline has passed before solving starts, but the facts are fixed without the SAT
+        // solver
+        let mut ctx = SolveContext::with_timeout_partial(Duration::from_secs(0));
+        assert_eq!(solve(&mut ctx), expected);
+        assert!(ctx.timed_out());
+
+        // nothing is proven before the deadline
+        let mut ctx = SolveContext::with_timeout_partial(Duration::from_secs(0));
+        let mut solver = Solver::new();
+        let array = &solver.bool_var_1d(5);
+        solver.add_answer_key_bool(array);
+        solver.add_expr(array.at(0) ^ array.at(1));
+        assert_eq!(
+            solver.irrefutable_facts(&mut ctx).map(|f| f.get(array)),
+            None
+        );
+        assert!(ctx.timed_out());
+    }
+
+    #[test]
+    fn test_solve_context_facts_mode() {
+        let solve = |ctx: &mut SolveContext| {
+            let mut solver = Solver::new();
+            let array = &solver.bool_var_1d(3);
+            solver.add_answer_key_bool(array);
+            solver.add_expr(array.at(0) ^ array.at(1));
+            solver.add_expr(array.at(2));
+            solver.irrefutable_facts(ctx).map(|f| f.get(array))
+        };
+
+        let facts = solve(&mut SolveContext::with_facts_mode(FactsMode::Irrefutable));
+        assert_eq!(facts, Some(vec![None, None, Some(true)]));
+
+        let mut ctx = SolveContext::with_facts_mode(FactsMode::AnyAnswer);
+        let answer = solve(&mut ctx).unwrap();
+        assert_eq!(answer[2], Some(true));
+        assert_eq!(answer[0].map(|b| !b), answer[1]);
+        assert!(answer[0].is_some());
+        assert_eq!(ctx.is_unique(), None);
+
+        let facts = solve(&mut SolveContext::with_facts_mode(FactsMode::Nothing));
+        assert_eq!(facts, Some(vec![None, None, None]));
+    }
+
+    #[test]
+    fn test_solve_context_statistics() {
+        let mut ctx = SolveContext::new();
+        let mut solver = Solver::new();
+        let array = &solver.bool_var_1d(5);
+        solver.add_answer_key_bool(array);
+        solver.add_expr(array.at(0) ^ array.at(1));
+        let n_facts = solver
+            .irrefutable_facts(&mut ctx)
+            .map(|f| f.get(array).iter().flatten().count());
+        assert_eq!(n_facts, Some(0));
+        assert!(ctx.statistics().solve_time > Duration::from_secs(0));
+        assert!(ctx.statistics().clauses.is_some());
+    }
+
+    #[test]
+    fn test_solve_context_is_unique() {
+        let solve = |ctx: &mut SolveContext, unique: bool| {
+            let mut solver = Solver::new();
+            let array = &solver.bool_var_1d(3);
+            solver.add_answer_key_bool(array);
+            solver.add_expr(array.at(0) ^ array.at(1));
+            solver.add_expr(array.at(2));
+            if unique {
+                solver.add_expr(array.at(0));
+            }
+            solver.irrefutable_facts(ctx).map(|f| f.get(array))
+        };
+
+        let mut ctx = SolveContext::new();
+        assert_eq!(ctx.is_unique(), None);
+        let res = solve(&mut ctx, true);
+        assert_eq!(res, Some(vec![Some(true), Some(false), Some(true)]));
+        assert_eq!(ctx.is_unique(), Some(true));
+
+        let mut ctx = SolveContext::new();
+        let res = solve(&mut ctx, false);
+        assert_eq!(res, Some(vec![None, None, Some(true)]));
+        assert_eq!(ctx.is_unique(), Some(false));
+
+        let mut ctx = SolveContext::new();
+        solve(&mut ctx, true);
+        solve(&mut ctx, false);
+        assert_eq!(ctx.is_unique(), Some(false));
+    }
+
     #[test]
     fn test_expr_macro() {
         let mut solver = Solver::new();
@@ -140,6 +140,7 @@ impl ItemKind {
     }
 }
 
+#[derive(PartialEq, Eq)]
 pub struct Item {
     pub y: usize,
     pub x: usize,
@@ -181,6 +182,9 @@ pub struct Board {
     height: usize,
     width: usize,
     data: Vec<Item>,
+    is_unique: Option<bool>,
+    /// Whether the value at each position (in the coordinates of `Item`) is uniquely determined.
+    decided: Vec<(usize, usize, bool)>,
 }
 
 impl Board {
@@ -190,6 +194,51 @@ impl Board {
             height,
             width,
             data: vec![],
+            is_unique: None,
+            decided: vec![],
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Sets whether the answer shown in the board is unique, which is output as `is_unique`.
+    pub fn set_is_unique(&mut self, is_unique: bool) {
+        self.is_unique = Some(is_unique);
+    }
+
+    /// Marks whether the value at (`y`, `x`) (in the coordinates of `Item`) is uniquely
+    /// determined, which is output in `decided`.
+    pub fn set_decided(&mut self, y: usize, x: usize, decided: bool) {
+        self.decided.push((y, x, decided));
+    }
+
+    /// Marks the positions of the answer as decided or not, given `answer`, the board showing an
+    /// arbitrary answer of the same problem, and `clues`, the board showing the problem only.
+    /// The answer is at the positions where `answer` has an item not in `clues`, and each of
+    /// them is decided if this board also has an item not in `clues` there.
+    pub fn set_decided_by_comparison(&mut self, answer: &Board, clues: &Board) {
+        let is_answer_item = |item: &&Item| !clues.data.contains(item);
+        let mut positions = answer
+            .data
+            .iter()
+            .filter(is_answer_item)
+            .map(|item| (item.y, item.x))
+            .collect::<Vec<_>>();
+        positions.sort();
+        positions.dedup();
+        for (y, x) in positions {
+            let decided = self
+                .data
+                .iter()
+                .filter(is_answer_item)
+                .any(|item| item.y == y && item.x == x);
+            self.set_decided(y, x, decided);
         }
     }
 
@@ -287,9 +336,53 @@ impl Board {
             .map(|item| item.to_json())
             .collect::<Vec<_>>()
             .join(",");
+        let is_unique = match self.is_unique {
+            Some(is_unique) => format!(",\"is_unique\":{}", is_unique),
+            None => String::new(),
+        };
+        let decided = if self.decided.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ",\"decided\":[{}]",
+                self.decided
+                    .iter()
+                    .map(|&(y, x, decided)| format!(
+                        "{{\"y\":{},\"x\":{},\"decided\":{}}}",
+                        y, x, decided
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        };
         format!(
-            "{{\"kind\":\"{}\",\"height\":{},\"width\":{},\"defaultStyle\":\"{}\",\"data\":[{}]}}",
-            kind, height, width, default_style, data
+            "{{\"kind\":\"{}\",\"height\":{},\"width\":{},\"defaultStyle\":\"{}\",\"data\":[{}]{}{}}}",
+            kind, height, width, default_style, data, is_unique, decided
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagonal_and_arrow_items_json() {
+        let mut board = Board::new(BoardKind::Grid, 1, 2);
+        board.push(Item::cell(0, 0, "green", ItemKind::AboloUpperLeft));
+        board.push(Item::cell(0, 1, "black", ItemKind::ArrowRight));
+        let json = board.to_json();
+        assert!(json.contains("{\"y\":1,\"x\":1,\"color\":\"green\",\"item\":\"aboloUpperLeft\"}"));
+        assert!(json.contains("{\"y\":1,\"x\":3,\"color\":\"black\",\"item\":\"arrowRight\"}"));
+    }
+
+    #[test]
+    fn test_colored_fill_json() {
+        let mut board = Board::new(BoardKind::Grid, 1, 2);
+        board.push(Item::cell(0, 0, "#e0e0ff", ItemKind::Fill));
+        board.push(Item::cell(0, 1, "black", ItemKind::Fill));
+        let json = board.to_json();
+        assert!(json.contains("{\"y\":1,\"x\":1,\"color\":\"#e0e0ff\",\"item\":\"fill\"}"));
+        assert!(json.contains("{\"y\":1,\"x\":3,\"color\":\"black\",\"item\":\"fill\"}"));
+    }
+}
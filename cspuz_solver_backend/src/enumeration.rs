@@ -0,0 +1,268 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use crate::puzzle;
+use cspuz_rs::solver::{BoolVar, IntVar, OwnedPartialModel, SolveContext, Solver};
+
+/// A variable of the solver, with the function giving the item shown for each of its values.
+pub enum AnswerVar {
+    Bool(BoolVar, fn(bool) -> ItemKind),
+    Int(IntVar, fn(i32) -> ItemKind),
+}
+
+impl AnswerVar {
+    fn add_answer_key(&self, solver: &mut Solver) {
+        match self {
+            AnswerVar::Bool(var, _) => solver.add_answer_key_bool([var]),
+            AnswerVar::Int(var, _) => solver.add_answer_key_int([var]),
+        }
+    }
+
+    /// Returns the item for the value of the variable in `model`, if it is determined.
+    fn item(&self, model: &OwnedPartialModel) -> Option<ItemKind> {
+        match self {
+            AnswerVar::Bool(var, item) => model.get(var).map(item),
+            AnswerVar::Int(var, item) => model.get(var).map(item),
+        }
+    }
+}
+
+/// An item of the board which is determined by a variable of the solver.
+pub struct AnswerKey {
+    pub y: usize,
+    pub x: usize,
+    pub var: AnswerVar,
+}
+
+impl AnswerKey {
+    pub fn cell(y: usize, x: usize, var: BoolVar, item: fn(bool) -> ItemKind) -> AnswerKey {
+        AnswerKey {
+            y: y * 2 + 1,
+            x: x * 2 + 1,
+            var: AnswerVar::Bool(var, item),
+        }
+    }
+
+    pub fn int_cell(y: usize, x: usize, var: IntVar, item: fn(i32) -> ItemKind) -> AnswerKey {
+        AnswerKey {
+            y: y * 2 + 1,
+            x: x * 2 + 1,
+            var: AnswerVar::Int(var, item),
+        }
+    }
+}
+
+pub trait EnumerablePuzzle {
+    /// Returns the solver with the constraints of the problem given by `url`, the board showing
+    /// the problem, and the answer keys of the problem.
+    fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError>;
+}
+
+pub fn enumerate_answers<P: EnumerablePuzzle>(
+    url: &str,
+    num_max_answers: usize,
+) -> Result<(Board, Vec<Board>), SolveError> {
+    let (mut solver, mut board_common, keys) = P::build(url)?;
+    for key in &keys {
+        key.var.add_answer_key(&mut solver);
+    }
+
+    let answers = solver.enumerate_answers(num_max_answers);
+    let common = solver
+        .irrefutable_facts(&mut SolveContext::new())
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let height = board_common.height();
+    let width = board_common.width();
+
+    let mut undecided = vec![];
+    for key in &keys {
+        match key.var.item(&common) {
+            Some(kind) => board_common.push(Item {
+                y: key.y,
+                x: key.x,
+                color: "#339933",
+                kind,
+            }),
+            None => undecided.push(key),
+        }
+    }
+
+    let mut board_answers = vec![];
+    for ans in answers {
+        let mut board_answer = Board::new(BoardKind::Empty, height, width);
+        for key in &undecided {
+            board_answer.push(Item {
+                y: key.y,
+                x: key.x,
+                color: "#cccccc",
+                kind: key.var.item(&ans).unwrap(),
+            });
+        }
+        board_answers.push(board_answer);
+    }
+
+    Ok((board_common, board_answers))
+}
+
+/// Returns the board showing the values of the answer keys which are common to all the answers,
+/// where each answer key is also marked whether it is decided (see `Board::set_decided`).
+pub fn solve_with_decided<P: EnumerablePuzzle>(
+    url: &str,
+    ctx: &mut SolveContext,
+) -> Result<Board, SolveError> {
+    let (mut solver, mut board, keys) = P::build(url)?;
+    for key in &keys {
+        key.var.add_answer_key(&mut solver);
+    }
+
+    let facts = solver
+        .irrefutable_facts(ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+    for key in &keys {
+        let item = key.var.item(&facts);
+        let is_decided = item.is_some();
+        if let Some(kind) = item {
+            board.push(Item {
+                y: key.y,
+                x: key.x,
+                color: "green",
+                kind,
+            });
+        }
+        board.set_decided(key.y, key.x, is_decided);
+    }
+
+    Ok(board)
+}
+
+pub type Enumerator = fn(&str, usize) -> Result<(Board, Vec<Board>), SolveError>;
+pub type DecidedSolver = fn(&str, &mut SolveContext) -> Result<Board, SolveError>;
+
+/// The puzzles implementing `EnumerablePuzzle`. These are the only puzzles supported by
+/// `enumerate_answers_problem`.
+const ENUMERABLE_PUZZLES: &[(&str, Enumerator, DecidedSolver)] = &[
+    (
+        "heyawake",
+        enumerate_answers::<puzzle::heyawake::Heyawake>,
+        solve_with_decided::<puzzle::heyawake::Heyawake>,
+    ),
+    (
+        "curvedata",
+        enumerate_answers::<puzzle::curvedata::Curvedata>,
+        solve_with_decided::<puzzle::curvedata::Curvedata>,
+    ),
+    (
+        "nurikabe",
+        enumerate_answers::<puzzle::nurikabe::Nurikabe>,
+        solve_with_decided::<puzzle::nurikabe::Nurikabe>,
+    ),
+    (
+        "sudoku",
+        enumerate_answers::<puzzle::sudoku::Sudoku>,
+        solve_with_decided::<puzzle::sudoku::Sudoku>,
+    ),
+];
+
+pub fn get_enumerator(puzzle_kind: &str) -> Option<Enumerator> {
+    ENUMERABLE_PUZZLES
+        .iter()
+        .find(|&&(kind, _, _)| kind == puzzle_kind)
+        .map(|&(_, enumerator, _)| enumerator)
+}
+
+pub fn get_decided_solver(puzzle_kind: &str) -> Option<DecidedSolver> {
+    ENUMERABLE_PUZZLES
+        .iter()
+        .find(|&&(kind, _, _)| kind == puzzle_kind)
+        .map(|&(_, _, solver)| solver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A row of 3 cells, exactly one of which is black. The leftmost cell is black if `url` is
+    /// "left", and is white if `url` is "not-left".
+    struct OneBlackCell;
+
+    impl EnumerablePuzzle for OneBlackCell {
+        fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError> {
+            let mut solver = Solver::new();
+            let is_black = solver.bool_var_1d(3);
+            solver.add_expr(is_black.count_true().eq(1));
+            if url == "left" {
+                solver.add_expr(is_black.at(0));
+            } else if url == "not-left" {
+                solver.add_expr(!is_black.at(0));
+            }
+
+            let board = Board::new(BoardKind::Grid, 1, 3);
+            let keys = (0..3)
+                .map(|x| {
+                    AnswerKey::cell(0, x, is_black.at(x), |b| {
+                        if b {
+                            ItemKind::Block
+                        } else {
+                            ItemKind::Dot
+                        }
+                    })
+                })
+                .collect();
+            Ok((solver, board, keys))
+        }
+    }
+
+    #[test]
+    fn test_enumerate_answers_trait() {
+        let (common, answers) = enumerate_answers::<OneBlackCell>("", 10).unwrap();
+        assert!(!common.to_json().contains("\"item\""));
+        let mut answers = answers.iter().map(|a| a.to_json()).collect::<Vec<_>>();
+        answers.sort();
+        answers.dedup();
+        assert_eq!(answers.len(), 3);
+        for answer in &answers {
+            assert_eq!(answer.matches("\"item\":\"block\"").count(), 1);
+            assert_eq!(answer.matches("\"item\":\"dot\"").count(), 2);
+        }
+
+        let (_, answers) = enumerate_answers::<OneBlackCell>("", 2).unwrap();
+        assert_eq!(answers.len(), 2);
+
+        let (common, answers) = enumerate_answers::<OneBlackCell>("left", 10).unwrap();
+        assert_eq!(common.to_json().matches("\"color\":\"#339933\"").count(), 3);
+        assert_eq!(answers.len(), 1);
+        assert!(!answers[0].to_json().contains("\"item\""));
+    }
+
+    #[test]
+    fn test_solve_with_decided() {
+        // only the leftmost cell is decided
+        let board =
+            solve_with_decided::<OneBlackCell>("not-left", &mut SolveContext::new()).unwrap();
+        let json = board.to_json();
+        assert!(json.ends_with(
+            ",\"decided\":[{\"y\":1,\"x\":1,\"decided\":true},\
+             {\"y\":1,\"x\":3,\"decided\":false},\
+             {\"y\":1,\"x\":5,\"decided\":false}]}"
+        ));
+        assert_eq!(json.matches("\"item\":\"dot\"").count(), 1);
+        assert!(!json.contains("\"item\":\"block\""));
+
+        let board = solve_with_decided::<OneBlackCell>("left", &mut SolveContext::new()).unwrap();
+        assert_eq!(board.to_json().matches("\"decided\":true").count(), 3);
+
+        let board = solve_with_decided::<OneBlackCell>("", &mut SolveContext::new()).unwrap();
+        assert_eq!(board.to_json().matches("\"decided\":false").count(), 3);
+    }
+
+    #[test]
+    fn test_get_enumerator() {
+        assert!(get_enumerator("heyawake").is_some());
+        assert!(get_enumerator("curvedata").is_some());
+        assert!(get_enumerator("nurikabe").is_some());
+        assert!(get_enumerator("sudoku").is_some());
+        assert!(get_enumerator("unknown").is_none());
+        assert!(get_decided_solver("heyawake").is_some());
+        assert!(get_decided_solver("unknown").is_none());
+    }
+}
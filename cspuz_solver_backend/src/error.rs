@@ -0,0 +1,35 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The kind of the puzzle is not recognized.
+    UnknownPuzzle,
+    /// The kind of the puzzle is recognized, but is not supported by the requested operation
+    /// (e.g. enumeration) or format (e.g. pzprv3).
+    UnsupportedPuzzle,
+    /// The problem could not be decoded.
+    ParseFailed { detail: &'static str },
+    /// The problem has no answer.
+    Unsatisfiable,
+    /// Solving did not finish within the time limit.
+    Timeout,
+}
+
+impl SolveError {
+    /// The error of the solvers of individual puzzles for URLs which do not describe a problem.
+    pub const INVALID_URL: SolveError = SolveError::ParseFailed {
+        detail: "invalid url",
+    };
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle => write!(f, "unknown puzzle type"),
+            SolveError::UnsupportedPuzzle => write!(f, "unsupported puzzle type"),
+            SolveError::ParseFailed { detail } => write!(f, "{}", detail),
+            SolveError::Unsatisfiable => write!(f, "no answer"),
+            SolveError::Timeout => write!(f, "timeout"),
+        }
+    }
+}
@@ -1,180 +1,432 @@
 extern crate cspuz_rs;
 
 pub mod board;
+mod enumeration;
+mod error;
 mod puzzle;
 
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::time::Duration;
+
 use board::Board;
-use cspuz_rs::serializer::{get_kudamono_url_info, url_to_puzzle_kind};
-
-static mut SHARED_ARRAY: Vec<u8> = vec![];
-
-fn solve_puzz_link(puzzle_kind: String, url: &str) -> Result<Board, &'static str> {
-    if puzzle_kind == "nurikabe" {
-        puzzle::nurikabe::solve_nurikabe(url)
-    } else if puzzle_kind == "yajilin" || puzzle_kind == "yajirin" {
-        puzzle::yajilin::solve_yajilin(url)
-    } else if puzzle_kind == "heyawake" {
-        puzzle::heyawake::solve_heyawake(url, false)
-    } else if puzzle_kind == "ayeheya" {
-        puzzle::heyawake::solve_heyawake(url, true)
-    } else if puzzle_kind == "slither" || puzzle_kind == "slitherlink" {
-        puzzle::slitherlink::solve_slitherlink(url)
-    } else if puzzle_kind == "slalom" {
-        puzzle::slalom::solve_slalom(url)
-    } else if puzzle_kind == "nurimisaki" {
-        puzzle::nurimisaki::solve_nurimisaki(url)
-    } else if puzzle_kind == "compass" {
-        puzzle::compass::solve_compass(url)
-    } else if puzzle_kind == "akari" {
-        puzzle::akari::solve_akari(url)
-    } else if puzzle_kind == "lits" {
-        puzzle::lits::solve_lits(url)
-    } else if puzzle_kind == "masyu" || puzzle_kind == "mashu" {
-        puzzle::masyu::solve_masyu(url)
-    } else if puzzle_kind == "shakashaka" {
-        puzzle::shakashaka::solve_shakashaka(url)
-    } else if puzzle_kind == "araf" {
-        puzzle::araf::solve_araf(url)
-    } else if puzzle_kind == "aqre" {
-        puzzle::aqre::solve_aqre(url)
-    } else if puzzle_kind == "tapa" {
-        puzzle::tapa::solve_tapa(url)
-    } else if puzzle_kind == "simpleloop" {
-        puzzle::simpleloop::solve_simpleloop(url)
-    } else if puzzle_kind == "yajilin-regions" {
-        puzzle::yajilin_regions::solve_yajilin_regions(url)
-    } else if puzzle_kind == "kropki" {
-        puzzle::kropki::solve_kropki(url)
-    } else if puzzle_kind == "kurotto" {
-        puzzle::kurotto::solve_kurotto(url)
-    } else if puzzle_kind == "castle" {
-        puzzle::castle_wall::solve_castle_wall(url)
-    } else if puzzle_kind == "shimaguni" {
-        puzzle::shimaguni::solve_shimaguni(url)
-    } else if puzzle_kind == "norinori" {
-        puzzle::norinori::solve_norinori(url)
-    } else if puzzle_kind == "coral" {
-        puzzle::coral::solve_coral(url)
-    } else if puzzle_kind == "cave" {
-        puzzle::cave::solve_cave(url)
-    } else if puzzle_kind == "curvedata" {
-        puzzle::curvedata::solve_curvedata(url)
-    } else if puzzle_kind == "shikaku" {
-        puzzle::shikaku::solve_shikaku(url)
-    } else if puzzle_kind == "sudoku" {
-        puzzle::sudoku::solve_sudoku(url)
-    } else if puzzle_kind == "sashigane" {
-        puzzle::sashigane::solve_sashigane(url)
-    } else if puzzle_kind == "lohkous" {
-        puzzle::lohkous::solve_lohkous(url)
-    } else if puzzle_kind == "hashi" {
-        puzzle::hashi::solve_hashi(url)
-    } else if puzzle_kind == "herugolf" {
-        puzzle::herugolf::solve_herugolf(url)
-    } else if puzzle_kind == "slashpack" {
-        puzzle::slashpack::solve_slashpack(url)
-    } else if puzzle_kind == "moonsun" {
-        puzzle::moonsun::solve_moonsun(url)
-    } else if puzzle_kind == "fillomino" {
-        puzzle::fillomino::solve_fillomino(url)
-    } else if puzzle_kind == "cbanana" {
-        puzzle::chocobanana::solve_chocobanana(url)
-    } else if puzzle_kind == "fivecells" {
-        puzzle::fivecells::solve_fivecells(url)
-    } else if puzzle_kind == "cocktail" {
-        puzzle::cocktail::solve_cocktail(url)
-    } else if puzzle_kind == "stostone" {
-        puzzle::stostone::solve_stostone(url)
-    } else if puzzle_kind == "pencils" {
-        puzzle::pencils::solve_pencils(url)
-    } else if puzzle_kind == "barns" {
-        puzzle::barns::solve_barns(url)
-    } else if puzzle_kind == "reflect" {
-        puzzle::reflect::solve_reflect_link(url)
-    } else if puzzle_kind == "ringring" {
-        puzzle::ringring::solve_ringring(url)
-    } else if puzzle_kind == "loopsp" {
-        puzzle::loop_special::solve_loop_speical(url)
-    } else if puzzle_kind == "nagenawa" {
-        puzzle::nagenawa::solve_nagenawa(url)
-    } else if puzzle_kind == "icewalk" {
-        puzzle::icewalk::solve_icewalk(url)
-    } else if puzzle_kind == "kouchoku" {
-        puzzle::kouchoku::solve_kouchoku(url)
-    } else if puzzle_kind == "creek" {
-        puzzle::creek::solve_creek(url)
-    } else if puzzle_kind == "squarejam" {
-        puzzle::square_jam::solve_square_jam(url)
-    } else {
-        Err("unknown puzzle type")
+use cspuz_rs::serializer::{
+    get_kudamono_url_info, map_2d, parse_pzprv3, pzprv3_cell_numbers, url_to_puzzle_kind,
+    Pzprv3Info,
+};
+use cspuz_rs::solver::{FactsMode, SolveContext, Statistics};
+use error::SolveError;
+
+thread_local! {
+    static SHARED_ARRAY: RefCell<Vec<u8>> = const { RefCell::new(vec![]) };
+}
+
+type SolveFn = fn(&str, &mut SolveContext) -> Result<Board, SolveError>;
+
+/// Solvers for puzz.link URLs, indexed by the puzzle kind in the URL (including aliases).
+const PUZZLE_SOLVERS: &[(&str, SolveFn)] = &[
+    ("nurikabe", puzzle::nurikabe::solve_nurikabe),
+    ("yajilin", puzzle::yajilin::solve_yajilin),
+    ("yajirin", puzzle::yajilin::solve_yajilin),
+    ("heyawake", |url, ctx| {
+        puzzle::heyawake::solve_heyawake(url, false, ctx)
+    }),
+    ("ayeheya", |url, ctx| {
+        puzzle::heyawake::solve_heyawake(url, true, ctx)
+    }),
+    ("slither", puzzle::slitherlink::solve_slitherlink),
+    ("slitherlink", puzzle::slitherlink::solve_slitherlink),
+    ("slalom", puzzle::slalom::solve_slalom),
+    ("numlin", puzzle::numberlink::solve_numberlink),
+    ("numberlink", puzzle::numberlink::solve_numberlink),
+    ("nurimisaki", puzzle::nurimisaki::solve_nurimisaki),
+    ("compass", puzzle::compass::solve_compass),
+    ("akari", puzzle::akari::solve_akari),
+    ("lits", puzzle::lits::solve_lits),
+    ("masyu", puzzle::masyu::solve_masyu),
+    ("mashu", puzzle::masyu::solve_masyu),
+    ("shakashaka", puzzle::shakashaka::solve_shakashaka),
+    ("araf", puzzle::araf::solve_araf),
+    ("aqre", puzzle::aqre::solve_aqre),
+    ("tapa", puzzle::tapa::solve_tapa),
+    ("simpleloop", puzzle::simpleloop::solve_simpleloop),
+    (
+        "yajilin-regions",
+        puzzle::yajilin_regions::solve_yajilin_regions,
+    ),
+    ("kropki", puzzle::kropki::solve_kropki),
+    ("kurotto", puzzle::kurotto::solve_kurotto),
+    ("castle", puzzle::castle_wall::solve_castle_wall),
+    ("shimaguni", puzzle::shimaguni::solve_shimaguni),
+    ("norinori", puzzle::norinori::solve_norinori),
+    ("coral", puzzle::coral::solve_coral),
+    ("cave", puzzle::cave::solve_cave),
+    ("curvedata", puzzle::curvedata::solve_curvedata),
+    ("shikaku", puzzle::shikaku::solve_shikaku),
+    ("sudoku", puzzle::sudoku::solve_sudoku),
+    ("sashigane", puzzle::sashigane::solve_sashigane),
+    ("lohkous", puzzle::lohkous::solve_lohkous),
+    ("hashi", puzzle::hashi::solve_hashi),
+    ("herugolf", puzzle::herugolf::solve_herugolf),
+    ("slashpack", puzzle::slashpack::solve_slashpack),
+    ("moonsun", puzzle::moonsun::solve_moonsun),
+    ("fillomino", puzzle::fillomino::solve_fillomino),
+    ("cbanana", puzzle::chocobanana::solve_chocobanana),
+    ("fivecells", puzzle::fivecells::solve_fivecells),
+    ("cocktail", puzzle::cocktail::solve_cocktail),
+    ("stostone", puzzle::stostone::solve_stostone),
+    ("pencils", puzzle::pencils::solve_pencils),
+    ("barns", puzzle::barns::solve_barns),
+    ("reflect", puzzle::reflect::solve_reflect_link),
+    ("ringring", puzzle::ringring::solve_ringring),
+    ("loopsp", puzzle::loop_special::solve_loop_speical),
+    ("nagenawa", puzzle::nagenawa::solve_nagenawa),
+    ("icewalk", puzzle::icewalk::solve_icewalk),
+    ("kouchoku", puzzle::kouchoku::solve_kouchoku),
+    ("creek", puzzle::creek::solve_creek),
+    ("squarejam", puzzle::square_jam::solve_square_jam),
+    ("hitori", puzzle::hitori::solve_hitori),
+    ("kakuro", puzzle::kakuro::solve_kakuro),
+    ("dominosa", puzzle::dominosa::solve_dominosa),
+    ("nonogram", puzzle::nonogram::solve_nonogram),
+    ("yinyang", puzzle::yin_yang::solve_yin_yang),
+    ("aquarium", puzzle::aquarium::solve_aquarium),
+    ("tentaisho", puzzle::spiral_galaxies::solve_spiral_galaxies),
+    (
+        "spiralgalaxies",
+        puzzle::spiral_galaxies::solve_spiral_galaxies,
+    ),
+    ("tatamibari", puzzle::tatamibari::solve_tatamibari),
+    ("nanro", puzzle::nanro::solve_nanro),
+    ("kurodoko", puzzle::kuromasu::solve_kuromasu),
+    ("kuromasu", puzzle::kuromasu::solve_kuromasu),
+    ("country", puzzle::country_road::solve_country_road),
+    ("country_road", puzzle::country_road::solve_country_road),
+    ("doubleback", puzzle::double_back::solve_double_back),
+    ("double_back", puzzle::double_back::solve_double_back),
+    ("detour", puzzle::detour::solve_detour),
+    ("tents", puzzle::tents::solve_tents),
+    ("skyscrapers", puzzle::skyscrapers::solve_skyscrapers),
+    ("killersudoku", puzzle::killer_sudoku::solve_killer_sudoku),
+    ("killer_sudoku", puzzle::killer_sudoku::solve_killer_sudoku),
+    ("thermosudoku", puzzle::thermo_sudoku::solve_thermo_sudoku),
+    ("thermo_sudoku", puzzle::thermo_sudoku::solve_thermo_sudoku),
+    (
+        "sandwichsudoku",
+        puzzle::sandwich_sudoku::solve_sandwich_sudoku,
+    ),
+    (
+        "sandwich_sudoku",
+        puzzle::sandwich_sudoku::solve_sandwich_sudoku,
+    ),
+    ("mochikoro", puzzle::mochikoro::solve_mochikoro),
+    ("mochinyoro", puzzle::mochinyoro::solve_mochinyoro),
+    ("geradeweg", puzzle::geradeweg::solve_geradeweg),
+    ("maxi", puzzle::maxi_loop::solve_maxi_loop),
+    ("maxi_loop", puzzle::maxi_loop::solve_maxi_loop),
+    ("balance", puzzle::balance_loop::solve_balance_loop),
+    ("balance_loop", puzzle::balance_loop::solve_balance_loop),
+    ("yajikazu", puzzle::yajisan_kazusan::solve_yajisan_kazusan),
+    (
+        "yajisan_kazusan",
+        puzzle::yajisan_kazusan::solve_yajisan_kazusan,
+    ),
+    ("kakurasu", puzzle::kakurasu::solve_kakurasu),
+    ("dosufuwa", puzzle::dosun_fuwari::solve_dosun_fuwari),
+    ("dosun_fuwari", puzzle::dosun_fuwari::solve_dosun_fuwari),
+    ("statuepark", puzzle::statue_park::solve_statue_park),
+    ("statue_park", puzzle::statue_park::solve_statue_park),
+    ("canal", puzzle::canal_view::solve_canal_view),
+    ("canal_view", puzzle::canal_view::solve_canal_view),
+    ("nurimaze", puzzle::nurimaze::solve_nurimaze),
+    ("haisu", puzzle::haisu::solve_haisu),
+    ("chainedb", puzzle::chained_block::solve_chained_block),
+    ("chained_block", puzzle::chained_block::solve_chained_block),
+];
+
+fn get_solver(puzzle_kind: &str) -> Option<SolveFn> {
+    PUZZLE_SOLVERS
+        .iter()
+        .find(|&&(kind, _)| kind == puzzle_kind)
+        .map(|&(_, solver)| solver)
+}
+
+fn solve_puzz_link(
+    puzzle_kind: String,
+    url: &str,
+    ctx: &mut SolveContext,
+) -> Result<Board, SolveError> {
+    let solver = get_solver(&puzzle_kind).ok_or(SolveError::UnknownPuzzle)?;
+    solver(url, ctx)
+}
+
+/// The board returned by `decode_and_solve`.
+enum Solution {
+    Complete(Board),
+    /// Solving did not finish within the time limit, so the board shows only the facts which
+    /// had been proven by then.
+    Partial(Board),
+}
+
+/// Returns `Err(SolveError::Timeout)` if solving does not finish within the time limit of `ctx`
+/// and nothing can be shown.
+fn decode_and_solve(url: &[u8], ctx: &mut SolveContext) -> Result<Solution, SolveError> {
+    let result = decode_and_solve_board(url, ctx);
+    match (result, ctx.timed_out()) {
+        (Ok(board), true) => Ok(Solution::Partial(board)),
+        (Ok(board), false) => Ok(Solution::Complete(board)),
+        (Err(_), true) => Err(SolveError::Timeout),
+        (Err(err), false) => Err(err),
     }
 }
 
-fn decode_and_solve(url: &[u8]) -> Result<Board, &'static str> {
-    let url = std::str::from_utf8(url).map_err(|_| "failed to decode URL as UTF-8")?;
+/// Decodes `url` as UTF-8.
+fn decode_url(url: &[u8]) -> Result<&str, SolveError> {
+    std::str::from_utf8(url).map_err(|_| SolveError::ParseFailed {
+        detail: "failed to decode URL as UTF-8",
+    })
+}
+
+fn puzzle_kind_of_url(url: &str) -> Result<String, SolveError> {
+    url_to_puzzle_kind(url).ok_or(SolveError::ParseFailed {
+        detail: "puzzle type not detected",
+    })
+}
+
+/// Decodes `url` as UTF-8, converting a problem in the pzprv3 format into its puzz.link URL.
+fn decode_url_or_pzprv3(url: &[u8]) -> Result<Cow<'_, str>, SolveError> {
+    let url = decode_url(url)?;
+    match parse_pzprv3(url) {
+        Some(info) => Ok(Cow::Owned(pzprv3_to_url(&info)?)),
+        None => Ok(Cow::Borrowed(url)),
+    }
+}
 
-    let puzzle_kind = url_to_puzzle_kind(url).ok_or("puzzle type not detected");
+fn decode_and_solve_board(url: &[u8], ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let url = &decode_url_or_pzprv3(url)?;
 
-    match puzzle_kind {
-        Ok(puzzle_kind) => solve_puzz_link(puzzle_kind, url),
-        Err(_) => {
-            let kudamono = get_kudamono_url_info(url).ok_or("failed to parse URL")?;
+    match url_to_puzzle_kind(url) {
+        Some(puzzle_kind) => solve_puzz_link(puzzle_kind, url, ctx),
+        None => {
+            let kudamono = get_kudamono_url_info(url).ok_or(SolveError::ParseFailed {
+                detail: "failed to parse URL",
+            })?;
             if kudamono.puzzle_kind == "tricklayer" {
-                puzzle::tricklayer::solve_tricklayer(url)
+                puzzle::tricklayer::solve_tricklayer(url, ctx)
             } else if kudamono.puzzle_kind == "parrot-loop" {
-                puzzle::parrot_loop::solve_parrot_loop(url)
+                puzzle::parrot_loop::solve_parrot_loop(url, ctx)
             } else if kudamono.puzzle_kind == "crosswall" {
-                puzzle::crosswall::solve_crosswall(url)
+                puzzle::crosswall::solve_crosswall(url, ctx)
             } else {
-                Err("unknown puzzle type")
+                Err(SolveError::UnknownPuzzle)
             }
         }
     }
 }
 
+/// Converts a problem in the pzprv3 format into the puzz.link URL of the same problem.
+fn pzprv3_to_url(info: &Pzprv3Info) -> Result<String, SolveError> {
+    const INVALID_DATA: SolveError = SolveError::ParseFailed {
+        detail: "invalid pzprv3 data",
+    };
+    let numbers = pzprv3_cell_numbers(info).ok_or(INVALID_DATA)?;
+    let url = match info.puzzle_kind {
+        "nurikabe" => cspuz_rs::puzzle::nurikabe::serialize_problem(&numbers),
+        "slither" => {
+            // unknown clues do not constrain the answer
+            let clues = map_2d(&numbers, |n| n.filter(|&n| n >= 0));
+            cspuz_rs::puzzle::slitherlink::serialize_problem(&clues)
+        }
+        _ => return Err(SolveError::UnsupportedPuzzle),
+    };
+    url.ok_or(INVALID_DATA)
+}
+
 fn decode_and_enumerate(
     url: &[u8],
     num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let url = std::str::from_utf8(url).map_err(|_| "failed to decode URL as UTF-8")?;
+) -> Result<(Board, Vec<Board>), SolveError> {
+    let url = decode_url(url)?;
 
-    let puzzle_kind = url_to_puzzle_kind(url).ok_or("puzzle type not detected")?;
+    let puzzle_kind = puzzle_kind_of_url(url)?;
 
-    if puzzle_kind == "heyawake" {
-        puzzle::heyawake::enumerate_answers_heyawake(url, num_max_answers)
-    } else if puzzle_kind == "curvedata" {
-        puzzle::curvedata::enumerate_answers_curvedata(url, num_max_answers)
+    if let Some(enumerate) = enumeration::get_enumerator(&puzzle_kind) {
+        enumerate(url, num_max_answers)
     } else {
-        Err("unsupported puzzle type")
+        Err(SolveError::UnsupportedPuzzle)
     }
 }
 
-#[no_mangle]
-fn solve_problem(url: *const u8, len: usize) -> *const u8 {
+/// Escapes `s` so that it can be embedded in a JSON string literal.
+fn json_escape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+fn error_json(err: &str) -> String {
+    format!(
+        "{{\"status\":\"error\",\"description\":\"{}\"}}",
+        json_escape(err)
+    )
+}
+
+/// Stores `ret_string` preceded by its length (4 bytes, little endian) in the buffer shared with
+/// the caller, and returns the pointer to it.
+/// The buffer is owned by the current thread and is valid until the next call on the same thread.
+fn write_shared_array(ret_string: &str) -> *const u8 {
+    let ret_len = ret_string.len();
+    SHARED_ARRAY.with(|shared_array| {
+        let mut shared_array = shared_array.borrow_mut();
+        shared_array.clear();
+        shared_array.reserve(4 + ret_len);
+        shared_array.push((ret_len & 0xff) as u8);
+        shared_array.push(((ret_len >> 8) & 0xff) as u8);
+        shared_array.push(((ret_len >> 16) & 0xff) as u8);
+        shared_array.push(((ret_len >> 24) & 0xff) as u8);
+        shared_array.extend_from_slice(ret_string.as_bytes());
+        shared_array.as_ptr()
+    })
+}
+
+fn stats_json(stats: &Statistics) -> String {
+    let num = |n: Option<u64>| match n {
+        Some(n) => n.to_string(),
+        None => String::from("null"),
+    };
+    format!(
+        "{{\"conflicts\":{},\"decisions\":{},\"propagations\":{},\"clauses\":{},\"solve_time_ms\":{}}}",
+        num(stats.conflicts),
+        num(stats.decisions),
+        num(stats.propagations),
+        num(stats.clauses),
+        stats.solve_time.as_secs_f64() * 1000.0
+    )
+}
+
+fn solve_problem_impl(
+    url: *const u8,
+    len: usize,
+    timeout_ms: usize,
+    with_stats: bool,
+) -> *const u8 {
     let url = unsafe { std::slice::from_raw_parts(url, len) };
-    let result = decode_and_solve(url);
+    let mut ctx = if timeout_ms == 0 {
+        SolveContext::new()
+    } else {
+        SolveContext::with_timeout_partial(Duration::from_millis(timeout_ms as u64))
+    };
+    write_shared_array(&solve_to_json(url, &mut ctx, with_stats))
+}
 
-    let ret_string = match result {
-        Ok(board) => {
-            format!("{{\"status\":\"ok\",\"description\":{}}}", board.to_json())
-        }
-        Err(err) => {
-            // TODO: escape `err` if necessary
-            format!("{{\"status\":\"error\",\"description\":\"{}\"}}", err)
+/// Solves the puzzle given by `url` within the time limit of `ctx` and returns the response of
+/// `solve_problem` (or `solve_problem_with_stats` if `with_stats` is true).
+fn solve_to_json(url: &[u8], ctx: &mut SolveContext, with_stats: bool) -> String {
+    let mut result = decode_and_solve(url, ctx);
+    if let Ok(Solution::Complete(board)) = &mut result {
+        match ctx.is_unique() {
+            Some(true) => board.set_is_unique(true),
+            Some(false) => {
+                // the cells which differ between the answers are marked as not decided
+                let _ = set_decided_by_comparison(url, board);
+                board.set_is_unique(false);
+            }
+            None => (),
         }
+    }
+
+    let stats = if with_stats {
+        format!(",\"stats\":{}", stats_json(ctx.statistics()))
+    } else {
+        String::new()
     };
+    match result {
+        Ok(Solution::Complete(board)) => format!(
+            "{{\"status\":\"ok\",\"description\":{}{}}}",
+            board.to_json(),
+            stats
+        ),
+        Ok(Solution::Partial(board)) => format!(
+            "{{\"status\":\"partial\",\"description\":{}{}}}",
+            board.to_json(),
+            stats
+        ),
+        Err(SolveError::Timeout) => String::from("{\"status\":\"timeout\"}"),
+        Err(err) => error_json(&err.to_string()),
+    }
+}
 
-    let ret_len = ret_string.len();
-    unsafe {
-        SHARED_ARRAY.clear();
-        SHARED_ARRAY.reserve(4 + ret_len);
-        SHARED_ARRAY.push((ret_len & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 8) & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 16) & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 24) & 0xff) as u8);
-        SHARED_ARRAY.extend_from_slice(ret_string.as_bytes());
-        SHARED_ARRAY.as_ptr()
+/// Solves the puzzle given by the URL `url[0..len]`, giving up after `timeout_ms` milliseconds
+/// unless it is 0.
+/// The board has `"is_unique"` if solving finished. If the answer is not unique, the board also
+/// has `"decided"` as in the response of `solve_problem_with_decided`, where the cells which
+/// differ between the answers are not decided.
+#[no_mangle]
+fn solve_problem(url: *const u8, len: usize, timeout_ms: usize) -> *const u8 {
+    solve_problem_impl(url, len, timeout_ms, false)
+}
+
+/// Same as `solve_problem`, but the response also has `"stats"`, the statistics of the SAT solver
+/// summed over all the calls for solving the puzzle.
+#[no_mangle]
+fn solve_problem_with_stats(url: *const u8, len: usize, timeout_ms: usize) -> *const u8 {
+    solve_problem_impl(url, len, timeout_ms, true)
+}
+
+fn decode_and_solve_with_decided(url: &[u8], ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let decoded_url = &decode_url_or_pzprv3(url)?;
+
+    let decided_solver = url_to_puzzle_kind(decoded_url)
+        .and_then(|puzzle_kind| enumeration::get_decided_solver(&puzzle_kind));
+    if let Some(solver) = decided_solver {
+        return solver(decoded_url, ctx);
     }
+
+    let mut board = decode_and_solve_board(url, ctx)?;
+    set_decided_by_comparison(url, &mut board)?;
+    Ok(board)
+}
+
+/// Marks whether each item of the answer is decided in `board`, the board showing the
+/// irrefutable facts of the problem `url`. This works for any puzzle, by comparing `board` with
+/// the boards showing an arbitrary answer and the problem only.
+fn set_decided_by_comparison(url: &[u8], board: &mut Board) -> Result<(), SolveError> {
+    let answer = decode_and_solve_board(
+        url,
+        &mut SolveContext::with_facts_mode(FactsMode::AnyAnswer),
+    )?;
+    let clues =
+        decode_and_solve_board(url, &mut SolveContext::with_facts_mode(FactsMode::Nothing))?;
+    board.set_decided_by_comparison(&answer, &clues);
+    Ok(())
+}
+
+/// Same as `solve_problem` without time limit, but the board also has `"decided"`, which tells
+/// whether each cell (or edge) of the answer is uniquely determined.
+///
+/// For the puzzles supporting `enumerate_answers_problem`, every answer key of the puzzle is
+/// listed. For the other puzzles, the answer is located by comparing the board with that of an
+/// arbitrary answer, so only the positions where this answer shows an item are listed.
+#[no_mangle]
+fn solve_problem_with_decided(url: *const u8, len: usize) -> *const u8 {
+    let url = unsafe { std::slice::from_raw_parts(url, len) };
+
+    let ret_string = match decode_and_solve_with_decided(url, &mut SolveContext::new()) {
+        Ok(board) => format!("{{\"status\":\"ok\",\"description\":{}}}", board.to_json()),
+        Err(err) => error_json(&err.to_string()),
+    };
+
+    write_shared_array(&ret_string)
 }
 
 #[no_mangle]
@@ -194,21 +446,440 @@ fn enumerate_answers_problem(url: *const u8, len: usize, num_max_answers: usize)
                     .join(",")
             )
         }
-        Err(err) => {
-            // TODO: escape `err` if necessary
-            format!("{{\"status\":\"error\",\"description\":\"{}\"}}", err)
-        }
+        Err(err) => error_json(&err.to_string()),
     };
 
-    let ret_len = ret_string.len();
-    unsafe {
-        SHARED_ARRAY.clear();
-        SHARED_ARRAY.reserve(4 + ret_len);
-        SHARED_ARRAY.push((ret_len & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 8) & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 16) & 0xff) as u8);
-        SHARED_ARRAY.push(((ret_len >> 24) & 0xff) as u8);
-        SHARED_ARRAY.extend_from_slice(ret_string.as_bytes());
-        SHARED_ARRAY.as_ptr()
+    write_shared_array(&ret_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a JSON string literal (without the surrounding quotes).
+    /// Returns `None` if it is not a valid one.
+    fn decode_json_string(s: &str) -> Option<String> {
+        let mut ret = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return None,
+                c if (c as u32) < 0x20 => return None,
+                '\\' => match chars.next()? {
+                    '"' => ret.push('"'),
+                    '\\' => ret.push('\\'),
+                    '/' => ret.push('/'),
+                    'n' => ret.push('\n'),
+                    'r' => ret.push('\r'),
+                    't' => ret.push('\t'),
+                    'u' => {
+                        let code = chars.by_ref().take(4).collect::<String>();
+                        ret.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                    }
+                    _ => return None,
+                },
+                c => ret.push(c),
+            }
+        }
+        Some(ret)
+    }
+
+    /// Reads the string returned by `solve_problem` or `enumerate_answers_problem`.
+    fn read_shared_array(ptr: *const u8) -> String {
+        let len = unsafe { std::slice::from_raw_parts(ptr, 4) };
+        let len = len[0] as usize
+            | (len[1] as usize) << 8
+            | (len[2] as usize) << 16
+            | (len[3] as usize) << 24;
+        let body = unsafe { std::slice::from_raw_parts(ptr.add(4), len) };
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_shared_array() {
+        let url = b"https://puzz.link/p?nurikabe/6/6/m8n8i9u";
+        let first = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(first.starts_with("{\"status\":\"ok\""));
+
+        let url = b"https://puzz.link/p?unknown-puzzle/2/2";
+        let second = read_shared_array(enumerate_answers_problem(url.as_ptr(), url.len(), 10));
+        assert_eq!(
+            second,
+            "{\"status\":\"error\",\"description\":\"unsupported puzzle type\"}"
+        );
+
+        let url = b"https://puzz.link/p?nurikabe/6/6/m8n8i9u";
+        let third = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert_eq!(first, third);
+
+        // each thread has its own buffer
+        let handle = std::thread::spawn(|| {
+            let url = b"https://puzz.link/p?nurikabe/6/6/m8n8i9u";
+            read_shared_array(solve_problem(url.as_ptr(), url.len(), 0))
+        });
+        assert_eq!(handle.join().unwrap(), first);
+    }
+
+    #[test]
+    fn test_solve_problem_timeout() {
+        // the deadline has already passed, so nothing can be proven
+        let url = b"https://puzz.link/p?nurikabe/6/6/m8n8i9u";
+        let mut ctx = SolveContext::with_timeout_partial(Duration::ZERO);
+        assert_eq!(
+            solve_to_json(url, &mut ctx, false),
+            "{\"status\":\"timeout\"}"
+        );
+
+        let mut ctx = SolveContext::with_timeout_partial(Duration::ZERO);
+        assert!(matches!(
+            decode_and_solve(url, &mut ctx),
+            Err(SolveError::Timeout)
+        ));
+
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 100000));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+    }
+
+    #[test]
+    fn test_solve_problem_partial() {
+        use cspuz_rs::puzzle::masyu::{self, MasyuClue};
+
+        // 30x30 Masyu with two clues; the deadline has already passed, but the lines from the
+        // black circle in the corner are fixed without the SAT solver
+        let mut problem = vec![vec![MasyuClue::None; 30]; 30];
+        problem[0][0] = MasyuClue::Black;
+        problem[10][20] = MasyuClue::White;
+        let url = masyu::serialize_problem(&problem).unwrap();
+
+        let mut ctx = SolveContext::with_timeout_partial(Duration::ZERO);
+        let res = solve_to_json(url.as_bytes(), &mut ctx, false);
+        assert!(res.starts_with("{\"status\":\"partial\""));
+        assert!(res.contains("{\"y\":1,\"x\":1,\"color\":\"black\",\"item\":\"filledCircle\"}"));
+        assert!(res.contains("{\"y\":21,\"x\":41,\"color\":\"black\",\"item\":\"circle\"}"));
+        assert!(res.contains("{\"y\":2,\"x\":1,\"color\":\"green\",\"item\":\"line\"}"));
+        assert!(res.contains("{\"y\":1,\"x\":2,\"color\":\"green\",\"item\":\"line\"}"));
+        assert!(!res.contains("\"is_unique\""));
+
+        let mut ctx = SolveContext::with_timeout_partial(Duration::ZERO);
+        assert!(matches!(
+            decode_and_solve(url.as_bytes(), &mut ctx),
+            Ok(Solution::Partial(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_problem_with_stats() {
+        let url = b"https://puzz.link/p?nurikabe/6/6/m8n8i9u";
+        let res = read_shared_array(solve_problem_with_stats(url.as_ptr(), url.len(), 0));
+        let stats_start = res.find(",\"stats\":{").unwrap() + ",\"stats\":{".len();
+        assert!(res.ends_with("}}"));
+        let stats = &res[stats_start..(res.len() - 2)];
+
+        let mut keys = vec![];
+        for entry in stats.split(',') {
+            let (key, value) = entry.split_once(':').unwrap();
+            assert!(value.parse::<f64>().is_ok(), "{} is not a number", entry);
+            keys.push(key);
+        }
+        assert_eq!(
+            keys,
+            vec![
+                "\"conflicts\"",
+                "\"decisions\"",
+                "\"propagations\"",
+                "\"clauses\"",
+                "\"solve_time_ms\""
+            ]
+        );
+
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(!res.contains("\"stats\""));
+    }
+
+    #[test]
+    fn test_solve_problem_uniqueness() {
+        let url = b"https://puzz.link/p?akari/10/10/hcscl.h.idn.i.cgcndg.h.ncs.h";
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(res.ends_with(",\"is_unique\":true}}"));
+
+        // empty 2x2 Akari, where lights can be placed on either diagonal
+        let url = cspuz_rs::puzzle::akari::serialize_problem(&vec![vec![None; 2]; 2]).unwrap();
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+        assert!(res.ends_with(
+            ",\"is_unique\":false,\"decided\":[{\"y\":1,\"x\":1,\"decided\":false},\
+             {\"y\":1,\"x\":3,\"decided\":false},\
+             {\"y\":3,\"x\":1,\"decided\":false},\
+             {\"y\":3,\"x\":3,\"decided\":false}]}}"
+        ));
+
+        // 2x2 Nurikabe with the clue 2 at the top left, which can extend either rightward or
+        // downward, so only the bottom right cell is decided
+        let url = cspuz_rs::puzzle::nurikabe::serialize_problem(&vec![
+            vec![Some(2), None],
+            vec![None; 2],
+        ])
+        .unwrap();
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(res.ends_with(
+            ",\"is_unique\":false,\"decided\":[{\"y\":1,\"x\":3,\"decided\":false},\
+             {\"y\":3,\"x\":1,\"decided\":false},\
+             {\"y\":3,\"x\":3,\"decided\":true}]}}"
+        ));
+    }
+
+    #[test]
+    fn test_enumerate_answers_nurikabe() {
+        // the clue 2 can extend either rightward or downward
+        let url = cspuz_rs::puzzle::nurikabe::serialize_problem(&vec![
+            vec![Some(2), None],
+            vec![None; 2],
+        ])
+        .unwrap();
+        let res = read_shared_array(enumerate_answers_problem(url.as_ptr(), url.len(), 10));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+        let answers_start = res.find(",\"answers\":[").unwrap();
+        let answers = &res[answers_start..];
+        assert_eq!(answers.matches("\"defaultStyle\":\"empty\"").count(), 2);
+        assert_eq!(answers.matches("\"item\":\"block\"").count(), 2);
+        assert_eq!(answers.matches("\"item\":\"dot\"").count(), 2);
+
+        let res = read_shared_array(enumerate_answers_problem(url.as_ptr(), url.len(), 1));
+        let answers_start = res.find(",\"answers\":[").unwrap();
+        assert_eq!(
+            res[answers_start..]
+                .matches("\"defaultStyle\":\"empty\"")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_enumerate_answers_sudoku() {
+        // the 1 and 3 in the top two rows can be swapped
+        let url = cspuz_rs::puzzle::sudoku::serialize_problem(&vec![
+            vec![None, Some(2), None, Some(4)],
+            vec![None, Some(4), None, Some(2)],
+            vec![Some(2), Some(1), Some(4), Some(3)],
+            vec![Some(4), Some(3), Some(2), Some(1)],
+        ])
+        .unwrap();
+        let res = read_shared_array(enumerate_answers_problem(url.as_ptr(), url.len(), 10));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+        let answers_start = res.find(",\"answers\":[").unwrap();
+        let (common, answers) = res.split_at(answers_start);
+        assert_eq!(common.matches("\"kind\":\"text\"").count(), 12);
+        let mut answers = answers
+            .split("\"defaultStyle\":\"empty\"")
+            .skip(1)
+            .map(|answer| {
+                answer
+                    .split("\"data\":\"")
+                    .skip(1)
+                    .map(|item| item.chars().next().unwrap())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        answers.sort();
+        assert_eq!(answers, vec!["1331", "3113"]);
+    }
+
+    #[test]
+    fn test_solve_problem_pzprv3() {
+        let cases: [(&[u8], &[u8]); 2] = [
+            (
+                b"pzprv3/nurikabe/6/6/. . . . . . /. 8 . . . . /. . . . 8 . /. . 9 . . . /. . . . . . /. . . . . . /",
+                b"https://puzz.link/p?nurikabe/6/6/m8n8i9u",
+            ),
+            (
+                b"pzprv3\nslither\n4\n4\n3 . . . \n3 . . . \n. 2 2 . \n. 2 . 1 \n",
+                b"https://puzz.link/p?slither/4/4/dgdh2c71",
+            ),
+        ];
+        for (pzprv3, url) in cases {
+            let res = read_shared_array(solve_problem(pzprv3.as_ptr(), pzprv3.len(), 0));
+            assert!(res.starts_with("{\"status\":\"ok\""));
+            let expected = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+            assert_eq!(res, expected);
+        }
+
+        let pzprv3 = b"pzprv3/unknown-puzzle/1/1/. /";
+        let res = read_shared_array(solve_problem(pzprv3.as_ptr(), pzprv3.len(), 0));
+        assert_eq!(
+            res,
+            "{\"status\":\"error\",\"description\":\"unsupported puzzle type\"}"
+        );
+    }
+
+    /// Solvable problems used as a smoke test of the dispatch through `PUZZLE_SOLVERS`. The
+    /// solvers themselves are tested in `cspuz_rs`.
+    const SMOKE_TEST_URLS: &[&str] = &[
+        "https://puzz.link/p?numlin/4/4/3k13g2h1h2",
+        "https://puzz.link/p?hitori/4/4/2411412321411323",
+        "https://puzz.link/p?kakuro/3/3/..4.3..3h.4h",
+        "https://puzz.link/p?dominosa/4/3/122020021011",
+        "https://puzz.link/p?nonogram/5/5/11.5.5.3.1.2.4.4.4.2",
+        "https://puzz.link/p?yinyang/4/4/00899i",
+        "https://puzz.link/p?aquarium/4/4/aqgd1o22234230",
+        "https://puzz.link/p?tentaisho/3/3/g0904",
+        "https://puzz.link/p?tatamibari/4/4/2i31g2i22g11",
+        "https://puzz.link/p?nanro/4/4/94g0f0h1l32g2i",
+        "https://puzz.link/p?kurodoko/5/5/g77j7q6k",
+        "https://puzz.link/p?country/5/5/8hlk03tm25h4g",
+        "https://puzz.link/p?doubleback/5/4/4h8046vg004",
+        "https://puzz.link/p?detour/4/4/928o80g46",
+        "https://puzz.link/p?tents/5/5/20111111022a048",
+        "https://puzz.link/p?skyscrapers/4/4/3g3i222m",
+        "https://puzz.link/p?killersudoku/4/4/luocq8g761i",
+        "https://puzz.link/p?thermosudoku/4/4/v/9gjh3i4gj",
+        "https://puzz.link/p?sandwichsudoku/4/4/50005302k4p",
+        "https://puzz.link/p?mochikoro/5/5/1iak1o2j",
+        "https://puzz.link/p?mochinyoro/5/5/2hbi2w",
+        "https://puzz.link/p?geradeweg/5/5/l2g2h3s",
+        "https://puzz.link/p?maxi/5/4/5880fi12232",
+        "https://puzz.link/p?balance/5/4/j24l1.k1.h",
+        "https://puzz.link/p?yajikazu/4/4/c2142a2112a3121e",
+        "https://puzz.link/p?kakurasu/4/4/65823754",
+        "https://puzz.link/p?dosufuwa/4/4/8k05ko0400",
+        "https://puzz.link/p?statuepark/5/5/0o000290i/13s22m21o",
+        "https://puzz.link/p?canal/4/5/g3q4g1i0",
+        "https://puzz.link/p?nurimaze/5/5/5rtmfasf32718494c",
+        "https://puzz.link/p?haisu/4/4/4mg1ogn12l/3/7",
+        "https://puzz.link/p?chainedb/5/4/1l2m2i3",
+    ];
+
+    #[test]
+    fn test_puzzle_solvers_smoke() {
+        for &url in SMOKE_TEST_URLS {
+            let kind = url_to_puzzle_kind(url).unwrap();
+            assert!(get_solver(&kind).is_some(), "{} is not registered", kind);
+            assert!(
+                solve_puzz_link(kind, url, &mut SolveContext::new()).is_ok(),
+                "failed to solve {}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_puzzle_solvers() {
+        for (i, &(kind, _)) in PUZZLE_SOLVERS.iter().enumerate() {
+            assert!(
+                PUZZLE_SOLVERS[..i].iter().all(|&(k, _)| k != kind),
+                "{} is registered twice",
+                kind
+            );
+            assert!(get_solver(kind).is_some());
+
+            // the problem is empty, but the puzzle kind should be recognized
+            let url = format!("https://puzz.link/p?{}/", kind);
+            let res = solve_puzz_link(String::from(kind), &url, &mut SolveContext::new());
+            assert!(res.is_err());
+            assert_ne!(res.err(), Some(SolveError::UnknownPuzzle));
+        }
+        assert!(get_solver("unknown-puzzle").is_none());
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let solve_error = |url: &[u8]| decode_and_solve(url, &mut SolveContext::new()).err();
+        assert_eq!(
+            solve_error(b"https://puzz.link/p?unknown-puzzle/2/2"),
+            Some(SolveError::UnknownPuzzle)
+        );
+        assert_eq!(
+            solve_error(b"https://pedros.works/paper-puzzle-player?W=1&H=1&G=unknown-puzzle"),
+            Some(SolveError::UnknownPuzzle)
+        );
+        assert_eq!(
+            solve_error(b"pzprv3/unknown-puzzle/1/1/. /"),
+            Some(SolveError::UnsupportedPuzzle)
+        );
+        assert_eq!(
+            solve_error(b"https://puzz.link/p?nurikabe/x/y"),
+            Some(SolveError::ParseFailed {
+                detail: "invalid url"
+            })
+        );
+        assert_eq!(
+            solve_error(b"not a url"),
+            Some(SolveError::ParseFailed {
+                detail: "failed to parse URL"
+            })
+        );
+        assert_eq!(
+            solve_error(b"\xff"),
+            Some(SolveError::ParseFailed {
+                detail: "failed to decode URL as UTF-8"
+            })
+        );
+
+        // the clue 1 in a 1x1 Akari can never be satisfied
+        let url = cspuz_rs::puzzle::akari::serialize_problem(&vec![vec![Some(1)]]).unwrap();
+        assert_eq!(solve_error(url.as_bytes()), Some(SolveError::Unsatisfiable));
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert_eq!(res, "{\"status\":\"error\",\"description\":\"no answer\"}");
+    }
+
+    #[test]
+    fn test_solve_problem_with_decided() {
+        // 2x2 Heyawake with two rooms (the columns) with clues 0 and 1, where the black cell
+        // can be either of the cells in the right column
+        let borders = cspuz_rs::graph::InnerGridEdges {
+            horizontal: vec![vec![false, false]],
+            vertical: vec![vec![true], vec![true]],
+        };
+        let url = cspuz_rs::puzzle::heyawake::serialize_problem(&(borders, vec![Some(0), Some(1)]))
+            .unwrap();
+        let res = read_shared_array(solve_problem_with_decided(url.as_ptr(), url.len()));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+        assert_eq!(res.matches("\"item\":\"dot\"").count(), 2);
+        assert!(!res.contains("\"item\":\"block\""));
+        assert!(res.ends_with(
+            ",\"decided\":[{\"y\":1,\"x\":1,\"decided\":true},\
+             {\"y\":1,\"x\":3,\"decided\":false},\
+             {\"y\":3,\"x\":1,\"decided\":true},\
+             {\"y\":3,\"x\":3,\"decided\":false}]}}"
+        ));
+
+        // 2x3 Akari with the clue 1 in the top middle, which does not implement
+        // `EnumerablePuzzle`: the light next to the clue is either of the top corners, and the
+        // cell below the clue is always lit
+        let url = cspuz_rs::puzzle::akari::serialize_problem(&vec![
+            vec![None, Some(1), None],
+            vec![None; 3],
+        ])
+        .unwrap();
+        let expected_decided = ",\"decided\":[{\"y\":1,\"x\":1,\"decided\":false},\
+                                {\"y\":1,\"x\":5,\"decided\":false},\
+                                {\"y\":3,\"x\":1,\"decided\":false},\
+                                {\"y\":3,\"x\":3,\"decided\":true},\
+                                {\"y\":3,\"x\":5,\"decided\":false}]}";
+        let res = read_shared_array(solve_problem_with_decided(url.as_ptr(), url.len()));
+        assert!(res.starts_with("{\"status\":\"ok\""));
+        assert_eq!(res.matches("\"item\":\"dot\"").count(), 1);
+        assert!(!res.contains("\"item\":\"circle\""));
+        assert!(res.ends_with(&format!("{}}}", expected_decided)));
+
+        // `solve_problem` marks the same cells if the answer is not unique
+        let res = read_shared_array(solve_problem(url.as_ptr(), url.len(), 0));
+        assert!(res.ends_with(&format!(",\"is_unique\":false{}}}", expected_decided)));
+    }
+
+    #[test]
+    fn test_error_json_escape() {
+        for err in [
+            "unknown puzzle type",
+            "invalid clue \"3\" at (1, 2)",
+            "path: C:\\puzzles\\a.txt",
+            "line 1\nline 2\t\u{1}",
+        ] {
+            let json = error_json(err);
+            let prefix = "{\"status\":\"error\",\"description\":\"";
+            assert!(json.starts_with(prefix));
+            assert!(json.ends_with("\"}"));
+            let literal = &json[prefix.len()..(json.len() - 2)];
+            assert_eq!(decode_json_string(literal).as_deref(), Some(err));
+        }
     }
 }
@@ -0,0 +1,32 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::aquarium;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_aquarium(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, (clue_vertical, clue_horizontal)) =
+        aquarium::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_water = aquarium::solve_aquarium(&borders, &clue_vertical, &clue_horizontal, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let height = is_water.len();
+    let width = is_water[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(b) = is_water[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
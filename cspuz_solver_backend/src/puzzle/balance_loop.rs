@@ -0,0 +1,38 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::balance_loop::{self, BalanceLoopCircle};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_balance_loop(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = balance_loop::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line =
+        balance_loop::solve_balance_loop(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some((circle, n)) = problem[y][x] {
+                let num_color = match circle {
+                    BalanceLoopCircle::White => {
+                        board.push(Item::cell(y, x, "black", ItemKind::Circle));
+                        "black"
+                    }
+                    BalanceLoopCircle::Black => {
+                        board.push(Item::cell(y, x, "black", ItemKind::FilledCircle));
+                        "white"
+                    }
+                };
+                if n > 0 {
+                    board.push(Item::cell(y, x, num_color, ItemKind::Num(n)));
+                }
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::barns;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_barns(url: &str) -> Result<Board, &'static str> {
-    let (icebarn, borders) = barns::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = barns::solve_barns(&icebarn, &borders).ok_or("no answer")?;
+pub fn solve_barns(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (icebarn, borders) = barns::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = barns::solve_barns(&icebarn, &borders, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = icebarn.len();
     let width = icebarn[0].len();
@@ -0,0 +1,33 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::canal_view;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_canal_view(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = canal_view::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = canal_view::solve_canal_view(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(clue) = problem[y][x] {
+                if clue >= 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
+                } else {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                }
+            } else if let Some(a) = ans[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if a { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
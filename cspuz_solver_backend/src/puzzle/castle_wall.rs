@@ -1,11 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::castle_wall;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_castle_wall(url: &str) -> Result<Board, &'static str> {
+pub fn solve_castle_wall(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
     use castle_wall::Side;
     use cspuz_rs::items::Arrow;
-    let problem = castle_wall::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = castle_wall::solve_castle_wall(&problem).ok_or("no answer")?;
+    let problem = castle_wall::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = castle_wall::solve_castle_wall(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
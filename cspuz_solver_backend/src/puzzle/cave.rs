@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::cave;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_cave(url: &str) -> Result<Board, &'static str> {
-    let problem = cave::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = cave::solve_cave(&problem).ok_or("no answer")?;
+pub fn solve_cave(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = cave::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = cave::solve_cave(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::chocobanana;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_chocobanana(url: &str) -> Result<Board, &'static str> {
-    let clues = chocobanana::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = chocobanana::solve_chocobanana(&clues).ok_or("no answer")?;
+pub fn solve_chocobanana(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let clues = chocobanana::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black = chocobanana::solve_chocobanana(&clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
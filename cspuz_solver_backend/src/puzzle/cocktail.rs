@@ -1,10 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs::puzzle::cocktail;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_cocktail(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = cocktail::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = cocktail::solve_cocktail(&borders, &clues).ok_or("no answer")?;
+pub fn solve_cocktail(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) = cocktail::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black =
+        cocktail::solve_cocktail(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Compass, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::compass;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_compass(url: &str) -> Result<Board, &'static str> {
-    let problem = compass::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = compass::solve_compass(&problem).ok_or("no answer")?;
+pub fn solve_compass(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = compass::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = compass::solve_compass(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
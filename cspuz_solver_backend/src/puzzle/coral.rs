@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::coral;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_coral(url: &str) -> Result<Board, &'static str> {
-    let (vertical, horizontal) = coral::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = coral::solve_coral(&vertical, &horizontal).ok_or("no answer")?;
+pub fn solve_coral(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (vertical, horizontal) = coral::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black =
+        coral::solve_coral(&vertical, &horizontal, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
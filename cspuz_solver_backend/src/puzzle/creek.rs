@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::creek;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_creek(url: &str) -> Result<Board, &'static str> {
-    let problem = creek::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = creek::solve_creek(&problem).ok_or("no answer")?;
+pub fn solve_creek(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = creek::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = creek::solve_creek(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = ans.len();
     let width = ans[0].len();
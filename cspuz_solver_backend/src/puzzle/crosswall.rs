@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::crosswall;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_crosswall(url: &str) -> Result<Board, &'static str> {
-    let problem = crosswall::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = crosswall::solve_crosswall(&problem).ok_or("no answer")?;
+pub fn solve_crosswall(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = crosswall::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = crosswall::solve_crosswall(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,9 +1,15 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::enumeration::{AnswerKey, AnswerVar, EnumerablePuzzle};
+use crate::error::SolveError;
+use cspuz_rs::graph;
 use cspuz_rs::puzzle::curvedata;
+use cspuz_rs::solver::{SolveContext, Solver};
 
-pub fn solve_curvedata(url: &str) -> Result<Board, &'static str> {
-    let (piece_id, borders, pieces) = curvedata::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or("no answer")?;
+pub fn solve_curvedata(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (piece_id, borders, pieces) =
+        curvedata::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = curvedata::solve_curvedata(&piece_id, &borders, &pieces, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
 
     let height = piece_id.len();
     let width = piece_id[0].len();
@@ -32,72 +38,59 @@ pub fn solve_curvedata(url: &str) -> Result<Board, &'static str> {
     Ok(board)
 }
 
-pub fn enumerate_answers_curvedata(
-    url: &str,
-    num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let (piece_id, borders, pieces) = curvedata::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line_common =
-        curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or("no answer")?;
-    let answers =
-        curvedata::enumerate_answers_curvedata(&piece_id, &borders, &pieces, num_max_answers);
+pub struct Curvedata;
 
-    let height = piece_id.len();
-    let width = piece_id[0].len();
-    let mut board_common = Board::new(BoardKind::Grid, height, width);
+impl EnumerablePuzzle for Curvedata {
+    fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError> {
+        let (piece_id, borders, pieces) =
+            curvedata::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
 
-    if let Some(borders) = borders {
-        board_common.add_borders(&borders, "black");
-    }
+        let height = piece_id.len();
+        let width = piece_id[0].len();
 
-    for y in 0..height {
-        for x in 0..width {
-            match piece_id[y][x] {
-                curvedata::PieceId::None => (),
-                curvedata::PieceId::Block => {
-                    board_common.push(Item::cell(y, x, "black", ItemKind::Fill));
-                }
-                curvedata::PieceId::Piece(_) => {
-                    board_common.push(Item::cell(y, x, "black", ItemKind::Circle));
+        let mut solver = Solver::new();
+        let is_line = &graph::BoolGridEdges::new(&mut solver, (height - 1, width - 1));
+        curvedata::add_constraints(&mut solver, is_line, &piece_id, &borders, &pieces);
+
+        let mut board = Board::new(BoardKind::Grid, height, width);
+        if let Some(borders) = borders {
+            board.add_borders(&borders, "black");
+        }
+        for y in 0..height {
+            for x in 0..width {
+                match piece_id[y][x] {
+                    curvedata::PieceId::None => (),
+                    curvedata::PieceId::Block => {
+                        board.push(Item::cell(y, x, "black", ItemKind::Fill));
+                    }
+                    curvedata::PieceId::Piece(_) => {
+                        board.push(Item::cell(y, x, "black", ItemKind::Circle));
+                    }
                 }
             }
         }
-    }
-    board_common.add_lines_irrefutable_facts(&is_line_common, "green", None);
 
-    let mut board_answers = vec![];
-    for ans in answers {
-        let mut board_answer = Board::new(BoardKind::Empty, height, width);
+        let line_or_cross = |b| if b { ItemKind::Line } else { ItemKind::Cross };
+        let mut keys = vec![];
         for y in 0..height {
             for x in 0..width {
-                if y < height - 1 && is_line_common.vertical[y][x].is_none() {
-                    board_answer.push(Item {
+                if y < height - 1 {
+                    keys.push(AnswerKey {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
-                        kind: if ans.vertical[y][x] {
-                            ItemKind::Line
-                        } else {
-                            ItemKind::Cross
-                        },
+                        var: AnswerVar::Bool(is_line.vertical.at((y, x)), line_or_cross),
                     });
                 }
-                if x < width - 1 && is_line_common.horizontal[y][x].is_none() {
-                    board_answer.push(Item {
+                if x < width - 1 {
+                    keys.push(AnswerKey {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
-                        kind: if ans.horizontal[y][x] {
-                            ItemKind::Line
-                        } else {
-                            ItemKind::Cross
-                        },
+                        var: AnswerVar::Bool(is_line.horizontal.at((y, x)), line_or_cross),
                     });
                 }
             }
         }
-        board_answers.push(board_answer);
-    }
 
-    Ok((board_common, board_answers))
+        Ok((solver, board, keys))
+    }
 }
@@ -0,0 +1,31 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::dosun_fuwari;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_dosun_fuwari(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, is_black) =
+        dosun_fuwari::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (is_balloon, is_iron) = dosun_fuwari::solve_dosun_fuwari(&borders, &is_black, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let (height, width) = borders.base_shape();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if is_black[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Fill));
+            } else if is_balloon[y][x] == Some(true) {
+                board.push(Item::cell(y, x, "green", ItemKind::Circle));
+            } else if is_iron[y][x] == Some(true) {
+                board.push(Item::cell(y, x, "green", ItemKind::FilledCircle));
+            } else if is_balloon[y][x] == Some(false) && is_iron[y][x] == Some(false) {
+                board.push(Item::cell(y, x, "green", ItemKind::Dot));
+            }
+        }
+    }
+
+    Ok(board)
+}
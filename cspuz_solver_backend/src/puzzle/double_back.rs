@@ -0,0 +1,27 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::double_back;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_double_back(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, is_blocked) =
+        double_back::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = double_back::solve_double_back(&borders, &is_blocked, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let (height, width) = borders.base_shape();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if is_blocked[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Fill));
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", Some(&is_blocked));
+
+    Ok(board)
+}
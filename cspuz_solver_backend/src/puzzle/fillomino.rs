@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::fillomino;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_fillomino(url: &str) -> Result<Board, &'static str> {
-    let problem = fillomino::deserialize_problem(url).ok_or("invalid url")?;
-    let (num, border) = fillomino::solve_fillomino(&problem).ok_or("no answer")?;
+pub fn solve_fillomino(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = fillomino::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (num, border) =
+        fillomino::solve_fillomino(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = num.len();
     let width = num[0].len();
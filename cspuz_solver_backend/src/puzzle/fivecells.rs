@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::fivecells;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_fivecells(url: &str) -> Result<Board, &'static str> {
-    let problem = fivecells::deserialize_problem(url).ok_or("invalid url")?;
-    let border = fivecells::solve_fivecells(&problem).ok_or("no answer")?;
+pub fn solve_fivecells(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = fivecells::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let border = fivecells::solve_fivecells(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -0,0 +1,29 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::geradeweg;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_geradeweg(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = geradeweg::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = geradeweg::solve_geradeweg(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = problem[y][x] {
+                if n > 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+                } else {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                }
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
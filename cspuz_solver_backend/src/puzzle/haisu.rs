@@ -0,0 +1,29 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::haisu;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_haisu(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues, (start, goal)) =
+        haisu::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line =
+        haisu::solve_haisu(&borders, start, goal, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let (height, width) = borders.base_shape();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    board.add_borders(&borders, "black");
+
+    board.push(Item::cell(start.0, start.1, "black", ItemKind::Text("S")));
+    board.push(Item::cell(goal.0, goal.1, "black", ItemKind::Text("G")));
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = clues[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::hashi;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_hashi(url: &str) -> Result<Board, &'static str> {
-    let clues = hashi::deserialize_problem(url).ok_or("invalid url")?;
-    let num_line = hashi::solve_hashi(&clues).ok_or("no answer")?;
+pub fn solve_hashi(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let clues = hashi::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let num_line = hashi::solve_hashi(&clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::herugolf;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_herugolf(url: &str) -> Result<Board, &'static str> {
-    let (pond, clues) = herugolf::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = herugolf::solve_herugolf(&pond, &clues).ok_or("no answer")?;
+pub fn solve_herugolf(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (pond, clues) = herugolf::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = herugolf::solve_herugolf(&pond, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = pond.len();
     let width = pond[0].len();
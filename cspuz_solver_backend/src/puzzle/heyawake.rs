@@ -1,25 +1,36 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::enumeration::{AnswerKey, EnumerablePuzzle};
+use crate::error::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs::puzzle::{ayeheya, heyawake};
+use cspuz_rs::solver::{SolveContext, Solver};
 
-pub fn solve_heyawake(url: &str, is_ayeheya: bool) -> Result<Board, &'static str> {
+pub fn solve_heyawake(
+    url: &str,
+    is_ayeheya: bool,
+    ctx: &mut SolveContext,
+) -> Result<Board, SolveError> {
     let borders;
     let clues;
     let is_black;
 
     if is_ayeheya {
-        let problem = ayeheya::deserialize_problem(url).ok_or("invalid url")?;
+        let problem = ayeheya::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
         borders = problem.0;
         clues = problem.1;
         if !ayeheya::all_room_symmetry(&borders) {
-            return Err("asymmetry room");
+            return Err(SolveError::ParseFailed {
+                detail: "asymmetry room",
+            });
         }
-        is_black = ayeheya::solve_ayeheya(&borders, &clues).ok_or("no answer")?;
+        is_black =
+            ayeheya::solve_ayeheya(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
     } else {
-        let problem = heyawake::deserialize_problem(url).ok_or("invalid url")?;
+        let problem = heyawake::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
         borders = problem.0;
         clues = problem.1;
-        is_black = heyawake::solve_heyawake(&borders, &clues).ok_or("no answer")?;
+        is_black =
+            heyawake::solve_heyawake(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
     }
 
     let height = is_black.len();
@@ -52,61 +63,41 @@ pub fn solve_heyawake(url: &str, is_ayeheya: bool) -> Result<Board, &'static str
     Ok(board)
 }
 
-pub fn enumerate_answers_heyawake(
-    url: &str,
-    num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let (borders, clues) = heyawake::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black_common = heyawake::solve_heyawake(&borders, &clues).ok_or("no answer")?;
-    let answers = heyawake::enumerate_answers_heyawake(&borders, &clues, num_max_answers);
+pub struct Heyawake;
 
-    let height = is_black_common.len();
-    let width = is_black_common[0].len();
+impl EnumerablePuzzle for Heyawake {
+    fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError> {
+        let (borders, clues) = heyawake::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+        let (height, width) = borders.base_shape();
 
-    let mut board_common = Board::new(BoardKind::Grid, height, width);
-    board_common.add_borders(&borders, "black");
-    for y in 0..height {
-        for x in 0..width {
-            if let Some(b) = is_black_common[y][x] {
-                board_common.push(Item::cell(
-                    y,
-                    x,
-                    "#339933",
-                    if b { ItemKind::Block } else { ItemKind::Dot },
-                ));
+        let mut solver = Solver::new();
+        let is_black = &solver.bool_var_2d((height, width));
+        heyawake::add_constraints(&mut solver, is_black, &borders, &clues);
+
+        let mut board = Board::new(BoardKind::Grid, height, width);
+        board.add_borders(&borders, "black");
+        let rooms = graph::borders_to_rooms(&borders);
+        assert_eq!(rooms.len(), clues.len());
+        for i in 0..rooms.len() {
+            if let Some(n) = clues[i] {
+                let (y, x) = rooms[i][0];
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
             }
         }
-    }
-    let rooms = graph::borders_to_rooms(&borders);
-    assert_eq!(rooms.len(), clues.len());
-    for i in 0..rooms.len() {
-        if let Some(n) = clues[i] {
-            let (y, x) = rooms[i][0];
-            board_common.push(Item::cell(y, x, "black", ItemKind::Num(n)));
-        }
-    }
 
-    let mut board_answers = vec![];
-    for ans in answers {
-        let mut board_answer = Board::new(BoardKind::Empty, height, width);
+        let mut keys = vec![];
         for y in 0..height {
             for x in 0..width {
-                if is_black_common[y][x].is_none() {
-                    board_answer.push(Item::cell(
-                        y,
-                        x,
-                        "#cccccc",
-                        if ans[y][x] {
-                            ItemKind::Block
-                        } else {
-                            ItemKind::Dot
-                        },
-                    ));
-                }
+                keys.push(AnswerKey::cell(y, x, is_black.at((y, x)), |b| {
+                    if b {
+                        ItemKind::Block
+                    } else {
+                        ItemKind::Dot
+                    }
+                }));
             }
         }
-        board_answers.push(board_answer);
-    }
 
-    Ok((board_common, board_answers))
+        Ok((solver, board, keys))
+    }
 }
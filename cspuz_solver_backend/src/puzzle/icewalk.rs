@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::icewalk;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_icewalk(url: &str) -> Result<Board, &'static str> {
-    let (icebarn, num) = icewalk::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = icewalk::solve_icewalk(&icebarn, &num).ok_or("no answer")?;
+pub fn solve_icewalk(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (icebarn, num) = icewalk::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = icewalk::solve_icewalk(&icebarn, &num, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = icebarn.len();
     let width = icebarn[0].len();
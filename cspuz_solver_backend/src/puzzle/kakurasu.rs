@@ -0,0 +1,41 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::kakurasu;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_kakurasu(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (clue_vertical, clue_horizontal) =
+        kakurasu::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black = kakurasu::solve_kakurasu(&clue_vertical, &clue_horizontal, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    // the clues are shown below and to the right of the grid
+    let height = clue_horizontal.len();
+    let width = clue_vertical.len();
+    let mut board = Board::new(BoardKind::Grid, height + 1, width + 1);
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(height, x, "black", ItemKind::Num(n)));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(y, width, "black", ItemKind::Num(n)));
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(b) = is_black[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -0,0 +1,31 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::kakuro;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_kakuro(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = kakuro::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = kakuro::solve_kakuro(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    for y in 0..height {
+        for x in 0..width {
+            if let Some((down, right)) = problem[y][x] {
+                board.push(Item::cell(y, x, "#cccccc", ItemKind::Fill));
+                board.push(Item::cell(y, x, "black", ItemKind::Backslash));
+                if let Some(n) = down {
+                    board.push(Item::cell(y, x, "black", ItemKind::NumLowerLeft(n)));
+                }
+                if let Some(n) = right {
+                    board.push(Item::cell(y, x, "black", ItemKind::NumUpperRight(n)));
+                }
+            } else if let Some(n) = ans[y][x] {
+                board.push(Item::cell(y, x, "green", ItemKind::Num(n)));
+            }
+        }
+    }
+
+    Ok(board)
+}
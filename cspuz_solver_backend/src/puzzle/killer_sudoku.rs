@@ -0,0 +1,57 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs::puzzle::{killer_sudoku, sudoku};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_killer_sudoku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, sums) = killer_sudoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = killer_sudoku::solve_killer_sudoku(&borders, &sums, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let height = ans.len();
+    let width = ans[0].len();
+    let (bh, bw) = sudoku::block_shape(height).ok_or(SolveError::ParseFailed {
+        detail: "invalid size",
+    })?;
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    board.add_borders(&borders, "#cc8888");
+
+    let cages = graph::borders_to_rooms(&borders);
+    for (cage, &sum) in cages.iter().zip(&sums) {
+        if let Some(s) = sum {
+            let (y, x) = cage[0];
+            board.push(Item::cell(y, x, "black", ItemKind::NumUpperLeft(s)));
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = ans[y][x] {
+                board.push(Item::cell(y, x, "green", ItemKind::Num(n)));
+            }
+        }
+    }
+    for x in 0..bh {
+        for y in 0..height {
+            board.push(Item {
+                y: 2 * y + 1,
+                x: 2 * x * bw,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+    for y in 0..bw {
+        for x in 0..width {
+            board.push(Item {
+                y: 2 * y * bh,
+                x: 2 * x + 1,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+
+    Ok(board)
+}
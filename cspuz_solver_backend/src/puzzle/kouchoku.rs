@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::kouchoku;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_kouchoku(url: &str) -> Result<Board, &'static str> {
-    let problem = kouchoku::deserialize_problem(url).ok_or("invalid url")?;
-    let (fixed_lines, undet_lines) = kouchoku::solve_kouchoku(&problem).ok_or("no answer")?;
+pub fn solve_kouchoku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = kouchoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (fixed_lines, undet_lines) =
+        kouchoku::solve_kouchoku(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
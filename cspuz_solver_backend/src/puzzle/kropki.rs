@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::kropki::{self, KropkiClue};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_kropki(url: &str) -> Result<Board, &'static str> {
-    let problem = kropki::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = kropki::solve_kropki(&problem).ok_or("no answer")?;
+pub fn solve_kropki(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = kropki::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = kropki::solve_kropki(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::kurotto;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_kurotto(url: &str) -> Result<Board, &'static str> {
-    let problem = kurotto::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = kurotto::solve_kurotto(&problem).ok_or("no answer")?;
+pub fn solve_kurotto(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = kurotto::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = kurotto::solve_kurotto(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
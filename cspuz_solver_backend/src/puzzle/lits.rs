@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::lits;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_lits(url: &str) -> Result<Board, &'static str> {
-    let borders = lits::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = lits::solve_lits(&borders).ok_or("no answer")?;
+pub fn solve_lits(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let borders = lits::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black = lits::solve_lits(&borders, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::loop_special::{self, LoopSpecialClue};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_loop_speical(url: &str) -> Result<Board, &'static str> {
-    let problem = loop_special::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = loop_special::solve_loop_special(&problem).ok_or("no answer")?;
+pub fn solve_loop_speical(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = loop_special::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line =
+        loop_special::solve_loop_special(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,11 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::masyu;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_masyu(url: &str) -> Result<Board, &'static str> {
+pub fn solve_masyu(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
     use masyu::MasyuClue;
 
-    let problem = masyu::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = masyu::solve_masyu(&problem).ok_or("no answer")?;
+    let problem = masyu::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = masyu::solve_masyu(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
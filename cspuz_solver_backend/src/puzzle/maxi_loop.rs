@@ -0,0 +1,28 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs::puzzle::maxi_loop;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_maxi_loop(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) = maxi_loop::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line =
+        maxi_loop::solve_maxi_loop(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let (height, width) = borders.base_shape();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    board.add_borders(&borders, "black");
+
+    let rooms = graph::borders_to_rooms(&borders);
+    assert_eq!(rooms.len(), clues.len());
+    for i in 0..rooms.len() {
+        if let Some(n) = clues[i] {
+            let (y, x) = rooms[i][0];
+            board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
@@ -1,50 +1,82 @@
 pub mod akari;
 pub mod aqre;
+pub mod aquarium;
 pub mod araf;
+pub mod balance_loop;
 pub mod barns;
+pub mod canal_view;
 pub mod castle_wall;
 pub mod cave;
+pub mod chained_block;
 pub mod chocobanana;
 pub mod cocktail;
 pub mod compass;
 pub mod coral;
+pub mod country_road;
 pub mod creek;
 pub mod crosswall;
 pub mod curvedata;
+pub mod detour;
+pub mod dominosa;
+pub mod dosun_fuwari;
+pub mod double_back;
 pub mod fillomino;
 pub mod fivecells;
+pub mod geradeweg;
+pub mod haisu;
 pub mod hashi;
 pub mod herugolf;
 pub mod heyawake;
+pub mod hitori;
 pub mod icewalk;
+pub mod kakurasu;
+pub mod kakuro;
+pub mod killer_sudoku;
 pub mod kouchoku;
 pub mod kropki;
+pub mod kuromasu;
 pub mod kurotto;
 pub mod lits;
 pub mod lohkous;
 pub mod loop_special;
 pub mod masyu;
+pub mod maxi_loop;
+pub mod mochikoro;
+pub mod mochinyoro;
 pub mod moonsun;
 pub mod nagenawa;
+pub mod nanro;
+pub mod nonogram;
 pub mod norinori;
+pub mod numberlink;
 pub mod nurikabe;
+pub mod nurimaze;
 pub mod nurimisaki;
 pub mod parrot_loop;
 pub mod pencils;
 pub mod reflect;
 pub mod ringring;
+pub mod sandwich_sudoku;
 pub mod sashigane;
 pub mod shakashaka;
 pub mod shikaku;
 pub mod shimaguni;
 pub mod simpleloop;
+pub mod skyscrapers;
 pub mod slalom;
 pub mod slashpack;
 pub mod slitherlink;
+pub mod spiral_galaxies;
 pub mod square_jam;
+pub mod statue_park;
 pub mod stostone;
 pub mod sudoku;
 pub mod tapa;
+pub mod tatamibari;
+pub mod tents;
+pub mod thermo_sudoku;
 pub mod tricklayer;
 pub mod yajilin;
 pub mod yajilin_regions;
+pub mod yajisan_kazusan;
+pub mod yin_yang;
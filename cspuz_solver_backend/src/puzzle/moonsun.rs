@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::moonsun;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_moonsun(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = moonsun::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = moonsun::solve_moonsun(&borders, &clues).ok_or("no answer")?;
+pub fn solve_moonsun(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) = moonsun::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = moonsun::solve_moonsun(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -1,10 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs::puzzle::nagenawa;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_nagenawa(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = nagenawa::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = nagenawa::solve_nagenawa(&borders, &clues).ok_or("no answer")?;
+pub fn solve_nagenawa(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) = nagenawa::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line =
+        nagenawa::solve_nagenawa(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_line.horizontal.len();
     let width = is_line.horizontal[0].len() + 1;
@@ -0,0 +1,36 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::nanro;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_nanro(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) = nanro::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = nanro::solve_nanro(&borders, &clues, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = clues.len();
+    let width = clues[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = clues[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+            } else if let Some(n) = ans[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if n > 0 {
+                        ItemKind::Num(n)
+                    } else {
+                        ItemKind::Dot
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -0,0 +1,45 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::nonogram;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_nonogram(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (row_clues, column_clues) =
+        nonogram::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = nonogram::solve_nonogram(&row_clues, &column_clues, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    // the clues are shown to the left of and above the grid
+    let offset_y = column_clues.iter().map(|c| c.len()).max().unwrap_or(0);
+    let offset_x = row_clues.iter().map(|c| c.len()).max().unwrap_or(0);
+    let height = row_clues.len();
+    let width = column_clues.len();
+    let mut board = Board::new(BoardKind::Grid, height + offset_y, width + offset_x);
+
+    for (y, clue) in row_clues.iter().enumerate() {
+        for (i, &n) in clue.iter().enumerate() {
+            let x = offset_x - clue.len() + i;
+            board.push(Item::cell(y + offset_y, x, "black", ItemKind::Num(n)));
+        }
+    }
+    for (x, clue) in column_clues.iter().enumerate() {
+        for (i, &n) in clue.iter().enumerate() {
+            let y = offset_y - clue.len() + i;
+            board.push(Item::cell(y, x + offset_x, "black", ItemKind::Num(n)));
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(a) = ans[y][x] {
+                board.push(Item::cell(
+                    y + offset_y,
+                    x + offset_x,
+                    "green",
+                    if a { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
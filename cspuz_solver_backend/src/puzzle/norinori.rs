@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::norinori;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_norinori(url: &str) -> Result<Board, &'static str> {
-    let borders = norinori::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = norinori::solve_norinori(&borders).ok_or("no answer")?;
+pub fn solve_norinori(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let borders = norinori::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black = norinori::solve_norinori(&borders, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -0,0 +1,63 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::numberlink;
+use cspuz_rs::solver::SolveContext;
+
+const PATH_COLORS: [&str; 6] = [
+    "#cc3333", "#3333cc", "#339933", "#cc9933", "#9933cc", "#33cccc",
+];
+
+fn path_color(color: Option<i32>) -> &'static str {
+    match color {
+        Some(c) if c > 0 => PATH_COLORS[(c - 1) as usize % PATH_COLORS.len()],
+        _ => "green",
+    }
+}
+
+pub fn solve_numberlink(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = numberlink::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (is_line, color) =
+        numberlink::solve_numberlink(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(clue) = problem[y][x] {
+                if clue > 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
+                } else {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                }
+            }
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if y < height - 1 {
+                if let Some(b) = is_line.vertical[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 2,
+                        x: x * 2 + 1,
+                        color: if b { path_color(color[y][x]) } else { "green" },
+                        kind: if b { ItemKind::Line } else { ItemKind::Cross },
+                    });
+                }
+            }
+            if x < width - 1 {
+                if let Some(b) = is_line.horizontal[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 1,
+                        x: x * 2 + 2,
+                        color: if b { path_color(color[y][x]) } else { "green" },
+                        kind: if b { ItemKind::Line } else { ItemKind::Cross },
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::enumeration::{AnswerKey, EnumerablePuzzle};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::nurikabe;
+use cspuz_rs::solver::{SolveContext, Solver};
 
-pub fn solve_nurikabe(url: &str) -> Result<Board, &'static str> {
-    let problem = nurikabe::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = nurikabe::solve_nurikabe(&problem).ok_or("no answer")?;
+pub fn solve_nurikabe(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = nurikabe::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = nurikabe::solve_nurikabe(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -29,3 +32,42 @@ pub fn solve_nurikabe(url: &str) -> Result<Board, &'static str> {
 
     Ok(board)
 }
+
+pub struct Nurikabe;
+
+impl EnumerablePuzzle for Nurikabe {
+    fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError> {
+        let problem = nurikabe::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+
+        let height = problem.len();
+        let width = problem[0].len();
+
+        let mut solver = Solver::new();
+        let is_black = &solver.bool_var_2d((height, width));
+        nurikabe::add_constraints(&mut solver, is_black, &problem);
+
+        let mut board = Board::new(BoardKind::Grid, height, width);
+        let mut keys = vec![];
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(clue) = problem[y][x] {
+                    if clue > 0 {
+                        board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
+                    } else {
+                        board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                    }
+                } else {
+                    keys.push(AnswerKey::cell(y, x, is_black.at((y, x)), |b| {
+                        if b {
+                            ItemKind::Block
+                        } else {
+                            ItemKind::Dot
+                        }
+                    }));
+                }
+            }
+        }
+
+        Ok((solver, board, keys))
+    }
+}
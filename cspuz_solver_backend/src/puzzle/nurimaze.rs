@@ -0,0 +1,45 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::nurimaze::{self, NurimazeMarker};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_nurimaze(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, markers) = nurimaze::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black =
+        nurimaze::solve_nurimaze(&borders, &markers, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let (height, width) = borders.base_shape();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            match markers[y][x] {
+                Some(NurimazeMarker::Start) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("S")))
+                }
+                Some(NurimazeMarker::Goal) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("G")))
+                }
+                Some(NurimazeMarker::Circle) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Circle))
+                }
+                Some(NurimazeMarker::Triangle) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("△")))
+                }
+                None => {
+                    if let Some(b) = is_black[y][x] {
+                        board.push(Item::cell(
+                            y,
+                            x,
+                            "green",
+                            if b { ItemKind::Block } else { ItemKind::Dot },
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(board)
+}
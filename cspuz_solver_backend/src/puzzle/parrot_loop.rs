@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::parrot_loop;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_parrot_loop(url: &str) -> Result<Board, &'static str> {
-    let problem = parrot_loop::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = parrot_loop::solve_parrot_loop(&problem).ok_or("no answer")?;
+pub fn solve_parrot_loop(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = parrot_loop::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = parrot_loop::solve_parrot_loop(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
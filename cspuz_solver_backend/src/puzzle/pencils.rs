@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::pencils::{self, PencilsAnswer, PencilsClue};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_pencils(url: &str) -> Result<Board, &'static str> {
-    let problem = pencils::deserialize_problem(url).ok_or("invalid url")?;
-    let (cell, line, border) = pencils::solve_pencils(&problem).ok_or("no answer")?;
+pub fn solve_pencils(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = pencils::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (cell, line, border) =
+        pencils::solve_pencils(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::reflect::{self, ReflectLinkClue};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_reflect_link(url: &str) -> Result<Board, &'static str> {
-    let problem = reflect::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = reflect::solve_reflect_link(&problem).ok_or("no answer")?;
+pub fn solve_reflect_link(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = reflect::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = reflect::solve_reflect_link(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
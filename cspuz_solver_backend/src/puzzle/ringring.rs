@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::ringring;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_ringring(url: &str) -> Result<Board, &'static str> {
-    let problem = ringring::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = ringring::solve_ringring(&problem).ok_or("no answer")?;
+pub fn solve_ringring(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = ringring::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = ringring::solve_ringring(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -0,0 +1,61 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::{sandwich_sudoku, sudoku};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_sandwich_sudoku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let ((clue_vertical, clue_horizontal), clues) =
+        sandwich_sudoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = sandwich_sudoku::solve_sandwich_sudoku(&clues, &clue_vertical, &clue_horizontal, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    // the sandwich clues are shown above and to the left of the grid
+    let height = ans.len();
+    let width = ans[0].len();
+    let (bh, bw) = sudoku::block_shape(height).ok_or(SolveError::ParseFailed {
+        detail: "invalid size",
+    })?;
+    let mut board = Board::new(BoardKind::Grid, height + 1, width + 1);
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(0, x + 1, "black", ItemKind::Num(n)));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(y + 1, 0, "black", ItemKind::Num(n)));
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = clues[y][x] {
+                board.push(Item::cell(y + 1, x + 1, "black", ItemKind::Num(n)));
+            } else if let Some(n) = ans[y][x] {
+                board.push(Item::cell(y + 1, x + 1, "green", ItemKind::Num(n)));
+            }
+        }
+    }
+    for x in 0..=bh {
+        for y in 0..height {
+            board.push(Item {
+                y: 2 * y + 3,
+                x: 2 * x * bw + 2,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+    for y in 0..=bw {
+        for x in 0..width {
+            board.push(Item {
+                y: 2 * y * bh + 2,
+                x: 2 * x + 3,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+
+    Ok(board)
+}
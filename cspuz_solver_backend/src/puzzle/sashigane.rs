@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::sasahigane::{self, SashiganeClue};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_sashigane(url: &str) -> Result<Board, &'static str> {
-    let problem = sasahigane::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = sasahigane::solve_sashigane(&problem).ok_or("no answer")?;
+pub fn solve_sashigane(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = sasahigane::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = sasahigane::solve_sashigane(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
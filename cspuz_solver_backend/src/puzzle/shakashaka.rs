@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::shakashaka::{self, ShakashakaCell};
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_shakashaka(url: &str) -> Result<Board, &'static str> {
-    let problem = shakashaka::deserialize_problem(url).ok_or("invalid url")?;
-    let answer = shakashaka::solve_shakashaka(&problem).ok_or("no answer")?;
+pub fn solve_shakashaka(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = shakashaka::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let answer = shakashaka::solve_shakashaka(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
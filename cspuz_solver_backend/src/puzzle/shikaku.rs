@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::shikaku;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_shikaku(url: &str) -> Result<Board, &'static str> {
-    let problem = shikaku::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = shikaku::solve_shikaku(&problem).ok_or("no answer")?;
+pub fn solve_shikaku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = shikaku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = shikaku::solve_shikaku(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -0,0 +1,37 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::skyscrapers;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_skyscrapers(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let clues = skyscrapers::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = skyscrapers::solve_skyscrapers(&clues, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    // the clues are shown around the grid
+    let n = clues.up.len();
+    let mut board = Board::new(BoardKind::Grid, n + 2, n + 2);
+
+    for i in 0..n {
+        if let Some(k) = clues.up[i] {
+            board.push(Item::cell(0, i + 1, "black", ItemKind::Num(k)));
+        }
+        if let Some(k) = clues.down[i] {
+            board.push(Item::cell(n + 1, i + 1, "black", ItemKind::Num(k)));
+        }
+        if let Some(k) = clues.left[i] {
+            board.push(Item::cell(i + 1, 0, "black", ItemKind::Num(k)));
+        }
+        if let Some(k) = clues.right[i] {
+            board.push(Item::cell(i + 1, n + 1, "black", ItemKind::Num(k)));
+        }
+    }
+    for y in 0..n {
+        for x in 0..n {
+            if let Some(k) = ans[y][x] {
+                board.push(Item::cell(y + 1, x + 1, "green", ItemKind::Num(k)));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,12 +1,15 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::slalom;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_slalom(url: &str) -> Result<Board, &'static str> {
+pub fn solve_slalom(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
     use slalom::{SlalomBlackCellDir, SlalomCell};
 
-    let problem = slalom::deserialize_problem_as_primitive(url).ok_or("invalid url")?;
+    let problem = slalom::deserialize_problem_as_primitive(url).ok_or(SolveError::INVALID_URL)?;
     let (is_black, gates, origin) = slalom::parse_primitive_problem(&problem);
-    let is_line = slalom::solve_slalom(origin, &is_black, &gates).ok_or("no answer")?;
+    let is_line =
+        slalom::solve_slalom(origin, &is_black, &gates, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
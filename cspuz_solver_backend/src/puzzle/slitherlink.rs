@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::slitherlink;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_slitherlink(url: &str) -> Result<Board, &'static str> {
-    let problem = slitherlink::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = slitherlink::solve_slitherlink(&problem).ok_or("no answer")?;
+pub fn solve_slitherlink(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = slitherlink::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_line = slitherlink::solve_slitherlink(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
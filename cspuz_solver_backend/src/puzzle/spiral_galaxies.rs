@@ -0,0 +1,86 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::spiral_galaxies;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_spiral_galaxies(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = spiral_galaxies::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let border =
+        spiral_galaxies::solve_spiral_galaxies(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len().div_ceil(2);
+    let width = problem[0].len().div_ceil(2);
+    let mut board = Board::new(BoardKind::OuterGrid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if y < height - 1 {
+                let mut need_default_edge = true;
+                if let Some(b) = border.horizontal[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 2,
+                        x: x * 2 + 1,
+                        color: "green",
+                        kind: if b {
+                            ItemKind::BoldWall
+                        } else {
+                            ItemKind::Cross
+                        },
+                    });
+                    if b {
+                        need_default_edge = false;
+                    }
+                }
+                if need_default_edge {
+                    board.push(Item {
+                        y: y * 2 + 2,
+                        x: x * 2 + 1,
+                        color: "#cccccc",
+                        kind: ItemKind::Wall,
+                    });
+                }
+            }
+            if x < width - 1 {
+                let mut need_default_edge = true;
+                if let Some(b) = border.vertical[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 1,
+                        x: x * 2 + 2,
+                        color: "green",
+                        kind: if b {
+                            ItemKind::BoldWall
+                        } else {
+                            ItemKind::Cross
+                        },
+                    });
+                    if b {
+                        need_default_edge = false;
+                    }
+                }
+                if need_default_edge {
+                    board.push(Item {
+                        y: y * 2 + 1,
+                        x: x * 2 + 2,
+                        color: "#cccccc",
+                        kind: ItemKind::Wall,
+                    });
+                }
+            }
+        }
+    }
+
+    for (y, row) in problem.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if c {
+                board.push(Item {
+                    y: y + 1,
+                    x: x + 1,
+                    color: "black",
+                    kind: ItemKind::SmallCircle,
+                });
+            }
+        }
+    }
+
+    Ok(board)
+}
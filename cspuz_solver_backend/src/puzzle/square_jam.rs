@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::square_jam;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_square_jam(url: &str) -> Result<Board, &'static str> {
-    let problem = square_jam::deserialize_problem(url).ok_or("invalid url")?;
-    let border = square_jam::solve_square_jam(&problem).ok_or("no answer")?;
+pub fn solve_square_jam(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = square_jam::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let border = square_jam::solve_square_jam(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -0,0 +1,39 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::statue_park::{self, StatueParkClue};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_statue_park(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (clues, (pieces, allow_transform)) =
+        statue_park::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black = statue_park::solve_statue_park(&clues, &pieces, allow_transform, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let height = clues.len();
+    let width = clues[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(b) = is_black[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+            match clues[y][x] {
+                Some(StatueParkClue::Black) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::FilledCircle))
+                }
+                Some(StatueParkClue::White) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Circle))
+                }
+                None => (),
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,9 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::enumeration::{AnswerKey, EnumerablePuzzle};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::sudoku;
+use cspuz_rs::solver::{SolveContext, Solver};
 
-pub fn solve_sudoku(url: &str) -> Result<Board, &'static str> {
-    let problem = sudoku::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = sudoku::solve_sudoku_as_cands(&problem).ok_or("no answer")?;
+pub fn solve_sudoku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = sudoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = sudoku::solve_sudoku_as_cands(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -15,7 +18,11 @@ pub fn solve_sudoku(url: &str) -> Result<Board, &'static str> {
         9 => (3, 3),
         16 => (4, 4),
         25 => (5, 5),
-        _ => return Err("invalid size"),
+        _ => {
+            return Err(SolveError::ParseFailed {
+                detail: "invalid size",
+            })
+        }
     };
 
     for y in 0..height {
@@ -65,3 +72,59 @@ pub fn solve_sudoku(url: &str) -> Result<Board, &'static str> {
 
     Ok(board)
 }
+
+pub struct Sudoku;
+
+impl EnumerablePuzzle for Sudoku {
+    fn build(url: &str) -> Result<(Solver<'static>, Board, Vec<AnswerKey>), SolveError> {
+        const INVALID_SIZE: SolveError = SolveError::ParseFailed {
+            detail: "invalid size",
+        };
+        let problem = sudoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+
+        let height = problem.len();
+        let width = problem[0].len();
+        if height != width {
+            return Err(INVALID_SIZE);
+        }
+        let (bh, bw) = sudoku::block_shape(height).ok_or(INVALID_SIZE)?;
+
+        let mut solver = Solver::new();
+        let num = &solver.int_var_2d((height, width), 1, height as i32);
+        sudoku::add_constraints(&mut solver, num, &problem, (bh, bw));
+
+        let mut board = Board::new(BoardKind::Grid, height, width);
+        let mut keys = vec![];
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(n) = problem[y][x] {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+                } else {
+                    keys.push(AnswerKey::int_cell(y, x, num.at((y, x)), ItemKind::Num));
+                }
+            }
+        }
+        for x in 0..bh {
+            for y in 0..height {
+                board.push(Item {
+                    y: 2 * y + 1,
+                    x: 2 * x * bw,
+                    color: "black",
+                    kind: ItemKind::BoldWall,
+                });
+            }
+        }
+        for y in 0..bw {
+            for x in 0..width {
+                board.push(Item {
+                    y: 2 * y * bh,
+                    x: 2 * x + 1,
+                    color: "black",
+                    kind: ItemKind::BoldWall,
+                });
+            }
+        }
+
+        Ok((solver, board, keys))
+    }
+}
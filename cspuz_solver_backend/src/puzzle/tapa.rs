@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::tapa;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_tapa(url: &str) -> Result<Board, &'static str> {
-    let problem = tapa::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = tapa::solve_tapa(&problem).ok_or("no answer")?;
+pub fn solve_tapa(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = tapa::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = tapa::solve_tapa(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
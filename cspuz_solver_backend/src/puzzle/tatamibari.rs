@@ -0,0 +1,83 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::tatamibari::{self, TatamibariClue};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_tatamibari(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = tatamibari::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = tatamibari::solve_tatamibari(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::OuterGrid, height, width);
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(clue) = problem[y][x] {
+                let text = match clue {
+                    TatamibariClue::Square => "+",
+                    TatamibariClue::Horizontal => "-",
+                    TatamibariClue::Vertical => "|",
+                };
+                board.push(Item::cell(y, x, "black", ItemKind::Text(text)));
+            }
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if y < height - 1 {
+                let mut need_default_edge = true;
+                if let Some(b) = ans.horizontal[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 2,
+                        x: x * 2 + 1,
+                        color: "green",
+                        kind: if b {
+                            ItemKind::BoldWall
+                        } else {
+                            ItemKind::Cross
+                        },
+                    });
+                    if b {
+                        need_default_edge = false;
+                    }
+                }
+                if need_default_edge {
+                    board.push(Item {
+                        y: y * 2 + 2,
+                        x: x * 2 + 1,
+                        color: "#cccccc",
+                        kind: ItemKind::Wall,
+                    });
+                }
+            }
+            if x < width - 1 {
+                let mut need_default_edge = true;
+                if let Some(b) = ans.vertical[y][x] {
+                    board.push(Item {
+                        y: y * 2 + 1,
+                        x: x * 2 + 2,
+                        color: "green",
+                        kind: if b {
+                            ItemKind::BoldWall
+                        } else {
+                            ItemKind::Cross
+                        },
+                    });
+                    if b {
+                        need_default_edge = false;
+                    }
+                }
+                if need_default_edge {
+                    board.push(Item {
+                        y: y * 2 + 1,
+                        x: x * 2 + 2,
+                        color: "#cccccc",
+                        kind: ItemKind::Wall,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -0,0 +1,43 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::tents;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_tents(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let ((clue_vertical, clue_horizontal), is_tree) =
+        tents::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_tent = tents::solve_tents(&is_tree, &clue_vertical, &clue_horizontal, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    // the clues are shown above and to the left of the grid
+    let height = is_tree.len();
+    let width = is_tree[0].len();
+    let mut board = Board::new(BoardKind::Grid, height + 1, width + 1);
+
+    for (x, &clue) in clue_vertical.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(0, x + 1, "black", ItemKind::Num(n)));
+        }
+    }
+    for (y, &clue) in clue_horizontal.iter().enumerate() {
+        if let Some(n) = clue {
+            board.push(Item::cell(y + 1, 0, "black", ItemKind::Num(n)));
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if is_tree[y][x] {
+                board.push(Item::cell(y + 1, x + 1, "#88cc88", ItemKind::Fill));
+            } else if let Some(b) = is_tent[y][x] {
+                board.push(Item::cell(
+                    y + 1,
+                    x + 1,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -0,0 +1,64 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::{sudoku, thermo_sudoku};
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_thermo_sudoku(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (clues, thermometers) =
+        thermo_sudoku::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = thermo_sudoku::solve_thermo_sudoku(&clues, &thermometers, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
+
+    let height = ans.len();
+    let width = ans[0].len();
+    let (bh, bw) = sudoku::block_shape(height).ok_or(SolveError::ParseFailed {
+        detail: "invalid size",
+    })?;
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for thermometer in &thermometers {
+        let (y, x) = thermometer[0];
+        board.push(Item::cell(y, x, "#cccccc", ItemKind::FilledCircle));
+        for i in 1..thermometer.len() {
+            let (y1, x1) = thermometer[i - 1];
+            let (y2, x2) = thermometer[i];
+            board.push(Item {
+                y: y1 * 2 + 1,
+                x: x1 * 2 + 1,
+                color: "#cccccc",
+                kind: ItemKind::LineTo(y2 as i32 * 2 + 1, x2 as i32 * 2 + 1),
+            });
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = clues[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+            } else if let Some(n) = ans[y][x] {
+                board.push(Item::cell(y, x, "green", ItemKind::Num(n)));
+            }
+        }
+    }
+    for x in 0..bh {
+        for y in 0..height {
+            board.push(Item {
+                y: 2 * y + 1,
+                x: 2 * x * bw,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+    for y in 0..bw {
+        for x in 0..width {
+            board.push(Item {
+                y: 2 * y * bh,
+                x: 2 * x + 1,
+                color: "black",
+                kind: ItemKind::BoldWall,
+            });
+        }
+    }
+
+    Ok(board)
+}
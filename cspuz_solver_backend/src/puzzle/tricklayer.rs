@@ -1,9 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::tricklayer;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_tricklayer(url: &str) -> Result<Board, &'static str> {
-    let problem = tricklayer::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = tricklayer::solve_tricklayer(&problem).ok_or("no answer")?;
+pub fn solve_tricklayer(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let problem = tricklayer::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = tricklayer::solve_tricklayer(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
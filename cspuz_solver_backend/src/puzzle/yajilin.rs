@@ -1,11 +1,14 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::puzzle::yajilin;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_yajilin(url: &str) -> Result<Board, &'static str> {
+pub fn solve_yajilin(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
     use cspuz_rs::items::Arrow;
 
-    let problem = yajilin::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) = yajilin::solve_yajilin(&problem).ok_or("no answer")?;
+    let problem = yajilin::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (is_line, is_black) =
+        yajilin::solve_yajilin(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
 
     let height = problem.len();
     let width = problem[0].len();
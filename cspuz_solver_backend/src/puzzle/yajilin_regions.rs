@@ -1,11 +1,14 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs::puzzle::yajilin_regions;
+use cspuz_rs::solver::SolveContext;
 
-pub fn solve_yajilin_regions(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = yajilin_regions::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) =
-        yajilin_regions::solve_yajilin_regions(&borders, &clues).ok_or("no answer")?;
+pub fn solve_yajilin_regions(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    let (borders, clues) =
+        yajilin_regions::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let (is_line, is_black) = yajilin_regions::solve_yajilin_regions(&borders, &clues, ctx)
+        .ok_or(SolveError::Unsatisfiable)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
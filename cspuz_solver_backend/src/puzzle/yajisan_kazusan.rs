@@ -0,0 +1,57 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::yajisan_kazusan;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_yajisan_kazusan(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    use cspuz_rs::items::Arrow;
+
+    let problem = yajisan_kazusan::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let is_black =
+        yajisan_kazusan::solve_yajisan_kazusan(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(clue) = problem[y][x] {
+                // the clue stays visible when its cell is shaded
+                if is_black[y][x] == Some(true) {
+                    board.push(Item::cell(y, x, "#88cc88", ItemKind::Fill));
+                }
+                let arrow = match clue.0 {
+                    Arrow::Unspecified => None,
+                    Arrow::Up => Some(ItemKind::SideArrowUp),
+                    Arrow::Down => Some(ItemKind::SideArrowDown),
+                    Arrow::Left => Some(ItemKind::SideArrowLeft),
+                    Arrow::Right => Some(ItemKind::SideArrowRight),
+                };
+                let n = clue.1;
+                if let Some(arrow) = arrow {
+                    board.push(Item::cell(y, x, "black", arrow));
+                }
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "black",
+                    if n >= 0 {
+                        ItemKind::Num(n)
+                    } else {
+                        ItemKind::Text("?")
+                    },
+                ));
+            } else if let Some(b) = is_black[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
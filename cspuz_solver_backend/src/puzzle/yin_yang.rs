@@ -0,0 +1,39 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::error::SolveError;
+use cspuz_rs::puzzle::yin_yang;
+use cspuz_rs::solver::SolveContext;
+
+pub fn solve_yin_yang(url: &str, ctx: &mut SolveContext) -> Result<Board, SolveError> {
+    use yin_yang::YinYangClue;
+
+    let problem = yin_yang::deserialize_problem(url).ok_or(SolveError::INVALID_URL)?;
+    let ans = yin_yang::solve_yin_yang(&problem, ctx).ok_or(SolveError::Unsatisfiable)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width);
+    for y in 0..height {
+        for x in 0..width {
+            match problem[y][x] {
+                YinYangClue::White => board.push(Item::cell(y, x, "black", ItemKind::Circle)),
+                YinYangClue::Black => board.push(Item::cell(y, x, "black", ItemKind::FilledCircle)),
+                YinYangClue::None => {
+                    if let Some(a) = ans[y][x] {
+                        board.push(Item::cell(
+                            y,
+                            x,
+                            "green",
+                            if a {
+                                ItemKind::FilledCircle
+                            } else {
+                                ItemKind::Circle
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(board)
+}
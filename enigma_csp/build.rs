@@ -23,6 +23,11 @@ fn build_glucose() {
     #[cfg(feature = "csp-extra-constraints")]
     let puzzle_solver_minimal_flag = "-DPUZZLE_SOLVER_MINIMAL=0";
 
+    #[cfg(not(feature = "drat"))]
+    let drat_flag = "-DENIGMA_CSP_DRAT=0";
+    #[cfg(feature = "drat")]
+    let drat_flag = "-DENIGMA_CSP_DRAT=1";
+
     if arch == "wasm32" {
         // TODO: enigma_csp can be built for wasm32-unknown-emscripten target, but the produced
         // library is hard to use (only a .wasm file is generated without any glue .js files).
@@ -45,6 +50,7 @@ fn build_glucose() {
             .include("lib/glucose")
             .flag("-std=c++17")
             .flag(puzzle_solver_minimal_flag)
+            .flag(drat_flag)
             .warnings(false)
             .compile("calc");
     }
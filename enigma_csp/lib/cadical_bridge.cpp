@@ -36,11 +36,29 @@ int32_t CaDiCaL_Solve(CaDiCaL::Solver* solver) {
     return 0;
 }
 
+int32_t CaDiCaL_SolveWithAssumptions(CaDiCaL::Solver* solver, const int32_t* assumptions, int32_t n_assumptions) {
+    // assumptions are reset by CaDiCaL after each call to `solve`
+    for (int i = 0; i < n_assumptions; ++i) {
+        solver->assume(to_cadical_lit(assumptions[i]));
+    }
+    int res = solver->solve();
+    if (res == 10) return 1;
+    return 0;
+}
+
+int32_t CaDiCaL_Failed(CaDiCaL::Solver* solver, int32_t lit) {
+    return solver->failed(to_cadical_lit(lit)) ? 1 : 0;
+}
+
 int32_t CaDiCaL_GetModelValueVar(CaDiCaL::Solver* solver, int32_t var) {
     int res = solver->val(var + 1);
     return (res > 0) ? 1 : 0;
 }
 
+void CaDiCaL_Phase(CaDiCaL::Solver* solver, int32_t lit) {
+    solver->phase(to_cadical_lit(lit));
+}
+
 void CaDiCaL_AddActiveVerticesConnected(CaDiCaL::Solver* solver, int32_t n_vertices, const int32_t* lits, int32_t n_edges, const int32_t* edges) {
     std::vector<int> c_lits;
     for (int i = 0; i < n_vertices; ++i) {
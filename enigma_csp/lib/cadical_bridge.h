@@ -14,7 +14,10 @@ CaDiCaL::Solver* CaDiCaL_CreateSolver();
 void CaDiCaL_DestroySolver(CaDiCaL::Solver* solver);
 void CaDiCaL_AddClause(CaDiCaL::Solver* solver, int32_t* lits, int32_t n_lits);
 int32_t CaDiCaL_Solve(CaDiCaL::Solver* solver);
+int32_t CaDiCaL_SolveWithAssumptions(CaDiCaL::Solver* solver, const int32_t* assumptions, int32_t n_assumptions);
+int32_t CaDiCaL_Failed(CaDiCaL::Solver* solver, int32_t lit);
 int32_t CaDiCaL_GetModelValueVar(CaDiCaL::Solver* solver, int32_t var);
+void CaDiCaL_Phase(CaDiCaL::Solver* solver, int32_t lit);
 void CaDiCaL_AddActiveVerticesConnected(CaDiCaL::Solver* solver, int32_t n_vertices, const int32_t* lits, int32_t n_edges, const int32_t* edges);
 
 }
@@ -6,6 +6,23 @@
 #include "constraints/GraphDivision.h"
 #include "constraints/OrderEncodingLinear.h"
 
+namespace {
+
+// Glucose writes learnt and deleted clauses to `certifiedOutput` while solving, but the empty
+// clause concluding the proof is left to the caller.
+void finish_drat_proof(Glucose::Solver* solver, bool is_sat, bool has_assumptions) {
+#if ENIGMA_CSP_DRAT
+    if (solver->certifiedUNSAT) {
+        if (!is_sat && !has_assumptions) {
+            fprintf(solver->certifiedOutput, "0\n");
+        }
+        fflush(solver->certifiedOutput);
+    }
+#endif
+}
+
+}
+
 extern "C" {
 
 Glucose::Solver* Glucose_CreateSolver() {
@@ -14,6 +31,11 @@ Glucose::Solver* Glucose_CreateSolver() {
 }
 
 void Glucose_DestroySolver(Glucose::Solver* solver) {
+#if ENIGMA_CSP_DRAT
+    if (solver->certifiedUNSAT) {
+        fclose(solver->certifiedOutput);
+    }
+#endif
     delete solver;
 }
 
@@ -35,7 +57,49 @@ int32_t Glucose_AddClause(Glucose::Solver* solver, int32_t* lits, int32_t n_lits
 }
 
 int32_t Glucose_Solve(Glucose::Solver* solver) {
-    return solver->solve();
+    bool res = solver->solve();
+    finish_drat_proof(solver, res, false);
+    return res ? 1 : 0;
+}
+
+int32_t Glucose_SolveWithAssumptions(Glucose::Solver* solver, const int32_t* assumptions, int32_t n_assumptions) {
+    Glucose::vec<Glucose::Lit> assumps;
+    for (int i = 0; i < n_assumptions; ++i) {
+        assumps.push(Glucose::Lit{assumptions[i]});
+    }
+    bool res = solver->solve(assumps);
+    finish_drat_proof(solver, res, n_assumptions > 0);
+    return res ? 1 : 0;
+}
+
+// Returns -1 if the search is stopped by `conflict_budget` (negative for no limit) or
+// an interruption.
+int32_t Glucose_SolveLimited(Glucose::Solver* solver, const int32_t* assumptions, int32_t n_assumptions, int64_t conflict_budget) {
+    Glucose::vec<Glucose::Lit> assumps;
+    for (int i = 0; i < n_assumptions; ++i) {
+        assumps.push(Glucose::Lit{assumptions[i]});
+    }
+    if (conflict_budget >= 0) {
+        solver->setConfBudget(conflict_budget);
+    } else {
+        solver->budgetOff();
+    }
+    Glucose::lbool res = solver->solveLimited(assumps);
+    solver->budgetOff();
+    if (res == l_Undef) {
+        return -1;
+    }
+    finish_drat_proof(solver, res == l_True, n_assumptions > 0);
+    return res == l_True ? 1 : 0;
+}
+
+int32_t Glucose_NumConflictLits(const Glucose::Solver* solver) {
+    return solver->conflict.size();
+}
+
+int32_t Glucose_GetFailedAssumption(const Glucose::Solver* solver, int32_t i) {
+    // `conflict` holds the negations of the failed assumptions
+    return (~solver->conflict[i]).x;
 }
 
 int32_t Glucose_NumVar(const Glucose::Solver* solver) {
@@ -144,6 +208,18 @@ uint64_t Glucose_SolverStats_conflicts(Glucose::Solver* solver) {
     return solver->conflicts;
 }
 
+uint64_t Glucose_SolverStats_restarts(Glucose::Solver* solver) {
+    return solver->starts;
+}
+
+uint64_t Glucose_SolverStats_learnts(Glucose::Solver* solver) {
+    return solver->nLearnts();
+}
+
+uint64_t Glucose_SolverStats_clauses(Glucose::Solver* solver) {
+    return solver->nClauses();
+}
+
 void Glucose_Set_random_seed(Glucose::Solver* solver, double random_seed) {
     solver->random_seed = random_seed;
 }
@@ -156,4 +232,33 @@ void Glucose_Set_dump_analysis_info(Glucose::Solver* solver, int32_t value) {
     solver->dump_analysis_info = value != 0;
 }
 
+void Glucose_SetPolarity(Glucose::Solver* solver, int32_t var, int32_t value) {
+    // `polarity[v] == true` means that `v` is first assigned false
+    solver->setPolarity(var, value == 0);
+}
+
+void Glucose_Interrupt(Glucose::Solver* solver) {
+    solver->interrupt();
+}
+
+void Glucose_ClearInterrupt(Glucose::Solver* solver) {
+    solver->clearInterrupt();
+}
+
+#if ENIGMA_CSP_DRAT
+int32_t Glucose_EnableDrat(Glucose::Solver* solver, const char* path) {
+    FILE* fp = fopen(path, "w");
+    if (fp == nullptr) {
+        return 0;
+    }
+    if (solver->certifiedUNSAT) {
+        fclose(solver->certifiedOutput);
+    }
+    solver->certifiedOutput = fp;
+    solver->certifiedUNSAT = true;
+    solver->vbyte = false;
+    return 1;
+}
+#endif
+
 }
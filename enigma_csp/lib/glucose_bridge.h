@@ -14,6 +14,10 @@ int32_t Glucose_NewVar(Glucose::Solver* solver);
 int32_t Glucose_NewNamedVar(Glucose::Solver* solver, const char* name);
 int32_t Glucose_AddClause(Glucose::Solver* solver, int32_t* lits, int32_t n_lits);
 int32_t Glucose_Solve(Glucose::Solver* solver);
+int32_t Glucose_SolveWithAssumptions(Glucose::Solver* solver, const int32_t* assumptions, int32_t n_assumptions);
+int32_t Glucose_SolveLimited(Glucose::Solver* solver, const int32_t* assumptions, int32_t n_assumptions, int64_t conflict_budget);
+int32_t Glucose_NumConflictLits(const Glucose::Solver* solver);
+int32_t Glucose_GetFailedAssumption(const Glucose::Solver* solver, int32_t i);
 int32_t Glucose_NumVar(const Glucose::Solver* solver);
 int32_t Glucose_GetModelValueVar(const Glucose::Solver* solver, int32_t var);
 int32_t Glucose_AddOrderEncodingLinear(Glucose::Solver* solver, int32_t n_terms, const int32_t* domain_size, const int32_t* lits, const int32_t* domain, const int32_t* coefs, int32_t constant);
@@ -23,5 +27,11 @@ int32_t Glucose_AddGraphDivision(Glucose::Solver* solver, int32_t n_vertices, co
 void Glucose_Set_random_seed(Glucose::Solver* solver, double random_seed);
 void Glucose_Set_rnd_init_act(Glucose::Solver* solver, int32_t rnd_init_act);
 void Glucose_Set_dump_analysis_info(Glucose::Solver* solver, int32_t value);
+void Glucose_SetPolarity(Glucose::Solver* solver, int32_t var, int32_t value);
+void Glucose_Interrupt(Glucose::Solver* solver);
+void Glucose_ClearInterrupt(Glucose::Solver* solver);
+#if ENIGMA_CSP_DRAT
+int32_t Glucose_EnableDrat(Glucose::Solver* solver, const char* path);
+#endif
 
 }
@@ -12,7 +12,14 @@ extern "C" {
     fn CaDiCaL_DestroySolver(solver: *mut Opaque);
     fn CaDiCaL_AddClause(solver: *mut Opaque, lits: *const i32, n_lits: i32);
     fn CaDiCaL_Solve(solver: *mut Opaque) -> i32;
+    fn CaDiCaL_SolveWithAssumptions(
+        solver: *mut Opaque,
+        assumptions: *const i32,
+        n_assumptions: i32,
+    ) -> i32;
+    fn CaDiCaL_Failed(solver: *mut Opaque, lit: i32) -> i32;
     fn CaDiCaL_GetModelValueVar(solver: *mut Opaque, var: i32) -> i32;
+    fn CaDiCaL_Phase(solver: *mut Opaque, lit: i32);
     fn CaDiCaL_AddActiveVerticesConnected(
         solver: *mut Opaque,
         n_vertices: i32,
@@ -25,6 +32,7 @@ extern "C" {
 pub struct Solver {
     ptr: *mut Opaque,
     num_var: i32,
+    last_assumptions: Vec<Lit>,
 }
 
 const NUM_VAR_MAX: i32 = 0x3fffffff;
@@ -34,6 +42,7 @@ impl Solver {
         Solver {
             ptr: unsafe { CaDiCaL_CreateSolver() },
             num_var: 0,
+            last_assumptions: vec![],
         }
     }
 
@@ -61,6 +70,11 @@ impl Solver {
         unsafe { CaDiCaL_AddClause(self.ptr, clause.as_ptr(), clause.len() as i32) };
     }
 
+    /// Makes the solver prefer `lit` to be true in decisions.
+    pub fn set_phase(&mut self, lit: Lit) {
+        unsafe { CaDiCaL_Phase(self.ptr, lit.0) }
+    }
+
     pub fn add_active_vertices_connected(&mut self, lits: &[Lit], edges: &[(usize, usize)]) {
         assert!(lits.len() <= i32::max_value() as usize);
         assert!(edges.len() <= i32::max_value() as usize);
@@ -98,10 +112,40 @@ impl Solver {
     }
 
     pub fn solve_without_model(&mut self) -> bool {
+        self.last_assumptions.clear();
         let res = unsafe { CaDiCaL_Solve(self.ptr) };
         res != 0
     }
 
+    pub fn solve_under_assumptions<'a>(&'a mut self, assumptions: &[Lit]) -> Option<Model<'a>> {
+        if self.solve_without_model_under_assumptions(assumptions) {
+            Some(unsafe { self.model() })
+        } else {
+            None
+        }
+    }
+
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        assert!(assumptions.len() <= i32::MAX as usize);
+        self.last_assumptions = assumptions.to_owned();
+        let assumptions = unsafe { std::mem::transmute::<&[Lit], &[i32]>(assumptions) };
+        for &l in assumptions {
+            assert!(0 <= l && l < 2 * self.num_var);
+        }
+        let res = unsafe {
+            CaDiCaL_SolveWithAssumptions(self.ptr, assumptions.as_ptr(), assumptions.len() as i32)
+        };
+        res != 0
+    }
+
+    pub fn unsat_core(&self) -> Vec<Lit> {
+        self.last_assumptions
+            .iter()
+            .filter(|&&lit| unsafe { CaDiCaL_Failed(self.ptr, lit.0) != 0 })
+            .copied()
+            .collect()
+    }
+
     pub(crate) unsafe fn model<'a>(&'a self) -> Model<'a> {
         Model { solver: self }
     }
@@ -1,20 +1,28 @@
-use std::io::{Read, Write};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::sat::{Lit, Var};
 
+static CNF_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 pub struct Solver {
+    path: String,
+    args: Vec<String>,
     num_vars: i32,
     clauses: Vec<Vec<Lit>>,
     model: Vec<bool>,
+    last_assumptions: Vec<Lit>,
 }
 
 impl Solver {
-    pub fn new() -> Solver {
+    pub fn new(path: &str, args: &[String]) -> Solver {
         Solver {
+            path: path.to_owned(),
+            args: args.to_owned(),
             num_vars: 0,
             clauses: vec![],
             model: vec![],
+            last_assumptions: vec![],
         }
     }
 
@@ -45,10 +53,15 @@ impl Solver {
         }
     }
 
-    pub fn solve_without_model(&mut self) -> bool {
+    fn dimacs_with_units(&self, units: &[Lit]) -> String {
         let mut description = String::new();
-        description.push_str(&format!("p cnf {} {}\n", self.num_vars, self.clauses.len()));
-        for clause in &self.clauses {
+        description.push_str(&format!(
+            "p cnf {} {}\n",
+            self.num_vars,
+            self.clauses.len() + units.len()
+        ));
+        let units = units.iter().map(std::slice::from_ref);
+        for clause in self.clauses.iter().map(|c| &c[..]).chain(units) {
             for l in clause {
                 let n = (l.var().0 + 1) * if l.is_negated() { -1 } else { 1 };
                 description.push_str(&(n.to_string()));
@@ -56,22 +69,44 @@ impl Solver {
             }
             description.push_str("0\n");
         }
+        description
+    }
 
-        let solver_command = std::env::var("ENIGMA_CSP_EXTERNAL_SOLVER").unwrap();
-        let process = Command::new(solver_command)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .spawn()
-            .unwrap();
+    pub fn solve_without_model(&mut self) -> bool {
+        self.solve_without_model_under_assumptions(&[])
+    }
 
-        process
-            .stdin
-            .unwrap()
-            .write_all(description.as_bytes())
-            .unwrap();
+    pub fn solve_under_assumptions<'a>(&'a mut self, assumptions: &[Lit]) -> Option<Model<'a>> {
+        if self.solve_without_model_under_assumptions(assumptions) {
+            Some(unsafe { self.model() })
+        } else {
+            None
+        }
+    }
 
-        let mut output = String::new();
-        process.stdout.unwrap().read_to_string(&mut output).unwrap();
+    /// Assumptions are passed to the external solver as unit clauses, which are not kept for
+    /// later calls.
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        self.last_assumptions = assumptions.to_owned();
+        let description = self.dimacs_with_units(assumptions);
+
+        let cnf_path = std::env::temp_dir().join(format!(
+            "enigma_csp_{}_{}.cnf",
+            std::process::id(),
+            CNF_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&cnf_path, description).unwrap();
+        let output = Command::new(&self.path)
+            .args(&self.args)
+            .arg(&cnf_path)
+            .stdin(Stdio::null())
+            .output();
+        let _ = std::fs::remove_file(&cnf_path);
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => panic!("failed to run external SAT solver {}: {}", self.path, e),
+        };
+        let output = String::from_utf8_lossy(&output.stdout);
 
         let mut is_sat: Option<bool> = None;
         let mut model = vec![false; self.num_vars as usize];
@@ -83,10 +118,10 @@ impl Solver {
                 } else if line.starts_with("s SAT") {
                     is_sat = Some(true);
                 } else {
-                    panic!();
+                    panic!("unexpected result from external SAT solver: {}", line);
                 }
             } else if line.starts_with("v ") {
-                for toks in line.split(' ').skip(1) {
+                for toks in line.split_whitespace().skip(1) {
                     let n = toks.parse::<i32>().unwrap();
                     if n == 0 {
                         break;
@@ -102,7 +137,13 @@ impl Solver {
             self.model = model;
         }
 
-        is_sat.unwrap()
+        is_sat.expect("external SAT solver did not report the result")
+    }
+
+    /// External solvers do not report failed assumptions, so all the assumptions of the last call
+    /// are returned as a (trivial) core.
+    pub fn unsat_core(&self) -> Vec<Lit> {
+        self.last_assumptions.clone()
     }
 
     pub(crate) unsafe fn model<'a>(&'a self) -> Model<'a> {
@@ -16,6 +16,19 @@ extern "C" {
     fn Glucose_NewNamedVar(solver: *mut Opaque, name: *const c_char) -> i32;
     fn Glucose_AddClause(solver: *mut Opaque, lits: *const Lit, n_lits: i32) -> i32;
     fn Glucose_Solve(solver: *mut Opaque) -> i32;
+    fn Glucose_SolveWithAssumptions(
+        solver: *mut Opaque,
+        assumptions: *const Lit,
+        n_assumptions: i32,
+    ) -> i32;
+    fn Glucose_SolveLimited(
+        solver: *mut Opaque,
+        assumptions: *const Lit,
+        n_assumptions: i32,
+        conflict_budget: i64,
+    ) -> i32;
+    fn Glucose_NumConflictLits(solver: *mut Opaque) -> i32;
+    fn Glucose_GetFailedAssumption(solver: *mut Opaque, i: i32) -> Lit;
     fn Glucose_NumVar(solver: *mut Opaque) -> i32;
     fn Glucose_GetModelValueVar(solver: *mut Opaque, var: i32) -> i32;
     fn Glucose_AddOrderEncodingLinear(
@@ -55,15 +68,42 @@ extern "C" {
     fn Glucose_SolverStats_decisions(solver: *mut Opaque) -> u64;
     fn Glucose_SolverStats_propagations(solver: *mut Opaque) -> u64;
     fn Glucose_SolverStats_conflicts(solver: *mut Opaque) -> u64;
+    fn Glucose_SolverStats_restarts(solver: *mut Opaque) -> u64;
+    fn Glucose_SolverStats_learnts(solver: *mut Opaque) -> u64;
+    fn Glucose_SolverStats_clauses(solver: *mut Opaque) -> u64;
     fn Glucose_Set_random_seed(solver: *mut Opaque, random_seed: f64);
     fn Glucose_Set_rnd_init_act(solver: *mut Opaque, rnd_init_act: i32);
     fn Glucose_Set_dump_analysis_info(solver: *mut Opaque, value: i32);
+    fn Glucose_SetPolarity(solver: *mut Opaque, var: i32, value: i32);
+    fn Glucose_Interrupt(solver: *mut Opaque);
+    fn Glucose_ClearInterrupt(solver: *mut Opaque);
+    #[cfg(feature = "drat")]
+    fn Glucose_EnableDrat(solver: *mut Opaque, path: *const c_char) -> i32;
 }
 
 pub struct Solver {
     ptr: *mut Opaque,
 }
 
+// A Glucose instance is exclusively owned by `Solver`, so it can be moved to another thread.
+unsafe impl Send for Solver {}
+
+/// Handle for interrupting `solve` of a `Solver` running in another thread.
+/// This must not outlive the `Solver`.
+pub(crate) struct InterruptHandle {
+    ptr: *mut Opaque,
+}
+
+// Glucose only sets a flag checked by the search loop on interruption.
+unsafe impl Send for InterruptHandle {}
+unsafe impl Sync for InterruptHandle {}
+
+impl InterruptHandle {
+    pub(crate) fn interrupt(&self) {
+        unsafe { Glucose_Interrupt(self.ptr) }
+    }
+}
+
 const NUM_VAR_MAX: i32 = 0x3fffffff;
 
 impl Solver {
@@ -264,6 +304,35 @@ impl Solver {
         unsafe { Glucose_Set_dump_analysis_info(self.ptr, if dump_analysis_info { 1 } else { 0 }) }
     }
 
+    /// Makes the solver assign `value` to `var` when it is first decided.
+    /// Later decisions follow the phase saving of Glucose.
+    pub fn set_polarity(&mut self, var: Var, value: bool) {
+        assert!(0 <= var.0 && var.0 < self.num_var());
+        unsafe { Glucose_SetPolarity(self.ptr, var.0, if value { 1 } else { 0 }) }
+    }
+
+    pub(crate) fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle { ptr: self.ptr }
+    }
+
+    /// Clears the interruption requested via `InterruptHandle`, which otherwise makes all the
+    /// subsequent calls of `solve` return immediately.
+    pub(crate) fn clear_interrupt(&mut self) {
+        unsafe { Glucose_ClearInterrupt(self.ptr) }
+    }
+
+    #[cfg(feature = "drat")]
+    pub fn enable_drat(&mut self, path: &str) -> std::io::Result<()> {
+        let c_string = CString::new(path)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let res = unsafe { Glucose_EnableDrat(self.ptr, c_string.as_ptr()) };
+        if res != 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
     pub fn solve<'a>(&'a mut self) -> Option<Model<'a>> {
         if self.solve_without_model() {
             Some(unsafe { self.model() })
@@ -277,6 +346,53 @@ impl Solver {
         res != 0
     }
 
+    pub fn solve_under_assumptions<'a>(&'a mut self, assumptions: &[Lit]) -> Option<Model<'a>> {
+        if self.solve_without_model_under_assumptions(assumptions) {
+            Some(unsafe { self.model() })
+        } else {
+            None
+        }
+    }
+
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        assert!(assumptions.len() <= i32::MAX as usize);
+        let res = unsafe {
+            Glucose_SolveWithAssumptions(self.ptr, assumptions.as_ptr(), assumptions.len() as i32)
+        };
+        res != 0
+    }
+
+    /// Same as `solve_without_model_under_assumptions`, but gives up and returns `None` after
+    /// `conflict_budget` conflicts (if specified) or on an interruption.
+    pub fn solve_limited(
+        &mut self,
+        assumptions: &[Lit],
+        conflict_budget: Option<u64>,
+    ) -> Option<bool> {
+        assert!(assumptions.len() <= i32::MAX as usize);
+        let conflict_budget = conflict_budget.map_or(-1, |b| b.min(i64::MAX as u64) as i64);
+        let res = unsafe {
+            Glucose_SolveLimited(
+                self.ptr,
+                assumptions.as_ptr(),
+                assumptions.len() as i32,
+                conflict_budget,
+            )
+        };
+        if res < 0 {
+            None
+        } else {
+            Some(res != 0)
+        }
+    }
+
+    pub fn unsat_core(&self) -> Vec<Lit> {
+        let n = unsafe { Glucose_NumConflictLits(self.ptr) };
+        (0..n)
+            .map(|i| unsafe { Glucose_GetFailedAssumption(self.ptr, i) })
+            .collect()
+    }
+
     pub(crate) unsafe fn model<'a>(&'a self) -> Model<'a> {
         Model { solver: self }
     }
@@ -292,6 +408,20 @@ impl Solver {
     pub fn stats_conflicts(&self) -> u64 {
         unsafe { Glucose_SolverStats_conflicts(self.ptr) }
     }
+
+    pub fn stats_restarts(&self) -> u64 {
+        unsafe { Glucose_SolverStats_restarts(self.ptr) }
+    }
+
+    /// Number of learnt clauses currently kept by the solver.
+    pub fn stats_learnts(&self) -> u64 {
+        unsafe { Glucose_SolverStats_learnts(self.ptr) }
+    }
+
+    /// Number of problem (not learnt) clauses currently kept by the solver.
+    pub fn stats_clauses(&self) -> u64 {
+        unsafe { Glucose_SolverStats_clauses(self.ptr) }
+    }
 }
 
 impl Drop for Solver {
@@ -350,4 +480,40 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_solver_assumptions() {
+        let mut solver = Solver::new();
+        let x = solver.new_var();
+        let y = solver.new_var();
+        let z = solver.new_var();
+
+        // x -> y, y -> z, x -> !z
+        assert!(solver.add_clause(&[Lit::new(x, true), Lit::new(y, false)]));
+        assert!(solver.add_clause(&[Lit::new(y, true), Lit::new(z, false)]));
+        assert!(solver.add_clause(&[Lit::new(x, true), Lit::new(z, true)]));
+
+        assert!(solver
+            .solve_under_assumptions(&[Lit::new(x, false)])
+            .is_none());
+        match solver.solve_under_assumptions(&[Lit::new(x, true)]) {
+            Some(model) => assert!(!model.assignment(x)),
+            None => panic!(),
+        }
+
+        // assumptions do not persist into later calls
+        assert!(solver.solve().is_some());
+        match solver.solve_under_assumptions(&[Lit::new(y, false)]) {
+            Some(model) => {
+                assert!(!model.assignment(x));
+                assert!(model.assignment(y));
+                assert!(model.assignment(z));
+            }
+            None => panic!(),
+        }
+        assert!(solver
+            .solve_under_assumptions(&[Lit::new(y, false), Lit::new(z, true)])
+            .is_none());
+        assert!(solver.solve().is_some());
+    }
 }
@@ -5,11 +5,31 @@ use std::io;
 
 #[cfg(feature = "parser")]
 fn main() {
-    let stdin = io::stdin();
-    let mut lock = stdin.lock();
-    let config = enigma_csp::config::Config::parse_from_args();
-    let (res, _) = enigma_csp::csugar_cli::csugar_cli(&mut lock, config);
-    print!("{}", res);
+    let (config, cli_options) = enigma_csp::config::Config::parse_from_args_with_cli_options();
+    let format = if cli_options.json_output {
+        enigma_csp::csugar_cli::OutputFormat::Json
+    } else if cli_options.dimacs_output {
+        enigma_csp::csugar_cli::OutputFormat::Dimacs
+    } else {
+        enigma_csp::csugar_cli::OutputFormat::Text
+    };
+    let res = match &cli_options.input_file {
+        Some(path) => {
+            enigma_csp::csugar_cli::try_csugar_cli_file(std::path::Path::new(path), config, format)
+        }
+        None => {
+            let stdin = io::stdin();
+            let mut lock = stdin.lock();
+            enigma_csp::csugar_cli::try_csugar_cli_with_format(&mut lock, config, format)
+        }
+    };
+    match res {
+        Ok((res, _)) => print!("{}", res),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(1);
+        }
+    }
 }
 
 #[cfg(not(feature = "parser"))]
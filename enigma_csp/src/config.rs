@@ -1,6 +1,10 @@
-use crate::sat::Backend;
+use std::collections::HashMap;
 
-#[derive(Clone, Copy)]
+use crate::encoder::EncodingKind;
+use crate::norm_csp::IntVar;
+use crate::sat::SatBackend;
+
+#[derive(Clone)]
 pub struct Config {
     pub use_constant_folding: bool,
     pub use_constant_propagation: bool,
@@ -9,30 +13,63 @@ pub struct Config {
     pub native_linear_encoding_terms: usize,
     pub native_linear_encoding_domain_product_threshold: usize,
     pub use_direct_encoding: bool,
+    /// Use both order and direct encodings for variables involved in both simple (in)equalities
+    /// and other linear constraints.
+    pub dual_encoding: bool,
     pub use_log_encoding: bool,
     pub force_use_log_encoding: bool,
+    /// Maximum number of terms in each linear sum after decomposition for log encoding.
+    /// Must be at least 2.
+    pub log_encoding_max_terms: usize,
     pub use_native_extension_supports: bool,
     pub direct_encoding_for_binary_vars: bool,
+    /// Encodings of individual (normalized) integer variables, which take precedence over the
+    /// encodings decided by other options.
+    /// An overridden encoding is not propagated to the other variables. Instead, if a linear
+    /// constraint has both log-encoded variables and the others, the latter are additionally
+    /// log-encoded.
+    pub encoding_override: Option<HashMap<IntVar, EncodingKind>>,
+    pub use_bdd_for_pb: bool,
     pub merge_equivalent_variables: bool,
     pub alldifferent_bijection_constraints: bool,
+    /// Hint the SAT solver to decide order-encoded variables to values around the middle of
+    /// their domains first.
+    pub order_encoding_phase_hints: bool,
     pub glucose_random_seed: Option<f64>,
     pub glucose_rnd_init_act: bool,
     pub dump_analysis_info: bool,
-    pub backend: Backend,
+    pub backend: SatBackend,
+    /// Number of Glucose instances solving the problem in parallel (1 for disabling portfolio
+    /// solving). Only the Glucose backend supports this.
+    pub portfolio: usize,
+    /// Record the SAT instance so that `IntegratedSolver::push` and `pop` can be used.
+    /// Only the Glucose backend supports this.
+    pub enable_push_pop: bool,
     pub verbose: bool,
 }
 
+/// Options of the csugar-like CLI which do not affect the solver.
+#[derive(Clone, Default)]
+pub struct CliOptions {
+    /// Print the result as JSON instead of the csugar-compatible text.
+    pub json_output: bool,
+    /// Print the encoded SAT instance in DIMACS format instead of solving the problem.
+    pub dimacs_output: bool,
+    /// Path of the problem file (stdin is used if omitted).
+    pub input_file: Option<String>,
+}
+
 thread_local! {
-    static DEFAULT_CONFIG: std::cell::Cell<Config> = std::cell::Cell::new(Config::initial_default());
+    static DEFAULT_CONFIG: std::cell::RefCell<Config> = std::cell::RefCell::new(Config::initial_default());
 }
 
 impl Config {
     pub fn default() -> Config {
-        DEFAULT_CONFIG.with(|f| f.get())
+        DEFAULT_CONFIG.with(|f| f.borrow().clone())
     }
 
     pub fn set_default(new_default: Config) {
-        DEFAULT_CONFIG.with(|f| f.set(new_default));
+        DEFAULT_CONFIG.with(|f| *f.borrow_mut() = new_default);
     }
 
     pub const fn initial_default() -> Config {
@@ -44,27 +81,40 @@ impl Config {
             native_linear_encoding_terms: 4,
             native_linear_encoding_domain_product_threshold: 20,
             use_direct_encoding: true,
+            dual_encoding: false,
             use_log_encoding: true,
             force_use_log_encoding: false,
+            log_encoding_max_terms: 6,
             use_native_extension_supports: false,
             direct_encoding_for_binary_vars: false,
+            encoding_override: None,
+            use_bdd_for_pb: false,
             merge_equivalent_variables: false,
             alldifferent_bijection_constraints: false,
+            order_encoding_phase_hints: true,
             glucose_random_seed: None,
             glucose_rnd_init_act: false,
             dump_analysis_info: false,
-            backend: Backend::Glucose,
+            backend: SatBackend::Glucose,
+            portfolio: 1,
+            enable_push_pop: false,
             verbose: false,
         }
     }
 
     #[cfg(feature = "cli")]
     pub fn parse_from_args() -> Config {
+        Config::parse_from_args_with_cli_options().0
+    }
+
+    #[cfg(feature = "cli")]
+    pub fn parse_from_args_with_cli_options() -> (Config, CliOptions) {
         extern crate getopts;
         use getopts::Options;
 
         let args = std::env::args().collect::<Vec<_>>();
         let mut config = Config::default();
+        let mut cli_options = CliOptions::default();
         let mut opts = Options::new();
 
         let mut bool_flags = [
@@ -88,6 +138,11 @@ impl Config {
                 "direct-encoding",
                 "use direct encoding if applicable",
             ),
+            (
+                &mut config.dual_encoding,
+                "dual-encoding",
+                "use both order and direct encodings with channeling clauses if beneficial",
+            ),
             (
                 &mut config.use_log_encoding,
                 "log-encoding",
@@ -103,16 +158,26 @@ impl Config {
                 "use-native-extension-supports",
                 "use native propagator for extension (supports) constraints",
             ),
+            (
+                &mut config.use_bdd_for_pb,
+                "bdd-for-pb",
+                "use BDD-based encoding for pseudo-Boolean constraints",
+            ),
             (
                 &mut config.merge_equivalent_variables,
                 "merge-equivalent-variables",
-                "merge equivalent variables (which is caused by, for example, (iff x y))",
+                "merge equivalent variables (which is caused by, for example, (iff x y) or (== x y))",
             ),
             (
                 &mut config.alldifferent_bijection_constraints,
                 "alldifferent-bijection-constraints",
                 "add auxiliary constraints for bijective alldifferent constraints",
             ),
+            (
+                &mut config.order_encoding_phase_hints,
+                "order-encoding-phase-hints",
+                "phase hints of the SAT solver for order-encoded variables",
+            ),
             (
                 &mut config.dump_analysis_info,
                 "dump-analysis-info",
@@ -148,9 +213,19 @@ impl Config {
         opts.optopt("", "domain-product-threshold", "Specify the threshold of domain product for introducing an auxiliary variable by Tseitin transformation.", "THRESHOLD");
         opts.optopt("", "native-linear-encoding-terms", "Specify the maximum number of terms in a linear sum which is encoded by the native linear constraint (0 for disabling this).", "TERMS");
         opts.optopt("", "native-linear-encoding-domain-product", "Specify the minimum domain product of linear sums which are encoded by the native linear constraint.", "DOMAIN_PRODUCT");
+        opts.optopt("", "log-encoding-max-terms", "Specify the maximum number of terms in a linear sum after decomposition for log encoding (at least 2).", "TERMS");
+        opts.optopt("", "portfolio", "Specify the number of SAT solver instances running in parallel (1 for disabling portfolio solving).", "WORKERS");
 
         opts.optopt("", "backend", "Specify the SAT backend", "BACKEND");
+        opts.optopt("", "external-solver", "Specify the path of the SAT solver binary for the external backend (ENIGMA_CSP_EXTERNAL_SOLVER is used if omitted).", "PATH");
+        opts.optopt("", "external-solver-args", "Specify the arguments (separated by spaces) passed to the external SAT solver before the CNF file.", "ARGS");
 
+        opts.optflag("", "json", "Print the result as JSON");
+        opts.optflag(
+            "",
+            "dimacs",
+            "Print the encoded SAT instance in DIMACS format instead of solving",
+        );
         opts.optflag("h", "help", "Display this help");
 
         let matches = match opts.parse(&args[1..]) {
@@ -163,7 +238,7 @@ impl Config {
 
         if matches.opt_present("h") {
             // display help
-            let brief = format!("Usage: {} [options]", args[0]);
+            let brief = format!("Usage: {} [options] [FILE]", args[0]);
             print!("{}", opts.usage(&brief));
             std::process::exit(0);
         }
@@ -186,6 +261,18 @@ impl Config {
             }
         }
 
+        cli_options.json_output = matches.opt_present("json");
+        cli_options.dimacs_output = matches.opt_present("dimacs");
+        if cli_options.json_output && cli_options.dimacs_output {
+            println!("error: conflicting options json and dimacs are specified at the same time");
+            std::process::exit(1);
+        }
+        if matches.free.len() > 1 {
+            println!("error: at most one input file can be specified");
+            std::process::exit(1);
+        }
+        cli_options.input_file = matches.free.first().cloned();
+
         if let Some(s) = matches.opt_str("domain-product-threshold") {
             let v = match s.parse::<usize>() {
                 Ok(v) => v,
@@ -225,19 +312,64 @@ impl Config {
             };
             config.native_linear_encoding_domain_product_threshold = v;
         }
+        if let Some(s) = matches.opt_str("log-encoding-max-terms") {
+            let v = match s.parse::<usize>() {
+                Ok(v) if v >= 2 => v,
+                Ok(_) => {
+                    println!("error: --log-encoding-max-terms must be at least 2");
+                    std::process::exit(1);
+                }
+                Err(f) => {
+                    println!(
+                        "error: parse failed for --log-encoding-max-terms: {}",
+                        f.to_string()
+                    );
+                    std::process::exit(1);
+                }
+            };
+            config.log_encoding_max_terms = v;
+        }
+        if let Some(s) = matches.opt_str("portfolio") {
+            let v = match s.parse::<usize>() {
+                Ok(v) if v >= 1 => v,
+                Ok(_) => {
+                    println!("error: --portfolio must be at least 1");
+                    std::process::exit(1);
+                }
+                Err(f) => {
+                    println!("error: parse failed for --portfolio: {}", f);
+                    std::process::exit(1);
+                }
+            };
+            config.portfolio = v;
+        }
         if let Some(s) = matches.opt_str("backend") {
             if s == "glucose" {
-                config.backend = Backend::Glucose;
+                config.backend = SatBackend::Glucose;
             } else if s == "external" {
-                config.backend = Backend::External;
+                let path = match matches
+                    .opt_str("external-solver")
+                    .or_else(|| std::env::var("ENIGMA_CSP_EXTERNAL_SOLVER").ok())
+                {
+                    Some(path) => path,
+                    None => {
+                        println!("error: --external-solver is required for external backend");
+                        std::process::exit(1);
+                    }
+                };
+                let args = matches
+                    .opt_str("external-solver-args")
+                    .map(|s| s.split_whitespace().map(|a| a.to_owned()).collect())
+                    .unwrap_or_default();
+                config.backend = SatBackend::External { path, args };
             } else if s == "cadical" {
-                config.backend = Backend::CaDiCaL;
+                config.backend = SatBackend::CaDiCaL;
             } else {
                 println!("error: unknown backend: {}", s);
                 std::process::exit(1);
             }
         }
 
-        config
+        (config, cli_options)
     }
 }
@@ -6,6 +6,7 @@ use std::ops::{Index, IndexMut};
 
 pub use super::csp_repr::{BoolExpr, BoolVar, IntExpr, IntVar, Stmt};
 
+#[derive(Clone)]
 pub(super) struct BoolVarData {
     possibility_mask: u8,
 }
@@ -43,6 +44,7 @@ impl BoolVarData {
     }
 }
 
+#[derive(Clone)]
 pub(super) struct IntVarData {
     pub(super) domain: Domain,
 }
@@ -53,6 +55,7 @@ impl IntVarData {
     }
 }
 
+#[derive(Clone)]
 pub(super) struct CSPVars {
     bool_var: Vec<BoolVarData>,
     int_var: Vec<IntVarData>,
@@ -235,7 +238,7 @@ impl CSPVars {
             IntExpr::Abs(x) => {
                 self.constant_folding_int(x);
             }
-            IntExpr::Mul(x, y) => {
+            IntExpr::Mul(x, y) | IntExpr::Div(x, y) | IntExpr::Mod(x, y) => {
                 self.constant_folding_int(x);
                 self.constant_folding_int(y);
                 // TODO: implement constant folding
@@ -331,6 +334,7 @@ pub enum IntVarStatus {
     Unfixed(CheckedInt), // an example of feasible value
 }
 
+#[derive(Clone)]
 pub struct CSP {
     pub(super) vars: CSPVars,
     pub(super) constraints: Vec<Stmt>,
@@ -402,6 +406,68 @@ impl CSP {
         }
     }
 
+    /// Returns a range `(low, high)` containing every value `expr` can take, computed from the
+    /// domains of the variables in it (the bounds are not necessarily tight).
+    pub(crate) fn int_expr_bounds(&self, expr: &IntExpr) -> (CheckedInt, CheckedInt) {
+        match expr {
+            &IntExpr::Const(c) => (CheckedInt::new(c), CheckedInt::new(c)),
+            &IntExpr::Var(v) => {
+                let domain = &self.vars.int_var(v).domain;
+                (domain.lower_bound_checked(), domain.upper_bound_checked())
+            }
+            IntExpr::NVar(_) => panic!("bounds of normalized variables are not available in CSP"),
+            IntExpr::Linear(terms) => {
+                let mut low = CheckedInt::new(0);
+                let mut high = CheckedInt::new(0);
+                for (e, coef) in terms {
+                    let (l, h) = self.int_expr_bounds(e);
+                    let coef = CheckedInt::new(*coef);
+                    if coef >= 0 {
+                        low += l * coef;
+                        high += h * coef;
+                    } else {
+                        low += h * coef;
+                        high += l * coef;
+                    }
+                }
+                (low, high)
+            }
+            IntExpr::If(_, t, f) => {
+                let (tl, th) = self.int_expr_bounds(t);
+                let (fl, fh) = self.int_expr_bounds(f);
+                (tl.min(fl), th.max(fh))
+            }
+            IntExpr::Abs(x) => {
+                let (l, h) = self.int_expr_bounds(x);
+                if l >= 0 {
+                    (l, h)
+                } else if h <= 0 {
+                    (-h, -l)
+                } else {
+                    (CheckedInt::new(0), (-l).max(h))
+                }
+            }
+            IntExpr::Mul(x, y) => {
+                let (xl, xh) = self.int_expr_bounds(x);
+                let (yl, yh) = self.int_expr_bounds(y);
+                let cands = [xl * yl, xl * yh, xh * yl, xh * yh];
+                (*cands.iter().min().unwrap(), *cands.iter().max().unwrap())
+            }
+            IntExpr::Div(x, _) => {
+                // |x / y| <= |x| since |y| >= 1
+                let (l, h) = self.int_expr_bounds(x);
+                let m = l.abs().max(h.abs());
+                (-m, m)
+            }
+            IntExpr::Mod(_, y) => {
+                // |x % y| < |y|
+                let (l, h) = self.int_expr_bounds(y);
+                let m = (l.abs().max(h.abs()) - CheckedInt::new(1)).max(CheckedInt::new(0));
+                (-m, m)
+            }
+        }
+    }
+
     pub fn apply_constant_folding(&mut self) {
         let vars = &mut self.vars;
         for stmt in &mut self.constraints {
@@ -410,11 +476,17 @@ impl CSP {
                 Stmt::AllDifferent(exprs) => {
                     exprs.iter_mut().for_each(|e| vars.constant_folding_int(e));
                 }
-                Stmt::ActiveVerticesConnected(vertices, _edges) => {
+                Stmt::ActiveVerticesConnected(vertices, _edges)
+                | Stmt::DirectedReachable(_, vertices, _edges)
+                | Stmt::SingleCycle(vertices, _edges)
+                | Stmt::Acyclic(vertices, _edges) => {
                     vertices
                         .iter_mut()
                         .for_each(|e| vars.constant_folding_bool(e));
                 }
+                Stmt::Cardinality(exprs, _, _) => {
+                    exprs.iter_mut().for_each(|e| vars.constant_folding_bool(e));
+                }
                 Stmt::Circuit(_) => (),
                 Stmt::ExtensionSupports(_, _) => (),
                 Stmt::GraphDivision(sizes, _edges, edge_lits) => {
@@ -576,6 +648,15 @@ impl Assignment {
             IntExpr::If(c, t, f) => self.eval_int_expr(if self.eval_bool_expr(c) { t } else { f }),
             IntExpr::Abs(x) => self.eval_int_expr(x).abs(),
             IntExpr::Mul(x, y) => self.eval_int_expr(x) * self.eval_int_expr(y),
+            IntExpr::Div(x, y) => {
+                let x = self.eval_int_expr(x);
+                let y = self.eval_int_expr(y);
+                (x - (x % y + y) % y) / y
+            }
+            IntExpr::Mod(x, y) => {
+                let y = self.eval_int_expr(y);
+                (self.eval_int_expr(x) % y + y) % y
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::util::ConvertMapIndex;
 use std::io::Write;
-use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Rem, Sub};
 
 use crate::arithmetic::CmpOp;
 
@@ -47,7 +47,20 @@ pub enum Stmt {
     Expr(BoolExpr),
     AllDifferent(Vec<IntExpr>),
     ActiveVerticesConnected(Vec<BoolExpr>, Vec<(usize, usize)>),
+    /// `DirectedReachable(source, vertices, edges)`: every active vertex is reachable from
+    /// `source` along the directed `edges` through active vertices only.
+    DirectedReachable(usize, Vec<BoolExpr>, Vec<(usize, usize)>),
+    /// `SingleCycle(edge_exprs, edges)`: the active `edges` form exactly one simple cycle,
+    /// that is, every vertex is incident to 0 or 2 active edges and the active edges are
+    /// connected. At least one edge must be active.
+    SingleCycle(Vec<BoolExpr>, Vec<(usize, usize)>),
+    /// `Acyclic(edge_exprs, edges)`: the active `edges` contain no cycle, i.e. they form a
+    /// forest.
+    Acyclic(Vec<BoolExpr>, Vec<(usize, usize)>),
     Circuit(Vec<IntVar>),
+    /// `Cardinality(exprs, low, high)`: the number of true `exprs` is between `low` and `high`
+    /// (inclusive).
+    Cardinality(Vec<BoolExpr>, usize, usize),
     ExtensionSupports(Vec<IntVar>, Vec<Vec<Option<i32>>>),
     GraphDivision(Vec<Option<IntExpr>>, Vec<(usize, usize)>, Vec<BoolExpr>),
 }
@@ -82,6 +95,48 @@ impl Stmt {
                 }
                 write!(out, "])")?;
             }
+            Stmt::DirectedReachable(source, exprs, edges) => {
+                write!(out, "(directed-reachable source={}", source)?;
+                for (i, expr) in exprs.iter().enumerate() {
+                    write!(out, " {}:", i)?;
+                    expr.pretty_print(out)?;
+                }
+                write!(out, " graph=[")?;
+                let mut is_first = true;
+                for &(u, v) in edges {
+                    if !is_first {
+                        write!(out, " ")?;
+                    } else {
+                        is_first = false;
+                    }
+                    write!(out, "{}->{}", u, v)?;
+                }
+                write!(out, "])")?;
+            }
+            Stmt::SingleCycle(exprs, edges) => {
+                write!(out, "(single-cycle")?;
+                for (expr, &(u, v)) in exprs.iter().zip(edges) {
+                    write!(out, " {}--{}:", u, v)?;
+                    expr.pretty_print(out)?;
+                }
+                write!(out, ")")?;
+            }
+            Stmt::Acyclic(exprs, edges) => {
+                write!(out, "(acyclic")?;
+                for (expr, &(u, v)) in exprs.iter().zip(edges) {
+                    write!(out, " {}--{}:", u, v)?;
+                    expr.pretty_print(out)?;
+                }
+                write!(out, ")")?;
+            }
+            Stmt::Cardinality(exprs, low, high) => {
+                write!(out, "(cardinality {} {}", low, high)?;
+                for expr in exprs {
+                    write!(out, " ")?;
+                    expr.pretty_print(out)?;
+                }
+                write!(out, ")")?;
+            }
             Stmt::Circuit(vars) => {
                 write!(out, "(circuit")?;
                 for v in vars {
@@ -236,6 +291,12 @@ pub enum IntExpr {
     If(Box<BoolExpr>, Box<IntExpr>, Box<IntExpr>),
     Abs(Box<IntExpr>),
     Mul(Box<IntExpr>, Box<IntExpr>),
+    /// Quotient of the floor division (rounded toward negative infinity).
+    /// The divisor is required to be nonzero wherever this appears.
+    Div(Box<IntExpr>, Box<IntExpr>),
+    /// Remainder of the floor division, which has the same sign as the divisor.
+    /// The divisor is required to be nonzero wherever this appears.
+    Mod(Box<IntExpr>, Box<IntExpr>),
 }
 
 impl IntExpr {
@@ -307,6 +368,20 @@ impl IntExpr {
                 y.pretty_print(out)?;
                 write!(out, ")")?;
             }
+            IntExpr::Div(x, y) => {
+                write!(out, "(div ")?;
+                x.pretty_print(out)?;
+                write!(out, " ")?;
+                y.pretty_print(out)?;
+                write!(out, ")")?;
+            }
+            IntExpr::Mod(x, y) => {
+                write!(out, "(mod ")?;
+                x.pretty_print(out)?;
+                write!(out, " ")?;
+                y.pretty_print(out)?;
+                write!(out, ")")?;
+            }
         }
         Ok(())
     }
@@ -343,3 +418,21 @@ impl Mul<IntExpr> for IntExpr {
         IntExpr::Mul(Box::new(self), Box::new(rhs))
     }
 }
+
+/// Floor division (see `IntExpr::Div`), not the truncating division of `i32`.
+impl Div<IntExpr> for IntExpr {
+    type Output = IntExpr;
+
+    fn div(self, rhs: IntExpr) -> IntExpr {
+        IntExpr::Div(Box::new(self), Box::new(rhs))
+    }
+}
+
+/// Floor modulo (see `IntExpr::Mod`), not the truncating remainder of `i32`.
+impl Rem<IntExpr> for IntExpr {
+    type Output = IntExpr;
+
+    fn rem(self, rhs: IntExpr) -> IntExpr {
+        IntExpr::Mod(Box::new(self), Box::new(rhs))
+    }
+}
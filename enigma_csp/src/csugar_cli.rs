@@ -1,44 +1,305 @@
 /// csugar-like CLI
 use std::fmt::Write;
-use std::io::BufRead;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 
 use super::config::Config;
-use super::integration::{IntegratedSolver, PerfStats};
+use super::csp::IntExpr;
+use super::integration::{DimacsVarLits, IntegratedSolver, PerfStats};
 use super::parser::{parse, ParseResult, Var, VarMap};
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    /// csugar-compatible text output.
+    Text,
+    /// `{"status":"sat","assignments":{"x":1,"b":true}}` or `{"status":"unsat"}`.
+    Json,
+    /// The encoded SAT instance in DIMACS format instead of solving it (see `write_dimacs`).
+    Dimacs,
+}
+
 pub fn csugar_cli<R: BufRead>(input: &mut R, config: Config) -> (String, PerfStats) {
+    match try_csugar_cli(input, config) {
+        Ok(res) => res,
+        Err(message) => panic!("{}", message),
+    }
+}
+
+/// Same as `csugar_cli`, but returns an error message instead of panicking when the input cannot
+/// be read or the target line refers to an undeclared variable.
+/// Malformed lines are reported to stderr and skipped rather than being treated as errors.
+pub fn try_csugar_cli<R: BufRead>(
+    input: &mut R,
+    config: Config,
+) -> Result<(String, PerfStats), String> {
+    try_csugar_cli_with_format(input, config, OutputFormat::Text)
+}
+
+/// Same as `try_csugar_cli_with_format`, but reads the problem from the file at `path`.
+pub fn try_csugar_cli_file(
+    path: &Path,
+    config: Config,
+    format: OutputFormat,
+) -> Result<(String, PerfStats), String> {
+    let file = File::open(path).map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    try_csugar_cli_with_format(&mut BufReader::new(file), config, format)
+}
+
+pub fn try_csugar_cli_with_format<R: BufRead>(
+    input: &mut R,
+    config: Config,
+    format: OutputFormat,
+) -> Result<(String, PerfStats), String> {
     let mut var_map = VarMap::new();
     let mut solver = IntegratedSolver::with_config(config);
 
     let perf_stats = PerfStats::new();
     solver.set_perf_stats(&perf_stats);
 
+    if format == OutputFormat::Dimacs {
+        solver.enable_dimacs_export();
+    }
+
+    let directives = load_problem(input, &mut solver, &mut var_map)?;
+    let targets = match directives.target_vars {
+        Some(target_vars) => {
+            let mut targets = vec![];
+            for target in target_vars {
+                match var_map.get_var(&target) {
+                    Some(var) => targets.push((target, var)),
+                    None => {
+                        return Err(format!(
+                            "undeclared variable in the target line: {}",
+                            target
+                        ))
+                    }
+                }
+            }
+            Some(targets)
+        }
+        None => None,
+    };
+    let mut ret = String::new();
+
+    let reported_vars = || match &targets {
+        Some(targets) => targets.clone(),
+        None => var_map
+            .iter()
+            .map(|(name, &var)| (name.clone(), var))
+            .collect::<Vec<_>>(),
+    };
+
+    if format == OutputFormat::Dimacs {
+        // the objective is not encoded
+        write_dimacs(&mut ret, &mut solver, &reported_vars());
+        return Ok((ret, perf_stats));
+    }
+
+    if let Some((objective, maximize)) = directives.objective {
+        let vars = reported_vars();
+        let res = if maximize {
+            solver.maximize_expr(objective)
+        } else {
+            solver.minimize_expr(objective)
+        };
+        let res = res.map(|(model, value)| {
+            let assignments = vars
+                .iter()
+                .map(|(name, var)| {
+                    let value = match *var {
+                        Var::Bool(var) => model.get_bool(var).to_string(),
+                        Var::Int(var) => model.get_int(var).to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect::<Vec<_>>();
+            (value, assignments)
+        });
+        match format {
+            OutputFormat::Text => match res {
+                Some((value, assignments)) => {
+                    writeln!(&mut ret, "o {}", value).unwrap();
+                    writeln!(&mut ret, "s OPTIMUM FOUND").unwrap();
+                    for (name, value) in assignments {
+                        writeln!(&mut ret, "a {}\t{}", name, value).unwrap();
+                    }
+                    writeln!(&mut ret, "a").unwrap();
+                }
+                None => writeln!(&mut ret, "s UNSATISFIABLE").unwrap(),
+            },
+            OutputFormat::Json => match res {
+                Some((value, assignments)) => write_json(&mut ret, Some(assignments), Some(value)),
+                None => write_json(&mut ret, None, None),
+            },
+            OutputFormat::Dimacs => unreachable!(),
+        }
+        return Ok((ret, perf_stats));
+    }
+
+    match targets {
+        Some(targets) => {
+            let mut bool_target = vec![];
+            let mut int_target = vec![];
+            for &(_, var) in &targets {
+                match var {
+                    Var::Bool(var) => bool_target.push(var),
+                    Var::Int(var) => int_target.push(var),
+                }
+            }
+            let assignments = solver
+                .decide_irrefutable_facts(&bool_target, &int_target)
+                .map(|result| {
+                    let mut assignments = vec![];
+                    for (target, var) in &targets {
+                        match *var {
+                            Var::Bool(var) => {
+                                if let Some(b) = result.get_bool(var) {
+                                    assignments.push((target.clone(), b.to_string()));
+                                }
+                            }
+                            Var::Int(var) => {
+                                if let Some(i) = result.get_int(var) {
+                                    assignments.push((target.clone(), i.to_string()));
+                                }
+                            }
+                        }
+                    }
+                    assignments
+                });
+            match format {
+                OutputFormat::Text => match assignments {
+                    Some(assignments) => {
+                        writeln!(&mut ret, "sat").unwrap();
+                        for (target, value) in assignments {
+                            writeln!(&mut ret, "{} {}", target, value).unwrap();
+                        }
+                    }
+                    None => writeln!(&mut ret, "unsat").unwrap(),
+                },
+                OutputFormat::Json => write_json(&mut ret, assignments, None),
+                OutputFormat::Dimacs => unreachable!(),
+            }
+        }
+        None => {
+            let assignments = solver.solve().map(|model| {
+                var_map
+                    .iter()
+                    .map(|(name, &var)| {
+                        let value = match var {
+                            Var::Bool(var) => model.get_bool(var).to_string(),
+                            Var::Int(var) => model.get_int(var).to_string(),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect::<Vec<_>>()
+            });
+            match format {
+                OutputFormat::Text => match assignments {
+                    Some(assignments) => {
+                        writeln!(&mut ret, "s SATISFIABLE").unwrap();
+                        for (name, value) in assignments {
+                            writeln!(&mut ret, "a {}\t{}", name, value).unwrap();
+                        }
+                        writeln!(&mut ret, "a").unwrap();
+                    }
+                    None => writeln!(&mut ret, "s UNSATISFIABLE").unwrap(),
+                },
+                OutputFormat::Json => write_json(&mut ret, assignments, None),
+                OutputFormat::Dimacs => unreachable!(),
+            }
+        }
+    }
+
+    Ok((ret, perf_stats))
+}
+
+/// Lines of a problem other than variable declarations and constraints.
+#[derive(Default)]
+pub struct Directives {
+    /// Variables in the target line (`#x y ...`).
+    pub target_vars: Option<Vec<String>>,
+    /// Objective given by `(minimize e)` or `(maximize e)`, with `true` for maximization.
+    pub objective: Option<(IntExpr, bool)>,
+}
+
+/// Reads a csugar-like problem from `input`, declaring its variables in `solver` and `var_map`
+/// and adding its constraints to `solver`.
+/// Everything after `;`, as well as lines starting with `//`, are ignored as comments.
+pub fn load_problem<R: BufRead>(
+    input: &mut R,
+    solver: &mut IntegratedSolver,
+    var_map: &mut VarMap,
+) -> Result<Directives, String> {
     let mut buffer = String::new();
 
     let mut target_vars: Option<Vec<String>> = None;
+    let mut objective: Option<(IntExpr, bool)> = None;
 
-    let mut ret = String::new();
+    let mut line_no = 0;
 
     loop {
         buffer.clear();
-        let num_bytes = input.read_line(&mut buffer).unwrap(); // TODO
+        let num_bytes = match input.read_line(&mut buffer) {
+            Ok(n) => n,
+            Err(e) => return Err(format!("failed to read line {}: {}", line_no + 1, e)),
+        };
         if num_bytes == 0 {
             // EOF
             break;
         }
-        let line = buffer.trim_end();
+        line_no += 1;
+        // `;` starts a comment (it cannot appear in identifiers), and so does `//` at the
+        // beginning of a line
+        let line = match buffer.find(';') {
+            Some(p) => &buffer[..p],
+            None => &buffer[..],
+        }
+        .trim_end();
+        if line.is_empty() || line.trim_start().starts_with("//") {
+            continue;
+        }
 
         if line.starts_with("#") {
-            assert!(target_vars.is_none());
+            if target_vars.is_some() {
+                return Err(format!("line {}: duplicate target line", line_no));
+            }
             target_vars = Some(
                 line.trim_start_matches("#")
                     .split(" ")
+                    .filter(|s| !s.is_empty())
                     .map(String::from)
                     .collect(),
             );
             continue;
         }
-        let result = parse(&var_map, line);
+        let result = parse(var_map, line);
+        if let ParseResult::BoolVarDecl(name)
+        | ParseResult::IntVarDecl(name, _)
+        | ParseResult::IntVarWithListDomDecl(name, _) = &result
+        {
+            if var_map.get_var(name).is_some() {
+                eprintln!(
+                    "error: line {}: variable {} is already declared",
+                    line_no, name
+                );
+                continue;
+            }
+        }
+        if let ParseResult::BoolArrayDecl(name, size)
+        | ParseResult::IntArrayDecl(name, size, _)
+        | ParseResult::IntArrayWithListDomDecl(name, size, _) = &result
+        {
+            if var_map.get_array_size(name).is_some()
+                || (0..*size).any(|i| var_map.get_var(&format!("{}[{}]", name, i)).is_some())
+            {
+                eprintln!(
+                    "error: line {}: array {} is already declared",
+                    line_no, name
+                );
+                continue;
+            }
+        }
         match result {
             ParseResult::BoolVarDecl(name) => {
                 let var = solver.new_bool_var();
@@ -52,59 +313,461 @@ pub fn csugar_cli<R: BufRead>(input: &mut R, config: Config) -> (String, PerfSta
                 let var = solver.new_int_var_from_list(domain_list);
                 var_map.add_int_var(name, var);
             }
+            ParseResult::BoolArrayDecl(name, size) => {
+                let vars = (0..size).map(|_| solver.new_bool_var()).collect::<Vec<_>>();
+                var_map.add_bool_array(name, &vars);
+            }
+            ParseResult::IntArrayDecl(name, size, domain) => {
+                let vars = (0..size)
+                    .map(|_| solver.new_int_var(domain.clone()))
+                    .collect::<Vec<_>>();
+                var_map.add_int_array(name, &vars);
+            }
+            ParseResult::IntArrayWithListDomDecl(name, size, domain_list) => {
+                let vars = (0..size)
+                    .map(|_| solver.new_int_var_from_list(domain_list.clone()))
+                    .collect::<Vec<_>>();
+                var_map.add_int_array(name, &vars);
+            }
             ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+            ParseResult::Minimize(_) | ParseResult::Maximize(_) if objective.is_some() => {
+                return Err(format!("line {}: duplicate objective", line_no));
+            }
+            ParseResult::Minimize(expr) => objective = Some((expr, false)),
+            ParseResult::Maximize(expr) => objective = Some((expr, true)),
+            ParseResult::Error {
+                line: err_line,
+                col,
+                message,
+            } => {
+                eprintln!(
+                    "error: line {}, column {}: {}",
+                    line_no + err_line - 1,
+                    col,
+                    message
+                );
+                eprintln!("    {}", line);
+                eprintln!("    {}^", " ".repeat(col.saturating_sub(1)));
+            }
         }
     }
 
-    match target_vars {
-        Some(target_vars) => {
-            let mut bool_target = vec![];
-            let mut int_target = vec![];
-            for target in &target_vars {
-                match var_map.get_var(target).unwrap() {
-                    Var::Bool(var) => bool_target.push(var),
-                    Var::Int(var) => int_target.push(var),
+    Ok(Directives {
+        target_vars,
+        objective,
+    })
+}
+
+/// Writes the SAT instance of `solver` in DIMACS format, preceded by comments describing how
+/// `vars` are represented (literals are in DIMACS format):
+/// - `c bool <name> <lit>`: `name` is equivalent to `lit`.
+/// - `c int <name> order <v>:<lit> ...`: `lit` is true iff `name >= v`.
+/// - `c int <name> direct <v>:<lit> ...`: `lit` is true iff `name == v`.
+/// - `c int <name> log <lit> ...`: the literals are the bits of `name` from the least
+///   significant one.
+/// - `c bool <name> fixed <value>`, `c int <name> fixed <value>`: `name` is not encoded and
+///   takes `value` in every model.
+///
+/// If the problem is found to be inconsistent before SAT encoding, a CNF consisting of the empty
+/// clause is written instead.
+fn write_dimacs(out: &mut String, solver: &mut IntegratedSolver, vars: &[(String, Var)]) {
+    let cnf = match solver.to_dimacs() {
+        Some(cnf) => cnf,
+        None => {
+            writeln!(out, "c inconsistent").unwrap();
+            writeln!(out, "p cnf 0 1").unwrap();
+            writeln!(out, "0").unwrap();
+            return;
+        }
+    };
+    for (name, var) in vars {
+        match *var {
+            Var::Bool(var) => match solver.dimacs_bool_var_lits(var) {
+                DimacsVarLits::Bool(lit) => writeln!(out, "c bool {} {}", name, lit).unwrap(),
+                DimacsVarLits::Fixed(v) => {
+                    writeln!(out, "c bool {} fixed {}", name, v != 0).unwrap()
                 }
-            }
-            match solver.decide_irrefutable_facts(&bool_target, &int_target) {
-                Some(result) => {
-                    writeln!(&mut ret, "sat").unwrap();
-                    for target in &target_vars {
-                        match var_map.get_var(target).unwrap() {
-                            Var::Bool(var) => {
-                                if let Some(b) = result.get_bool(var) {
-                                    writeln!(&mut ret, "{} {}", target, b).unwrap();
-                                }
-                            }
-                            Var::Int(var) => {
-                                if let Some(i) = result.get_int(var) {
-                                    writeln!(&mut ret, "{} {}", target, i).unwrap();
-                                }
-                            }
+                _ => unreachable!(),
+            },
+            Var::Int(var) => {
+                write!(out, "c int {}", name).unwrap();
+                match solver.dimacs_int_var_lits(var) {
+                    DimacsVarLits::Order(lits) => {
+                        write!(out, " order").unwrap();
+                        for (v, lit) in lits {
+                            write!(out, " {}:{}", v, lit).unwrap();
                         }
                     }
-                }
-                None => writeln!(&mut ret, "unsat").unwrap(),
-            }
-        }
-        None => match solver.solve() {
-            Some(model) => {
-                writeln!(&mut ret, "s SATISFIABLE").unwrap();
-                for (name, &var) in var_map.iter() {
-                    match var {
-                        Var::Bool(var) => {
-                            writeln!(&mut ret, "a {}\t{}", name, model.get_bool(var)).unwrap()
+                    DimacsVarLits::Direct(lits) => {
+                        write!(out, " direct").unwrap();
+                        for (v, lit) in lits {
+                            write!(out, " {}:{}", v, lit).unwrap();
                         }
-                        Var::Int(var) => {
-                            writeln!(&mut ret, "a {}\t{}", name, model.get_int(var)).unwrap()
+                    }
+                    DimacsVarLits::Log(lits) => {
+                        write!(out, " log").unwrap();
+                        for lit in lits {
+                            write!(out, " {}", lit).unwrap();
                         }
                     }
+                    DimacsVarLits::Fixed(v) => write!(out, " fixed {}", v).unwrap(),
+                    DimacsVarLits::Bool(_) => unreachable!(),
                 }
-                writeln!(&mut ret, "a").unwrap();
+                writeln!(out).unwrap();
+            }
+        }
+    }
+    out.push_str(&cnf);
+}
+
+/// Writes `{"status":"sat","assignments":{...}}` or `{"status":"unsat"}`.
+/// If `objective` is given, the optimum value is written as `"objective":value` after `status`.
+/// Values are written as JSON booleans or numbers. Variable names need no escaping since the
+/// parser only accepts alphanumeric characters, `[`, `]` and `_` in identifiers.
+fn write_json(
+    out: &mut String,
+    assignments: Option<Vec<(String, String)>>,
+    objective: Option<i32>,
+) {
+    match assignments {
+        Some(assignments) => {
+            let assignments = assignments
+                .iter()
+                .map(|(name, value)| format!("\"{}\":{}", name, value))
+                .collect::<Vec<_>>()
+                .join(",");
+            let objective = match objective {
+                Some(value) => format!("\"objective\":{},", value),
+                None => String::new(),
+            };
+            writeln!(
+                out,
+                "{{\"status\":\"sat\",{}\"assignments\":{{{}}}}}",
+                objective, assignments
+            )
+            .unwrap();
+        }
+        None => writeln!(out, "{{\"status\":\"unsat\"}}").unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> String {
+        csugar_cli(&mut input.as_bytes(), Config::default()).0
+    }
+
+    #[test]
+    fn test_csugar_cli_abs() {
+        let input = "(int x -3 3)
+(int y -3 3)
+(== (abs (- x y)) 3)
+(>= x 3)
+#x y
+";
+        assert_eq!(run(input), "sat\nx 3\ny 0\n");
+
+        let input = "(int x -3 3)
+(int y -3 3)
+(<= (+ (abs x) (abs y)) 1)
+(== (abs x) 1)
+(> x 0)
+#x y
+";
+        assert_eq!(run(input), "sat\nx 1\ny 0\n");
+
+        let input = "(int x -3 3)
+(int y -3 3)
+(== (abs (- x y)) 7)
+";
+        assert_eq!(run(input), "s UNSATISFIABLE\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_alldifferent() {
+        // 2x2 fragment of a Latin square: rows and columns are all different
+        let input = "(int a 1 2)
+(int b 1 2)
+(int c 1 2)
+(int d 1 2)
+(alldifferent a b)
+(alldifferent c d)
+(alldifferent a c)
+(alldifferent b d)
+(== a 2)
+#a b c d
+";
+        assert_eq!(run(input), "sat\na 2\nb 1\nc 1\nd 2\n");
+
+        let input = "(int a 1 2)
+(int b 1 2)
+(int c 1 2)
+(int d 1 2)
+(alldifferent a b)
+(alldifferent c d)
+(alldifferent a c)
+(alldifferent b d)
+(== a d)
+(!= b c)
+";
+        assert_eq!(run(input), "s UNSATISFIABLE\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_comments() {
+        let input = "; a problem with comments
+(int x 0 3) ; the first variable
+// (== x 0)
+(int y 0 3)
+   ; (== y 0)
+(== (+ x y) 5) ;; sum
+(> x y);
+#x y ; targets
+";
+        assert_eq!(run(input), "sat\nx 3\ny 2\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_malformed_lines() {
+        // malformed lines are reported and skipped
+        let input = "(int x 0 3)
+(int y 0 3)
+(== x (+ y z))
+(>= x 2
+(== x 0))
+(== x (+ y 1))
+(== y 2)
+#x y
+";
+        assert_eq!(run(input), "sat\nx 3\ny 2\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_bad_inputs() {
+        let input = "(int x 0 3)
+(bool b)
+(== x 2)
+#x b y
+";
+        let res = try_csugar_cli(&mut input.as_bytes(), Config::default());
+        assert_eq!(
+            res.err(),
+            Some(String::from("undeclared variable in the target line: y"))
+        );
+
+        let input = "(int x 0 3)
+#x
+#x
+";
+        let res = try_csugar_cli(&mut input.as_bytes(), Config::default());
+        assert_eq!(
+            res.err(),
+            Some(String::from("line 3: duplicate target line"))
+        );
+
+        // invalid UTF-8 makes `read_line` fail
+        let input: &[u8] = b"(int x 0 3)\n(== x \xff)\n";
+        let res = try_csugar_cli(&mut &input[..], Config::default());
+        assert!(res.err().unwrap().starts_with("failed to read line 2"));
+
+        // redeclarations and empty lines are skipped
+        let input = "(int x 0 3)
+
+(int x 5 6)
+(bool x)
+(>= x 3)
+# x
+";
+        assert_eq!(run(input), "sat\nx 3\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_json() {
+        let run_json = |input: &str| {
+            try_csugar_cli_with_format(&mut input.as_bytes(), Config::default(), OutputFormat::Json)
+                .unwrap()
+                .0
+        };
+
+        let input = "(int x 0 3)
+(bool b)
+(int y 0 3)
+(== x (+ y 2))
+(>= y 1)
+(iff b (< x 3))
+";
+        assert_eq!(
+            run_json(input),
+            "{\"status\":\"sat\",\"assignments\":{\"b\":false,\"x\":3,\"y\":1}}\n"
+        );
+
+        let input = "(int x 0 3)
+(bool b)
+(int y 0 3)
+(== x (+ y 2))
+(iff b (< x 3))
+#x y b
+";
+        assert_eq!(run_json(input), "{\"status\":\"sat\",\"assignments\":{}}\n");
+
+        let input = "(int x 0 3)
+(bool b)
+(int y 0 3)
+(== x (+ y 2))
+(iff b (< x 3))
+(!= y 0)
+#x y b
+";
+        assert_eq!(
+            run_json(input),
+            "{\"status\":\"sat\",\"assignments\":{\"x\":3,\"y\":1,\"b\":false}}\n"
+        );
+
+        let input = "(int x 0 3)
+(> x 3)
+";
+        assert_eq!(run_json(input), "{\"status\":\"unsat\"}\n");
+        let input = "(int x 0 3)
+(> x 3)
+#x
+";
+        assert_eq!(run_json(input), "{\"status\":\"unsat\"}\n");
+    }
+
+    #[test]
+    fn test_csugar_cli_file() {
+        let path = std::env::temp_dir().join(format!(
+            "enigma_csp_test_csugar_cli_file_{}.csp",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "(int x 0 3)
+(int y 0 3)
+(== (+ x y) 5)
+(> x y)
+",
+        )
+        .unwrap();
+        let res = try_csugar_cli_file(&path, Config::default(), OutputFormat::Text);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(res.unwrap().0, "s SATISFIABLE\na x\t3\na y\t2\na\n");
+
+        let res = try_csugar_cli_file(&path, Config::default(), OutputFormat::Text);
+        assert!(res.err().unwrap().starts_with("failed to open"));
+    }
+
+    #[test]
+    fn test_csugar_cli_dimacs() {
+        let run_dimacs = |input: &str| {
+            try_csugar_cli_with_format(
+                &mut input.as_bytes(),
+                Config::default(),
+                OutputFormat::Dimacs,
+            )
+            .unwrap()
+            .0
+        };
+
+        let input = "(int x 0 3)
+(int y 0 3)
+(bool b)
+(int z 0 5)
+(=> b (>= (+ x y) 4))
+(|| b (== x y))
+#x b z
+";
+        let out = run_dimacs(input);
+
+        let mut header = None;
+        let mut comments = vec![];
+        let mut clauses = vec![];
+        for line in out.lines() {
+            if let Some(comment) = line.strip_prefix("c ") {
+                comments.push(comment);
+            } else if let Some(h) = line.strip_prefix("p cnf ") {
+                assert!(header.is_none());
+                let h = h
+                    .split(' ')
+                    .map(|t| t.parse::<usize>().unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(h.len(), 2);
+                header = Some((h[0], h[1]));
+            } else {
+                assert!(header.is_some());
+                let mut clause = line
+                    .split_whitespace()
+                    .map(|t| t.parse::<i32>().unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(clause.pop(), Some(0));
+                clauses.push(clause);
+            }
+        }
+        let (num_vars, num_clauses) = header.unwrap();
+        assert_eq!(clauses.len(), num_clauses);
+        for clause in &clauses {
+            for &l in clause {
+                assert!(l != 0 && l.unsigned_abs() as usize <= num_vars);
             }
-            None => writeln!(&mut ret, "s UNSATISFIABLE").unwrap(),
-        },
+        }
+
+        assert_eq!(comments.len(), 5);
+        let x_desc = comments[0].split(' ').collect::<Vec<_>>();
+        assert_eq!(&x_desc[..3], &["int", "x", "order"]);
+        let thresholds = x_desc[3..]
+            .iter()
+            .map(|t| t.split(':').next().unwrap().parse::<i32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(thresholds, vec![1, 2, 3]);
+        assert!(comments[1].starts_with("bool b "));
+        assert_eq!(comments[2], "int z fixed 0");
+
+        let input = "(int x 0 3)
+(> x 3)
+";
+        assert_eq!(run_dimacs(input), "c inconsistent\np cnf 0 1\n0\n");
     }
 
-    (ret, perf_stats)
+    #[test]
+    fn test_csugar_cli_optimization() {
+        // knapsack: the optimum is 9 with items 1 and 2 (weights 3 + 4, values 4 + 5)
+        let problem = "(bool a)
+(bool b)
+(bool c)
+(int w 0 7)
+(int v 0 20)
+(== w (+ (if a 2 0) (if b 3 0) (if c 4 0)))
+(== v (+ (if a 3 0) (if b 4 0) (if c 5 0)))
+";
+        let input = format!("{}(maximize v)\n", problem);
+        assert_eq!(
+            run(&input),
+            "o 9\ns OPTIMUM FOUND\na a\tfalse\na b\ttrue\na c\ttrue\na v\t9\na w\t7\na\n"
+        );
+
+        let input = format!("{}(maximize (- (* 2 v) w))\n#v b\n", problem);
+        assert_eq!(run(&input), "o 11\ns OPTIMUM FOUND\na v\t9\na b\ttrue\na\n");
+
+        let input = format!("{}(minimize (+ v w))\n(|| a b)\n#a b c\n", problem);
+        let res = try_csugar_cli_with_format(
+            &mut input.as_bytes(),
+            Config::default(),
+            OutputFormat::Json,
+        )
+        .unwrap()
+        .0;
+        assert_eq!(
+            res,
+            "{\"status\":\"sat\",\"objective\":5,\"assignments\":{\"a\":true,\"b\":false,\"c\":false}}\n"
+        );
+
+        let input = format!("{}(minimize v)\n(maximize w)\n", problem);
+        let res = try_csugar_cli(&mut input.as_bytes(), Config::default());
+        assert_eq!(res.err(), Some(String::from("line 9: duplicate objective")));
+
+        let input = format!("{}(minimize v)\n(>= v 13)\n", problem);
+        assert_eq!(run(&input), "s UNSATISFIABLE\n");
+    }
 }
@@ -1,6 +1,6 @@
 use crate::arithmetic::CheckedInt;
 use crate::util::UpdateStatus;
-use std::ops::{Add, BitOr, Mul};
+use std::ops::{Add, BitAnd, BitOr, Mul};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Domain {
@@ -243,3 +243,28 @@ impl BitOr<Domain> for Domain {
         }
     }
 }
+
+impl BitAnd<Domain> for Domain {
+    type Output = Domain;
+
+    fn bitand(self, rhs: Domain) -> Domain {
+        match (self, rhs) {
+            (Domain::Range(low1, high1), Domain::Range(low2, high2)) => {
+                Domain::Range(low1.max(low2), high1.min(high2))
+            }
+            (Domain::Range(low, high), Domain::Enumerative(cands))
+            | (Domain::Enumerative(cands), Domain::Range(low, high)) => Domain::Enumerative(
+                cands
+                    .into_iter()
+                    .filter(|&x| low <= x && x <= high)
+                    .collect(),
+            ),
+            (Domain::Enumerative(cands1), Domain::Enumerative(cands2)) => Domain::Enumerative(
+                cands1
+                    .into_iter()
+                    .filter(|x| cands2.binary_search(x).is_ok())
+                    .collect(),
+            ),
+        }
+    }
+}
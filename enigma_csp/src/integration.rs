@@ -7,9 +7,13 @@ use super::csp::{
 use super::encoder::{encode, EncodeMap};
 use super::norm_csp::NormCSP;
 use super::normalizer::{normalize, NormalizeMap};
-use super::sat::{SATModel, SAT};
+use super::sat::{Lit, SATMark, SATModel, SAT};
 use crate::domain::Domain;
 use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Debug)]
 pub struct PerfStats {
@@ -58,6 +62,56 @@ impl PerfStats {
     }
 }
 
+/// Statistics of the last call to the SAT solver.
+/// Counters are `None` if the SAT backend does not report them.
+#[derive(Clone, Debug, Default)]
+pub struct Statistics {
+    pub decisions: Option<u64>,
+    pub conflicts: Option<u64>,
+    pub propagations: Option<u64>,
+    pub restarts: Option<u64>,
+    /// Number of learnt clauses kept by the SAT solver after the call.
+    pub learnts: Option<u64>,
+    /// Number of problem clauses kept by the SAT solver after the call.
+    pub clauses: Option<u64>,
+    pub solve_time: Duration,
+}
+
+impl Statistics {
+    /// Adds the counters and the time of `other`, which is a later call than those in `self`.
+    /// `learnts` and `clauses` are replaced by those of `other`.
+    pub fn accumulate(&mut self, other: &Statistics) {
+        let add = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.decisions = add(self.decisions, other.decisions);
+        self.conflicts = add(self.conflicts, other.conflicts);
+        self.propagations = add(self.propagations, other.propagations);
+        self.restarts = add(self.restarts, other.restarts);
+        self.learnts = other.learnts;
+        self.clauses = other.clauses;
+        self.solve_time += other.solve_time;
+    }
+}
+
+/// How a CSP variable is represented in the output of `IntegratedSolver::to_dimacs`.
+/// Literals are in DIMACS format (1-based, negative for negated literals).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DimacsVarLits {
+    /// A Boolean variable which is equivalent to the literal.
+    Bool(i32),
+    /// `(v, l)`: `l` is true iff the variable is at least `v`.
+    Order(Vec<(i32, i32)>),
+    /// `(v, l)`: `l` is true iff the variable is equal to `v`.
+    Direct(Vec<(i32, i32)>),
+    /// The `i`-th literal is the `i`-th bit (from the least significant one) of the value.
+    Log(Vec<i32>),
+    /// The variable is not encoded and takes this value (0 or 1 for Boolean variables) in
+    /// every model.
+    Fixed(i32),
+}
+
 pub struct IntegratedSolver<'a> {
     csp: CSP,
     normalize_map: NormalizeMap,
@@ -65,8 +119,29 @@ pub struct IntegratedSolver<'a> {
     encode_map: EncodeMap,
     sat: SAT,
     already_used: bool,
+    unsat_core: Vec<BoolVar>,
+    soft_constraints: Vec<(BoolVar, usize)>,
     config: Config,
     perf_stats: Option<&'a PerfStats>,
+    statistics: Statistics,
+    total_statistics: Statistics,
+    scopes: Vec<Scope>,
+    /// If set, every call to the SAT solver gives up after this (see `run_sat_solver`).
+    deadline: Option<Instant>,
+    timed_out: bool,
+    /// Set if a call to the SAT solver is cancelled by the flag of `interrupt_handle`.
+    cancelled: bool,
+}
+
+/// State of `IntegratedSolver` saved by `push`.
+struct Scope {
+    csp: CSP,
+    normalize_map: NormalizeMap,
+    norm: NormCSP,
+    encode_map: EncodeMap,
+    sat_mark: SATMark,
+    already_used: bool,
+    num_soft_constraints: usize,
 }
 
 impl<'a> IntegratedSolver<'a> {
@@ -80,20 +155,38 @@ impl<'a> IntegratedSolver<'a> {
             normalize_map: NormalizeMap::new(),
             norm: NormCSP::new(),
             encode_map: EncodeMap::new(),
-            sat: SAT::new_with_backend(config.backend),
+            sat: SAT::new_with_backend(&config.backend),
             already_used: false,
+            unsat_core: vec![],
+            soft_constraints: vec![],
             config,
             perf_stats: None,
+            statistics: Statistics::default(),
+            total_statistics: Statistics::default(),
+            scopes: vec![],
+            deadline: None,
+            timed_out: false,
+            cancelled: false,
         };
-        ret.sat.set_rnd_init_act(ret.config.glucose_rnd_init_act);
-        ret.sat
-            .set_dump_analysis_info(ret.config.dump_analysis_info);
-        if let Some(seed) = ret.config.glucose_random_seed {
-            ret.sat.set_seed(seed);
+        if ret.config.enable_push_pop {
+            ret.sat.enable_recording();
+        }
+        if ret.config.portfolio > 1 {
+            ret.sat.set_portfolio(ret.config.portfolio);
         }
+        ret.apply_sat_settings();
         ret
     }
 
+    fn apply_sat_settings(&mut self) {
+        self.sat.set_rnd_init_act(self.config.glucose_rnd_init_act);
+        self.sat
+            .set_dump_analysis_info(self.config.dump_analysis_info);
+        if let Some(seed) = self.config.glucose_random_seed {
+            self.sat.set_seed(seed);
+        }
+    }
+
     pub fn new_bool_var(&mut self) -> BoolVar {
         self.csp.new_bool_var()
     }
@@ -118,6 +211,66 @@ impl<'a> IntegratedSolver<'a> {
         self.add_constraint(Stmt::Expr(expr))
     }
 
+    /// Adds `stmt` and immediately encodes it into the current SAT instance.
+    /// Variables which are already encoded keep their encodings, so the SAT solver (including
+    /// its learned clauses) remains valid and subsequent calls of `solve` continue from it.
+    /// Returns `false` if the problem is found to be inconsistent during the encoding.
+    pub fn add_constraint_incremental(&mut self, stmt: Stmt) -> bool {
+        self.add_constraint(stmt);
+        self.encode()
+    }
+
+    /// Saves the current set of variables and constraints, which is restored by the matching
+    /// `pop`. Scopes can be nested.
+    /// `Config::enable_push_pop` must be set, and only the Glucose backend is supported.
+    pub fn push(&mut self) {
+        assert!(
+            self.config.enable_push_pop,
+            "push requires Config::enable_push_pop"
+        );
+        self.scopes.push(Scope {
+            csp: self.csp.clone(),
+            normalize_map: self.normalize_map.clone(),
+            norm: self.norm.clone(),
+            encode_map: self.encode_map.clone(),
+            sat_mark: self.sat.mark(),
+            already_used: self.already_used,
+            num_soft_constraints: self.soft_constraints.len(),
+        });
+    }
+
+    /// Discards all the variables and constraints added since the last `push`.
+    /// Variables created after the `push` must not be used afterwards.
+    /// The SAT solver is rebuilt, so learned clauses are not kept, while phase hints and solver
+    /// settings are restored.
+    pub fn pop(&mut self) {
+        let scope = self.scopes.pop().expect("pop without matching push");
+        self.csp = scope.csp;
+        self.normalize_map = scope.normalize_map;
+        self.norm = scope.norm;
+        self.encode_map = scope.encode_map;
+        self.sat.truncate(scope.sat_mark);
+        self.apply_sat_settings();
+        self.already_used = scope.already_used;
+        self.soft_constraints.truncate(scope.num_soft_constraints);
+        self.unsat_core.clear();
+    }
+
+    /// Adds `expr` as a soft constraint, which may be violated at the cost of `weight`.
+    /// Soft constraints are taken into account only by `solve_max`; other methods ignore them.
+    pub fn add_soft_expr(&mut self, expr: BoolExpr, weight: usize) {
+        // `relax` is true if `expr` is allowed to be violated
+        let relax = self.new_bool_var();
+        self.add_expr(relax.expr() | expr);
+        self.soft_constraints.push((relax, weight));
+    }
+
+    /// Returns the auxiliary variables introduced by `add_soft_expr`, which are not regarded as
+    /// variables of the problem when solutions are counted or enumerated.
+    fn soft_relax_vars(&self) -> BTreeSet<BoolVar> {
+        self.soft_constraints.iter().map(|&(var, _)| var).collect()
+    }
+
     pub fn encode(&mut self) -> bool {
         let is_first = !self.already_used;
         self.already_used = true;
@@ -149,6 +302,10 @@ impl<'a> IntegratedSolver<'a> {
         if is_first && self.config.use_norm_domain_refinement {
             self.norm.refine_domain();
         }
+        if self.config.merge_equivalent_variables {
+            self.norm.merge_equal_int_vars();
+        }
+        self.norm.inline_constant_int_vars();
         if self.norm.is_inconsistent() {
             return false;
         }
@@ -180,22 +337,533 @@ impl<'a> IntegratedSolver<'a> {
         true
     }
 
-    pub fn solve<'b>(&'b mut self) -> Option<Model<'b>> {
+    /// Makes the SAT instance exportable by `to_dimacs`.
+    /// This must be called before any constraint is encoded.
+    pub fn enable_dimacs_export(&mut self) {
+        assert!(
+            !self.already_used,
+            "enable_dimacs_export must be called before encoding"
+        );
+        self.sat.enable_recording();
+    }
+
+    /// Encodes the problem and returns the SAT instance in DIMACS format (see `SAT::to_dimacs`),
+    /// or None if the problem is found to be inconsistent before SAT encoding.
+    /// `enable_dimacs_export` must have been called. `dimacs_bool_var_lits` and
+    /// `dimacs_int_var_lits` tell how the variables are represented in the output.
+    pub fn to_dimacs(&mut self) -> Option<String> {
         if !self.encode() {
             return None;
         }
+        Some(self.sat.to_dimacs())
+    }
+
+    pub fn dimacs_bool_var_lits(&self, var: BoolVar) -> DimacsVarLits {
+        let value = match self.normalize_map.get_bool_var(var) {
+            Some(norm_lit) => match self.encode_map.get_bool_lit(norm_lit) {
+                Some(lit) => return DimacsVarLits::Bool(lit.to_dimacs()),
+                None => false, // unused variable optimization
+            },
+            None => match self.csp.get_bool_var_status(var) {
+                BoolVarStatus::Infeasible => panic!(),
+                BoolVarStatus::Fixed(v) => v,
+                BoolVarStatus::Unfixed => false, // unused variable optimization
+            },
+        };
+        DimacsVarLits::Fixed(value as i32)
+    }
+
+    pub fn dimacs_int_var_lits(&self, var: IntVar) -> DimacsVarLits {
+        let value = match self.normalize_map.get_int_var(var) {
+            Some(norm_var) => {
+                if let Some((domain, lits)) = self.encode_map.int_var_order_encoding(norm_var) {
+                    return DimacsVarLits::Order(
+                        domain[1..]
+                            .iter()
+                            .zip(lits)
+                            .map(|(v, l)| (v.get(), l.to_dimacs()))
+                            .collect(),
+                    );
+                }
+                if let Some((domain, lits)) = self.encode_map.int_var_direct_encoding(norm_var) {
+                    return DimacsVarLits::Direct(
+                        domain
+                            .iter()
+                            .zip(lits)
+                            .map(|(v, l)| (v.get(), l.to_dimacs()))
+                            .collect(),
+                    );
+                }
+                if let Some(lits) = self.encode_map.int_var_log_encoding(norm_var) {
+                    return DimacsVarLits::Log(lits.iter().map(|l| l.to_dimacs()).collect());
+                }
+                // unused variable optimization
+                self.norm.vars.int_var(norm_var).lower_bound_checked()
+            }
+            None => match self.csp.get_int_var_status(var) {
+                IntVarStatus::Infeasible => panic!(),
+                IntVarStatus::Fixed(v) => v,
+                IntVarStatus::Unfixed(v) => v,
+            },
+        };
+        DimacsVarLits::Fixed(value.get())
+    }
+
+    pub fn solve<'b>(&'b mut self) -> Option<Model<'b>> {
+        self.solve_under_assumptions(&[])
+    }
+
+    /// Same as `solve`, but gives up if it does not finish within `timeout` (including the time
+    /// for normalization and encoding). After timeout, the solver is still usable.
+    /// See `SAT::solve_without_model_with_timeout` for the configurations supporting timeout.
+    pub fn solve_with_timeout<'b>(&'b mut self, timeout: Duration) -> SolveOutcome<'b> {
+        self.solve_with_limit(Some(timeout))
+    }
+
+    /// Same as `solve`, but reports `SolveOutcome::Cancelled` if solving is cancelled by
+    /// the flag returned by `interrupt_handle` (which `solve` reports only by `was_cancelled`).
+    pub fn solve_cancellable<'b>(&'b mut self) -> SolveOutcome<'b> {
+        self.solve_with_limit(None)
+    }
+
+    fn solve_with_limit<'b>(&'b mut self, timeout: Option<Duration>) -> SolveOutcome<'b> {
         let start = std::time::Instant::now();
-        let solver_result = if self.sat.solve_without_model() {
-            Some(unsafe { self.sat.model() })
+        self.unsat_core.clear();
+        self.cancelled = false;
+        if !self.encode() {
+            return SolveOutcome::Unsat;
+        }
+
+        let remaining = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+        match self.run_sat_solver_with_timeout(&[], remaining) {
+            Some(true) => SolveOutcome::Sat(self.model()),
+            Some(false) => SolveOutcome::Unsat,
+            None if self.sat.is_interrupted() => SolveOutcome::Cancelled,
+            None => SolveOutcome::Timeout,
+        }
+    }
+
+    /// Returns the flag for cancelling solving from another thread.
+    /// Once the flag is set to true, the SAT solver stops shortly (the flag is checked every
+    /// fixed number of conflicts). Then `solve_cancellable` and `solve_with_timeout` return
+    /// `SolveOutcome::Cancelled`, `decide_irrefutable_facts_with_timeout` returns
+    /// `IrrefutableFactsOutcome::Cancelled` and `is_unique` returns `Uniqueness::Cancelled`.
+    /// The other methods return no (more) solution (e.g. `solve` returns `None`, `enumerate`
+    /// stops and `count_solutions` returns the number of solutions found so far), and
+    /// `was_cancelled` tells that this is due to cancellation.
+    /// The flag is not cleared automatically, so it must be reset to false for solving again.
+    /// See `SAT::interrupt_flag` for the configurations supporting this.
+    pub fn interrupt_handle(&mut self) -> Arc<AtomicBool> {
+        self.sat.interrupt_flag()
+    }
+
+    /// Returns whether the last call of a solving method (such as `solve`, `minimize`,
+    /// `count_solutions` or `next` of the iterator returned by `enumerate`) was cancelled by the
+    /// flag of `interrupt_handle`, in which case its result does not mean unsatisfiability.
+    pub fn was_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Solves the problem assuming that all of `assumptions` are true.
+    /// Unlike constraints added by `add_expr`, the assumptions are effective only in this call.
+    /// If the problem is unsatisfiable, `unsat_core` tells which assumptions are responsible.
+    /// Therefore, conflicting constraints can be identified by guarding each of them by a fresh
+    /// Boolean variable `s` as `s.expr().imp(constraint)` and passing these variables here.
+    pub fn solve_under_assumptions<'b>(&'b mut self, assumptions: &[BoolVar]) -> Option<Model<'b>> {
+        self.unsat_core.clear();
+        self.cancelled = false;
+        if !self.encode() {
+            return None;
+        }
+
+        let mut sat_assumptions = vec![];
+        let mut assumption_vars = vec![];
+        for &var in assumptions {
+            match self.normalize_map.get_bool_var(var) {
+                Some(norm_lit) => {
+                    let lit =
+                        self.encode_map
+                            .convert_bool_lit(&self.norm.vars, &mut self.sat, norm_lit);
+                    sat_assumptions.push(lit);
+                    assumption_vars.push(var);
+                }
+                None => match self.csp.get_bool_var_status(var) {
+                    BoolVarStatus::Infeasible => panic!(),
+                    BoolVarStatus::Fixed(true) => (),
+                    BoolVarStatus::Fixed(false) => {
+                        self.unsat_core.push(var);
+                        return None;
+                    }
+                    BoolVarStatus::Unfixed => (), // unused variable optimization
+                },
+            }
+        }
+
+        let is_sat = self.run_sat_solver(&sat_assumptions);
+        if !is_sat {
+            for lit in self.sat.unsat_core() {
+                for i in 0..sat_assumptions.len() {
+                    if sat_assumptions[i].0 == lit.0
+                        && !self.unsat_core.contains(&assumption_vars[i])
+                    {
+                        self.unsat_core.push(assumption_vars[i]);
+                    }
+                }
+            }
+            return None;
+        }
+        Some(self.model())
+    }
+
+    /// Finds a model maximizing the total weight of satisfied soft constraints (added by
+    /// `add_soft_expr`), and returns it together with the achieved weight.
+    /// Returns `None` if the hard constraints are unsatisfiable or solving is cancelled (see
+    /// `was_cancelled`).
+    /// The optimization is a linear search on the weight of violated soft constraints. This weight
+    /// is encoded once by a weighted totalizer, and each bound is imposed by assuming some of its
+    /// outputs to be false, so the problem itself is not modified.
+    pub fn solve_max<'b>(&'b mut self) -> Option<(Model<'b>, usize)> {
+        self.unsat_core.clear();
+        self.cancelled = false;
+        if !self.encode() {
+            return None;
+        }
+
+        let mut total_weight = 0;
+        let mut fixed_violation = 0;
+        let mut relax_lits = vec![];
+        for i in 0..self.soft_constraints.len() {
+            let (var, weight) = self.soft_constraints[i];
+            total_weight += weight;
+            match self.normalize_map.get_bool_var(var) {
+                Some(norm_lit) => {
+                    if weight > 0 {
+                        let lit = self.encode_map.convert_bool_lit(
+                            &self.norm.vars,
+                            &mut self.sat,
+                            norm_lit,
+                        );
+                        relax_lits.push((lit, weight));
+                    }
+                }
+                None => match self.csp.get_bool_var_status(var) {
+                    BoolVarStatus::Infeasible => return None,
+                    BoolVarStatus::Fixed(true) => fixed_violation += weight,
+                    BoolVarStatus::Fixed(false) => (),
+                    BoolVarStatus::Unfixed => (), // unused variable optimization
+                },
+            }
+        }
+
+        if !self.run_sat_solver(&[]) {
+            return None;
+        }
+        let count_violation = |sat: &SAT, relax_lits: &[(Lit, usize)]| {
+            let model = unsafe { sat.model() };
+            relax_lits
+                .iter()
+                .filter(|&&(lit, _)| model.assignment_lit(lit))
+                .map(|&(_, weight)| weight)
+                .sum::<usize>()
+        };
+        let mut best_violation = count_violation(&self.sat, &relax_lits);
+        if best_violation == 0 {
+            return Some((self.model(), total_weight - fixed_violation));
+        }
+
+        // only violations not exceeding that of the first model are of interest, so the sums
+        // above it are not distinguished
+        let sums = self.add_weighted_totalizer(&relax_lits, best_violation + 1);
+        let at_most = |k: usize| {
+            sums.iter()
+                .filter(|&&(sum, _)| sum > k)
+                .map(|&(_, lit)| !lit)
+                .collect::<Vec<_>>()
+        };
+        let mut is_model_current = true;
+        while best_violation > 0 {
+            if !self.run_sat_solver(&at_most(best_violation - 1)) {
+                if self.cancelled {
+                    return None;
+                }
+                is_model_current = false;
+                break;
+            }
+            best_violation = count_violation(&self.sat, &relax_lits);
+        }
+
+        if !is_model_current && !self.run_sat_solver(&at_most(best_violation)) {
+            // the problem with this bound is known to be satisfiable, so solving has been cancelled
+            // or timed out
+            return None;
+        }
+        Some((
+            self.model(),
+            total_weight - fixed_violation - best_violation,
+        ))
+    }
+
+    /// Finds a model minimizing the value of `objective`, and returns it together with the
+    /// minimum value. Returns `None` if the problem is unsatisfiable or solving is cancelled (see
+    /// `was_cancelled`).
+    /// This is a binary search on the objective value. If `objective` is order-encoded, each step
+    /// just assumes one of the threshold literals of the encoding; otherwise a bound guarded by a
+    /// fresh variable is added. In either case the problem itself is not modified.
+    pub fn minimize<'b>(&'b mut self, objective: IntVar) -> Option<(Model<'b>, i32)> {
+        self.optimize(objective, false)
+    }
+
+    /// Finds a model maximizing the value of `objective`, and returns it together with the
+    /// maximum value. Returns `None` if the problem is unsatisfiable.
+    /// See `minimize` for the details.
+    pub fn maximize<'b>(&'b mut self, objective: IntVar) -> Option<(Model<'b>, i32)> {
+        self.optimize(objective, true)
+    }
+
+    /// Same as `minimize`, but the objective is an arbitrary integer expression.
+    /// Unless `objective` is a variable, a fresh variable equal to `objective` is added to the
+    /// problem, which does not change the set of solutions.
+    pub fn minimize_expr<'b>(&'b mut self, objective: IntExpr) -> Option<(Model<'b>, i32)> {
+        let var = self.objective_var(objective);
+        self.optimize(var, false)
+    }
+
+    /// Same as `maximize`, but the objective is an arbitrary integer expression.
+    /// See `minimize_expr` for the details.
+    pub fn maximize_expr<'b>(&'b mut self, objective: IntExpr) -> Option<(Model<'b>, i32)> {
+        let var = self.objective_var(objective);
+        self.optimize(var, true)
+    }
+
+    fn objective_var(&mut self, objective: IntExpr) -> IntVar {
+        if let IntExpr::Var(v) = objective {
+            return v;
+        }
+        let (low, high) = self.csp.int_expr_bounds(&objective);
+        let var = self.csp.new_int_var(Domain::range_from_checked(low, high));
+        self.add_expr(var.expr().eq(objective));
+        var
+    }
+
+    /// Finds a model minimizing `objectives` lexicographically, and returns it together with the
+    /// optimum values of `objectives`. Returns `None` if the problem is unsatisfiable.
+    /// The objectives are minimized one by one with `minimize`, and each optimum value is added to
+    /// the problem as a constraint before the next objective is minimized. Therefore, unlike
+    /// `minimize`, this modifies the problem.
+    pub fn minimize_lex<'b>(&'b mut self, objectives: &[IntVar]) -> Option<(Model<'b>, Vec<i32>)> {
+        let mut optimum = vec![];
+        for &objective in objectives {
+            let (_, value) = self.minimize(objective)?;
+            self.add_expr(objective.expr().eq(IntExpr::Const(value)));
+            optimum.push(value);
+        }
+        let model = self.solve()?;
+        Some((model, optimum))
+    }
+
+    fn optimize<'b>(&'b mut self, objective: IntVar, maximize: bool) -> Option<(Model<'b>, i32)> {
+        self.unsat_core.clear();
+        self.cancelled = false;
+        if !self.encode() || !self.run_sat_solver(&[]) {
+            return None;
+        }
+
+        // the optimum value is in [low, high], and `best` is achieved by the last model found
+        let domain = &self.csp.vars.int_var(objective).domain;
+        let mut low = domain.lower_bound_checked().get();
+        let mut high = domain.upper_bound_checked().get();
+        let mut best = self.model().get_int(objective);
+        let mut is_model_current = true;
+        loop {
+            if maximize {
+                low = best;
+            } else {
+                high = best;
+            }
+            if low >= high {
+                break;
+            }
+            let mid = if maximize {
+                (low as i64 + high as i64 + 1).div_euclid(2) as i32
+            } else {
+                (low as i64 + high as i64).div_euclid(2) as i32
+            };
+            if self.run_sat_solver_with_bound(objective, mid, maximize) {
+                best = self.model().get_int(objective);
+                is_model_current = true;
+            } else if self.cancelled {
+                return None;
+            } else {
+                if maximize {
+                    high = mid - 1;
+                } else {
+                    low = mid + 1;
+                }
+                is_model_current = false;
+            }
+        }
+
+        if !is_model_current && !self.run_sat_solver_with_bound(objective, best, maximize) {
+            // the model achieving `best` is found again unless solving is cancelled
+            return None;
+        }
+        Some((self.model(), best))
+    }
+
+    /// Runs the SAT solver under the condition that `objective` is at least (if `lower` is true)
+    /// or at most (otherwise) `bound`, which does not remain after this call.
+    fn run_sat_solver_with_bound(&mut self, objective: IntVar, bound: i32, lower: bool) -> bool {
+        if let Some(norm_var) = self.normalize_map.get_int_var(objective) {
+            if let Some((domain, lits)) = self.encode_map.int_var_order_encoding(norm_var) {
+                // `objective >= domain[i]` is represented by `lits[i - 1]` for 0 < i < n
+                let n = domain.len();
+                let assumption = if lower {
+                    match domain.partition_point(|&d| d.get() < bound) {
+                        0 => None,
+                        i if i == n => return false,
+                        i => Some(lits[i - 1]),
+                    }
+                } else {
+                    // `objective <= bound` is equivalent to `!(objective >= domain[i])`,
+                    // where `domain[i]` is the smallest value greater than `bound`
+                    match domain.partition_point(|&d| d.get() <= bound) {
+                        0 => return false,
+                        i if i == n => None,
+                        i => Some(!lits[i - 1]),
+                    }
+                };
+                return self.run_sat_solver(&assumption.into_iter().collect::<Vec<_>>());
+            }
+        }
+
+        // `objective` is not order-encoded (e.g. log-encoded), so the bound is encoded as
+        // a constraint which is effective only if `guard` is assumed
+        let guard = self.new_bool_var();
+        let cond = if lower {
+            objective.expr().ge(IntExpr::Const(bound))
         } else {
-            None
+            objective.expr().le(IntExpr::Const(bound))
+        };
+        self.add_expr(guard.expr().imp(cond));
+        let is_sat = self.solve_under_assumptions(&[guard]).is_some();
+        // `guard` is fixed to false so that it does not remain as a free variable, which would
+        // double the number of solutions (e.g. in `count_solutions`)
+        self.add_expr(!guard.expr());
+        is_sat
+    }
+
+    /// Adds the constraint that at most `k` of `lits` are true, which is effective only if the
+    /// returned literal is assumed.
+    /// Encodes the total weight of the true literals in `lits` by a generalized totalizer.
+    /// Returns the pairs `(sum, out)` such that `out` is true if the total weight, capped at `cap`,
+    /// is at least `sum`. Nothing else is imposed on `out`, so the encoding does not constrain
+    /// `lits` unless some of the outputs are assumed to be false.
+    fn add_weighted_totalizer(&mut self, lits: &[(Lit, usize)], cap: usize) -> Vec<(usize, Lit)> {
+        if lits.len() == 1 {
+            let (lit, weight) = lits[0];
+            return vec![(weight.min(cap), lit)];
+        }
+        let mid = lits.len() / 2;
+        let left = self.add_weighted_totalizer(&lits[..mid], cap);
+        let right = self.add_weighted_totalizer(&lits[mid..], cap);
+
+        let mut sums = BTreeSet::new();
+        for &(a, _) in &left {
+            sums.insert(a);
+            for &(b, _) in &right {
+                sums.insert((a + b).min(cap));
+            }
+        }
+        for &(b, _) in &right {
+            sums.insert(b);
+        }
+        let outputs = sums
+            .into_iter()
+            .map(|sum| {
+                #[cfg(not(feature = "sat-analyzer"))]
+                let out = self.sat.new_var().as_lit(false);
+                #[cfg(feature = "sat-analyzer")]
+                let out = self.sat.new_var("weighted_totalizer").as_lit(false);
+                (sum, out)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        for &(a, la) in left.iter().chain(right.iter()) {
+            self.sat.add_clause(&[!la, outputs[&a]]);
+        }
+        for &(a, la) in &left {
+            for &(b, lb) in &right {
+                self.sat.add_clause(&[!la, !lb, outputs[&(a + b).min(cap)]]);
+            }
+        }
+        outputs.into_iter().collect()
+    }
+
+    /// Returns `false` also if the SAT solver is cancelled (in which case `cancelled` is set), or
+    /// gives up due to `deadline` (in which case `timed_out` is set).
+    fn run_sat_solver(&mut self, assumptions: &[Lit]) -> bool {
+        let timeout = match self.deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    self.timed_out = true;
+                    return false;
+                }
+                Some(deadline - now)
+            }
+            None => None,
+        };
+        match self.run_sat_solver_with_timeout(assumptions, timeout) {
+            Some(is_sat) => is_sat,
+            None => {
+                if self.sat.is_interrupted() {
+                    self.cancelled = true;
+                } else if timeout.is_some() {
+                    self.timed_out = true;
+                }
+                false
+            }
+        }
+    }
+
+    /// Returns `None` if the SAT solver gives up due to `timeout` or cancellation.
+    fn run_sat_solver_with_timeout(
+        &mut self,
+        assumptions: &[Lit],
+        timeout: Option<Duration>,
+    ) -> Option<bool> {
+        let stats_before = self.sat.stats();
+        let start = std::time::Instant::now();
+        let mut is_sat = match timeout {
+            Some(timeout) => self
+                .sat
+                .solve_without_model_with_timeout(assumptions, timeout),
+            None => Some(self.sat.solve_without_model_under_assumptions(assumptions)),
         };
+        if is_sat == Some(false) && self.sat.is_interrupted() {
+            is_sat = None;
+        }
+        let solve_time = start.elapsed();
         if let Some(perf_stats) = self.perf_stats {
             perf_stats
                 .time_sat_solver
-                .set(perf_stats.time_sat_solver() + start.elapsed().as_secs_f64());
+                .set(perf_stats.time_sat_solver() + solve_time.as_secs_f64());
         }
         let solver_stats = self.sat.stats();
+        // The solver may have been replaced by another portfolio worker, hence `saturating_sub`
+        let diff = |after: Option<u64>, before: Option<u64>| {
+            after.map(|after| after.saturating_sub(before.unwrap_or(0)))
+        };
+        self.statistics = Statistics {
+            decisions: diff(solver_stats.decisions, stats_before.decisions),
+            conflicts: diff(solver_stats.conflicts, stats_before.conflicts),
+            propagations: diff(solver_stats.propagations, stats_before.propagations),
+            restarts: diff(solver_stats.restarts, stats_before.restarts),
+            learnts: solver_stats.learnts,
+            clauses: solver_stats.clauses,
+            solve_time,
+        };
+        self.total_statistics.accumulate(&self.statistics);
         if let Some(perf_stats) = self.perf_stats {
             if let Some(decisions) = solver_stats.decisions {
                 perf_stats.decisions.set(decisions);
@@ -207,25 +875,132 @@ impl<'a> IntegratedSolver<'a> {
                 perf_stats.conflicts.set(conflicts);
             }
         }
+        is_sat
+    }
+
+    /// Returns the model for the last successful call to the SAT solver.
+    fn model<'b>(&'b self) -> Model<'b> {
+        Model {
+            csp: &self.csp,
+            normalize_map: &self.normalize_map,
+            norm_csp: &self.norm,
+            encode_map: &self.encode_map,
+            model: unsafe { self.sat.model() },
+        }
+    }
 
-        match solver_result {
-            Some(model) => Some(Model {
-                csp: &self.csp,
-                normalize_map: &self.normalize_map,
-                norm_csp: &self.norm,
-                encode_map: &self.encode_map,
-                model,
-            }),
-            None => None,
+    /// Returns the assumptions responsible for the unsatisfiability in the last call to
+    /// `solve_under_assumptions` (or `solve`), which is not necessarily minimal.
+    /// This is empty if the last call was satisfiable or the problem is unsatisfiable even
+    /// without the assumptions.
+    pub fn unsat_core(&self) -> Vec<BoolVar> {
+        self.unsat_core.clone()
+    }
+
+    /// Count the valid assignments of all the variables of the CSP problem.
+    /// If `limit` is specified, the counting stops when `limit` assignments are found.
+    /// If solving is cancelled, the assignments found so far are counted (see `was_cancelled`).
+    /// Assignments are enumerated by blocking clauses on the SAT literals determining the values
+    /// of the variables, so auxiliary SAT variables (as well as the variables introduced by
+    /// `add_soft_expr`) do not affect the result. The blocking clauses are disabled after counting,
+    /// so the problem itself is not modified.
+    pub fn count_solutions(&mut self, limit: Option<usize>) -> usize {
+        self.cancelled = false;
+        if limit == Some(0) || !self.encode() {
+            return 0;
+        }
+
+        // Variables which do not appear in the SAT instance take arbitrary values in their domains
+        let mut multiplier = 1usize;
+        let mut key_lits = vec![];
+        let mut unencoded_bool_vars = BTreeSet::new();
+        let mut unencoded_int_vars = BTreeSet::new();
+        let relax_vars = self.soft_relax_vars();
+        for var in self.csp.vars.bool_vars_iter() {
+            if relax_vars.contains(&var) {
+                continue;
+            }
+            match self.normalize_map.get_bool_var(var) {
+                Some(norm_lit) => match self.encode_map.get_bool_lit(norm_lit) {
+                    Some(lit) => key_lits.push(lit),
+                    None => {
+                        if unencoded_bool_vars.insert(norm_lit.var) {
+                            multiplier = multiplier.saturating_mul(2);
+                        }
+                    }
+                },
+                None => match self.csp.get_bool_var_status(var) {
+                    BoolVarStatus::Infeasible => panic!(),
+                    BoolVarStatus::Fixed(_) => (),
+                    BoolVarStatus::Unfixed => multiplier = multiplier.saturating_mul(2),
+                },
+            }
+        }
+        for var in self.csp.vars.int_vars_iter() {
+            match self.normalize_map.get_int_var(var) {
+                Some(norm_var) => match self.encode_map.int_var_lits(norm_var) {
+                    Some(lits) => key_lits.extend_from_slice(lits),
+                    None => {
+                        if unencoded_int_vars.insert(norm_var) {
+                            let n = self.norm.vars.int_var(norm_var).enumerate().len();
+                            multiplier = multiplier.saturating_mul(n);
+                        }
+                    }
+                },
+                None => match self.csp.get_int_var_status(var) {
+                    IntVarStatus::Infeasible => panic!(),
+                    IntVarStatus::Fixed(_) => (),
+                    IntVarStatus::Unfixed(_) => {
+                        let n = self.csp.vars.int_var(var).domain.num_candidates();
+                        multiplier = multiplier.saturating_mul(n);
+                    }
+                },
+            }
+        }
+
+        // Blocking clauses are activated by `active`, which is assumed only during counting
+        #[cfg(not(feature = "sat-analyzer"))]
+        let active = self.sat.new_var().as_lit(false);
+        #[cfg(feature = "sat-analyzer")]
+        let active = self.sat.new_var("count_solutions").as_lit(false);
+
+        let mut count = 0usize;
+        loop {
+            if let Some(limit) = limit {
+                if count >= limit {
+                    break;
+                }
+            }
+            if !self.run_sat_solver(&[active]) {
+                break;
+            }
+            let mut blocking = vec![!active];
+            {
+                let model = unsafe { self.sat.model() };
+                for &lit in &key_lits {
+                    blocking.push(if model.assignment_lit(lit) { !lit } else { lit });
+                }
+            }
+            self.sat.add_clause(&blocking);
+            count = count.saturating_add(multiplier);
+        }
+        self.sat.add_clause(&[!active]);
+
+        match limit {
+            Some(limit) => count.min(limit),
+            None => count,
         }
     }
 
     /// Enumerate all the valid assignments of the CSP problem.
     /// Since this function may modify the problem instance, this consumes `self` to avoid further operations.
     pub fn enumerate_valid_assignments(self) -> Vec<Assignment> {
+        let relax_vars = self.soft_relax_vars();
         let mut bool_vars = vec![];
         for v in self.csp.vars.bool_vars_iter() {
-            bool_vars.push(v);
+            if !relax_vars.contains(&v) {
+                bool_vars.push(v);
+            }
         }
         let mut int_vars = vec![];
         for v in self.csp.vars.int_vars_iter() {
@@ -235,11 +1010,43 @@ impl<'a> IntegratedSolver<'a> {
         self.answer_iter(&bool_vars, &int_vars).collect()
     }
 
+    /// Returns the values of `bool_vars` and `int_vars` which are common to all the solutions,
+    /// or `None` if there is no solution (or solving is cancelled, see `interrupt_handle`).
     pub fn decide_irrefutable_facts(
         mut self,
         bool_vars: &[BoolVar],
         int_vars: &[IntVar],
     ) -> Option<Assignment> {
+        match self.decide_irrefutable_facts_impl(bool_vars, int_vars) {
+            IrrefutableFactsOutcome::Facts(assignment) => Some(assignment),
+            _ => None,
+        }
+    }
+
+    /// Same as `decide_irrefutable_facts`, but gives up if it does not finish within `timeout`
+    /// (including the time for normalization and encoding). No timeout is applied if `timeout`
+    /// is `None`. Unlike `decide_irrefutable_facts`, the solver is not consumed, so that
+    /// `total_statistics` can be inspected afterwards.
+    /// See `SAT::solve_without_model_with_timeout` for the configurations supporting timeout.
+    pub fn decide_irrefutable_facts_with_timeout(
+        &mut self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+        timeout: Option<Duration>,
+    ) -> IrrefutableFactsOutcome {
+        self.deadline = timeout.map(|timeout| Instant::now() + timeout);
+        self.timed_out = false;
+        let res = self.decide_irrefutable_facts_impl(bool_vars, int_vars);
+        self.deadline = None;
+        res
+    }
+
+    fn decide_irrefutable_facts_impl(
+        &mut self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+    ) -> IrrefutableFactsOutcome {
+        self.cancelled = false;
         let mut assignment = Assignment::new();
         match self.solve() {
             Some(model) => {
@@ -250,47 +1057,123 @@ impl<'a> IntegratedSolver<'a> {
                     assignment.set_int(var, model.get_int(var));
                 }
             }
-            None => return None,
+            None => {
+                return if self.cancelled {
+                    IrrefutableFactsOutcome::Cancelled
+                } else if self.timed_out {
+                    IrrefutableFactsOutcome::Timeout(self.fixed_facts(bool_vars, int_vars))
+                } else {
+                    IrrefutableFactsOutcome::Unsat
+                };
+            }
         }
-        loop {
-            let mut refutation = vec![];
-            for (&v, &b) in assignment.bool_iter() {
-                refutation.push(Box::new(if b { !v.expr() } else { v.expr() }));
+
+        // The facts in `assignment` which are already checked, kept for reporting them on timeout.
+        let mut proven = Assignment::new();
+
+        // Each candidate fact is refuted if the SAT solver finds a model under the assumption of
+        // its negation. Such a model also refutes all the other candidates it violates.
+        for &var in bool_vars {
+            let b = match assignment.get_bool(var) {
+                Some(b) => b,
+                None => continue,
+            };
+            if self.run_sat_solver_with_bool_value(var, !b) {
+                assignment.remove_bool(var);
+                self.remove_refuted_facts(&mut assignment);
+            } else if self.cancelled {
+                return IrrefutableFactsOutcome::Cancelled;
+            } else if self.timed_out {
+                return IrrefutableFactsOutcome::Timeout(proven);
+            } else {
+                proven.set_bool(var, b);
             }
-            for (&v, &i) in assignment.int_iter() {
-                refutation.push(Box::new(v.expr().ne(IntExpr::Const(i))));
+        }
+        for &var in int_vars {
+            let n = match assignment.get_int(var) {
+                Some(n) => n,
+                None => continue,
+            };
+            if self.run_sat_solver_with_bound(var, n - 1, false)
+                || self.run_sat_solver_with_bound(var, n + 1, true)
+            {
+                assignment.remove_int(var);
+                self.remove_refuted_facts(&mut assignment);
+            } else if self.cancelled {
+                return IrrefutableFactsOutcome::Cancelled;
+            } else if self.timed_out {
+                return IrrefutableFactsOutcome::Timeout(proven);
+            } else {
+                proven.set_int(var, n);
             }
-            self.add_expr(BoolExpr::Or(refutation));
-
-            match self.solve() {
-                Some(model) => {
-                    let bool_erased = assignment
-                        .bool_iter()
-                        .filter_map(|(&v, &b)| {
-                            if model.get_bool(v) != b {
-                                Some(v)
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    let int_erased = assignment
-                        .int_iter()
-                        .filter_map(|(&v, &i)| if model.get_int(v) != i { Some(v) } else { None })
-                        .collect::<Vec<_>>();
-
-                    bool_erased
-                        .iter()
-                        .for_each(|&v| assert!(assignment.remove_bool(v).is_some()));
-                    int_erased
-                        .iter()
-                        .for_each(|&v| assert!(assignment.remove_int(v).is_some()));
+        }
+
+        IrrefutableFactsOutcome::Facts(assignment)
+    }
+
+    /// Returns the values of the variables in `bool_vars` and `int_vars` which are fixed without
+    /// the SAT solver (e.g. by preprocessing).
+    fn fixed_facts(&self, bool_vars: &[BoolVar], int_vars: &[IntVar]) -> Assignment {
+        let mut ret = Assignment::new();
+        for &var in bool_vars {
+            if self.normalize_map.get_bool_var(var).is_none() {
+                if let BoolVarStatus::Fixed(b) = self.csp.get_bool_var_status(var) {
+                    ret.set_bool(var, b);
                 }
-                None => break,
             }
         }
+        for &var in int_vars {
+            if self.normalize_map.get_int_var(var).is_none() {
+                if let IntVarStatus::Fixed(n) = self.csp.get_int_var_status(var) {
+                    ret.set_int(var, n.get());
+                }
+            }
+        }
+        ret
+    }
+
+    /// Runs the SAT solver under the condition that `var` is `value`.
+    fn run_sat_solver_with_bool_value(&mut self, var: BoolVar, value: bool) -> bool {
+        match self.normalize_map.get_bool_var(var) {
+            Some(norm_lit) => {
+                let lit =
+                    self.encode_map
+                        .convert_bool_lit(&self.norm.vars, &mut self.sat, norm_lit);
+                self.run_sat_solver(&[if value { lit } else { !lit }])
+            }
+            None => match self.csp.get_bool_var_status(var) {
+                BoolVarStatus::Infeasible => panic!(),
+                BoolVarStatus::Fixed(b) => b == value && self.run_sat_solver(&[]),
+                BoolVarStatus::Unfixed => self.run_sat_solver(&[]), // unused variable optimization
+            },
+        }
+    }
+
+    /// Removes the values in `assignment` which differ from those in the model for the last
+    /// successful call to the SAT solver.
+    fn remove_refuted_facts(&self, assignment: &mut Assignment) {
+        let model = self.model();
+        let bool_erased = assignment
+            .bool_iter()
+            .filter_map(|(&v, &b)| {
+                if model.get_bool(v) != b {
+                    Some(v)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let int_erased = assignment
+            .int_iter()
+            .filter_map(|(&v, &i)| if model.get_int(v) != i { Some(v) } else { None })
+            .collect::<Vec<_>>();
 
-        Some(assignment)
+        bool_erased
+            .iter()
+            .for_each(|&v| assert!(assignment.remove_bool(v).is_some()));
+        int_erased
+            .iter()
+            .for_each(|&v| assert!(assignment.remove_int(v).is_some()));
     }
 
     pub fn answer_iter(self, bool_vars: &[BoolVar], int_vars: &[IntVar]) -> AnswerIterator<'a> {
@@ -301,10 +1184,80 @@ impl<'a> IntegratedSolver<'a> {
         }
     }
 
+    /// Returns an iterator enumerating the solutions projected to `bool_vars` and `int_vars`.
+    /// Unlike `answer_iter`, the solver is only borrowed: the blocking constraints excluding
+    /// the enumerated solutions are disabled when the iterator is dropped, so the problem
+    /// itself is not modified afterwards.
+    pub fn enumerate<'s>(
+        &'s mut self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+    ) -> SolutionIter<'s, 'a> {
+        let guard = self.new_bool_var();
+        SolutionIter {
+            solver: self,
+            guard,
+            key_bool: bool_vars.to_vec(),
+            key_int: int_vars.to_vec(),
+        }
+    }
+
+    /// Calls `f` with the model of each solution, where models are distinct when projected to
+    /// `bool_vars` and `int_vars`, until `f` returns `false` or all the solutions are visited.
+    /// Returns the number of calls of `f`. As with `enumerate`, the problem itself is not
+    /// modified.
+    pub fn for_each_solution<F: FnMut(&Model) -> bool>(
+        &mut self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+        mut f: F,
+    ) -> usize {
+        let mut iter = self.enumerate(bool_vars, int_vars);
+        let mut n_calls = 0;
+        while let Some((_, cont)) = iter.next_with(&mut f) {
+            n_calls += 1;
+            if !cont {
+                break;
+            }
+        }
+        n_calls
+    }
+
+    /// Checks whether the solution projected to `bool_vars` and `int_vars` is unique, by finding
+    /// at most two solutions with `enumerate`. The problem itself is not modified.
+    pub fn is_unique(&mut self, bool_vars: &[BoolVar], int_vars: &[IntVar]) -> Uniqueness {
+        self.cancelled = false;
+        let (first, second) = {
+            let mut iter = self.enumerate(bool_vars, int_vars);
+            let first = iter.next();
+            let second = if first.is_some() { iter.next() } else { None };
+            (first, second)
+        };
+        if self.cancelled {
+            return Uniqueness::Cancelled;
+        }
+        match (first, second) {
+            (Some(first), Some(second)) => Uniqueness::Multiple(first, second),
+            (Some(first), None) => Uniqueness::Unique(first),
+            (None, _) => Uniqueness::None,
+        }
+    }
+
     pub fn set_perf_stats<'b: 'a>(&mut self, perf_stats: &'b PerfStats) {
         self.perf_stats = Some(perf_stats);
     }
 
+    /// Returns the statistics of the last call to the SAT solver. Note that some methods, such
+    /// as `minimize` and `decide_irrefutable_facts`, call the SAT solver more than once.
+    pub fn statistics(&self) -> Statistics {
+        self.statistics.clone()
+    }
+
+    /// Statistics accumulated over all the calls to the SAT solver so far.
+    pub fn total_statistics(&self) -> Statistics {
+        self.total_statistics.clone()
+    }
+
     pub fn perf_stats(&self) -> Option<PerfStats> {
         self.perf_stats.as_deref().cloned()
     }
@@ -320,51 +1273,123 @@ impl<'a> Iterator for AnswerIterator<'a> {
     type Item = Assignment;
 
     fn next(&mut self) -> Option<Assignment> {
-        let model = self.solver.solve();
-        if let Some(model) = &model {
-            let mut ret = Assignment::new();
-            let mut refutation = vec![];
-            for &var in &self.key_bool {
-                let b = model.get_bool(var);
-                ret.set_bool(var, b);
-                refutation.push(Box::new(if b { !var.expr() } else { var.expr() }));
-            }
-            for &var in &self.key_int {
-                let n = model.get_int(var);
-                ret.set_int(var, n);
-                refutation.push(Box::new(var.expr().ne(IntExpr::Const(n))));
-            }
-            self.solver.add_expr(BoolExpr::Or(refutation));
+        let model = self.solver.solve()?;
+        let (ret, refutation) = project_model(&model, &self.key_bool, &self.key_int);
+        self.solver.add_expr(refutation);
 
-            Some(ret)
-        } else {
-            None
-        }
+        Some(ret)
     }
 }
 
-pub struct Model<'a> {
-    csp: &'a CSP,
-    normalize_map: &'a NormalizeMap,
-    norm_csp: &'a NormCSP,
-    encode_map: &'a EncodeMap,
-    model: SATModel<'a>,
+pub struct SolutionIter<'s, 'a> {
+    solver: &'s mut IntegratedSolver<'a>,
+    /// Blocking constraints are effective only if `guard` is assumed.
+    guard: BoolVar,
+    key_bool: Vec<BoolVar>,
+    key_int: Vec<IntVar>,
 }
 
-impl<'a> Model<'a> {
-    pub fn get_bool(&self, var: BoolVar) -> bool {
-        match self.normalize_map.get_bool_var(var) {
-            Some(norm_lit) => {
-                self.encode_map
-                    .get_bool_lit(norm_lit)
-                    .map(|sat_lit| self.model.assignment(sat_lit.var()) ^ sat_lit.is_negated())
-                    .unwrap_or(false) // unused variable optimization
-            }
-            None => {
-                let var_data = self.csp.get_bool_var_status(var);
-                match var_data {
-                    BoolVarStatus::Infeasible => panic!(),
-                    BoolVarStatus::Fixed(v) => v,
+impl<'s, 'a> Iterator for SolutionIter<'s, 'a> {
+    type Item = Assignment;
+
+    fn next(&mut self) -> Option<Assignment> {
+        self.next_with(|_| ()).map(|(ret, _)| ret)
+    }
+}
+
+impl<'s, 'a> SolutionIter<'s, 'a> {
+    /// Returns whether the last call of `next` was cancelled by the flag of `interrupt_handle`,
+    /// that is, the enumeration stopped before all the solutions were found.
+    pub fn was_cancelled(&self) -> bool {
+        self.solver.was_cancelled()
+    }
+
+    /// Finds the next solution and excludes it from the later ones, like `next`, but also returns
+    /// the result of `f` applied to the model of the solution.
+    fn next_with<T, F: FnOnce(&Model) -> T>(&mut self, f: F) -> Option<(Assignment, T)> {
+        let model = self.solver.solve_under_assumptions(&[self.guard])?;
+        let res = f(&model);
+        let (ret, refutation) = project_model(&model, &self.key_bool, &self.key_int);
+        self.solver.add_expr(self.guard.expr().imp(refutation));
+
+        Some((ret, res))
+    }
+}
+
+impl<'s, 'a> Drop for SolutionIter<'s, 'a> {
+    fn drop(&mut self) {
+        self.solver.add_expr(!self.guard.expr());
+    }
+}
+
+/// Returns the values of `key_bool` and `key_int` in `model`, together with the constraint
+/// excluding these values.
+fn project_model(
+    model: &Model,
+    key_bool: &[BoolVar],
+    key_int: &[IntVar],
+) -> (Assignment, BoolExpr) {
+    let mut ret = Assignment::new();
+    let mut refutation = vec![];
+    for &var in key_bool {
+        let b = model.get_bool(var);
+        ret.set_bool(var, b);
+        refutation.push(Box::new(if b { !var.expr() } else { var.expr() }));
+    }
+    for &var in key_int {
+        let n = model.get_int(var);
+        ret.set_int(var, n);
+        refutation.push(Box::new(var.expr().ne(IntExpr::Const(n))));
+    }
+    (ret, BoolExpr::Or(refutation))
+}
+
+pub enum Uniqueness {
+    None,
+    Unique(Assignment),
+    Multiple(Assignment, Assignment),
+    /// Solving was cancelled before uniqueness was decided.
+    Cancelled,
+}
+
+pub enum IrrefutableFactsOutcome {
+    Facts(Assignment),
+    Unsat,
+    /// Solving timed out. Holds the facts which had been proven by then.
+    Timeout(Assignment),
+    /// Solving was cancelled by the flag of `interrupt_handle`.
+    Cancelled,
+}
+
+pub enum SolveOutcome<'a> {
+    Sat(Model<'a>),
+    Unsat,
+    Timeout,
+    Cancelled,
+}
+
+pub struct Model<'a> {
+    csp: &'a CSP,
+    normalize_map: &'a NormalizeMap,
+    norm_csp: &'a NormCSP,
+    encode_map: &'a EncodeMap,
+    model: SATModel<'a>,
+}
+
+impl<'a> Model<'a> {
+    pub fn get_bool(&self, var: BoolVar) -> bool {
+        match self.normalize_map.get_bool_var(var) {
+            Some(norm_lit) => {
+                self.encode_map
+                    .get_bool_lit(norm_lit)
+                    .map(|sat_lit| self.model.assignment(sat_lit.var()) ^ sat_lit.is_negated())
+                    .unwrap_or(false) // unused variable optimization
+            }
+            None => {
+                let var_data = self.csp.get_bool_var_status(var);
+                match var_data {
+                    BoolVarStatus::Infeasible => panic!(),
+                    BoolVarStatus::Fixed(v) => v,
                     BoolVarStatus::Unfixed => false, // unused variable optimization
                 }
             }
@@ -532,6 +1557,42 @@ mod tests {
                             return false;
                         }
                     }
+                    Stmt::Cardinality(exprs, low, high) => {
+                        let n = exprs
+                            .iter()
+                            .filter(|e| assignment.eval_bool_expr(e))
+                            .count();
+                        if !(*low <= n && n <= *high) {
+                            return false;
+                        }
+                    }
+                    Stmt::DirectedReachable(source, is_active, edges) => {
+                        let is_active = is_active
+                            .iter()
+                            .map(|v| assignment.eval_bool_expr(v))
+                            .collect::<Vec<_>>();
+                        if !test_util::check_graph_directed_reachable(*source, &is_active, edges) {
+                            return false;
+                        }
+                    }
+                    Stmt::SingleCycle(edge_exprs, edges) => {
+                        let is_active = edge_exprs
+                            .iter()
+                            .map(|e| assignment.eval_bool_expr(e))
+                            .collect::<Vec<_>>();
+                        if !test_util::check_single_cycle(&is_active, edges) {
+                            return false;
+                        }
+                    }
+                    Stmt::Acyclic(edge_exprs, edges) => {
+                        let is_active = edge_exprs
+                            .iter()
+                            .map(|e| assignment.eval_bool_expr(e))
+                            .collect::<Vec<_>>();
+                        if !test_util::check_acyclic(&is_active, edges) {
+                            return false;
+                        }
+                    }
                     Stmt::Circuit(values) => {
                         let values = values
                             .iter()
@@ -758,6 +1819,130 @@ mod tests {
         assert!(model.is_none());
     }
 
+    fn build_latin_square(solver: &mut IntegratedSolver, n: i32) -> Vec<Vec<IntVar>> {
+        let cells = (0..n)
+            .map(|_| {
+                (0..n)
+                    .map(|_| solver.new_int_var(Domain::range(1, n)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for i in 0..(n as usize) {
+            solver.add_constraint(Stmt::AllDifferent(
+                cells[i].iter().map(|v| v.expr()).collect(),
+            ));
+            solver.add_constraint(Stmt::AllDifferent(
+                cells.iter().map(|row| row[i].expr()).collect(),
+            ));
+        }
+        cells
+    }
+
+    /// Adds the constraints of the pigeonhole principle with `n + 1` pigeons and `n` holes, which
+    /// is unsatisfiable but hard for CDCL solvers for large `n`. Returns whether each pigeon is in
+    /// each hole.
+    fn pigeonhole(solver: &mut IntegratedSolver, n: usize) -> Vec<Vec<BoolVar>> {
+        let vars = (0..=n)
+            .map(|_| (0..n).map(|_| solver.new_bool_var()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for row in &vars {
+            solver.add_expr(BoolExpr::Or(
+                row.iter().map(|v| Box::new(v.expr())).collect(),
+            ));
+        }
+        for i in 0..=n {
+            for k in 0..i {
+                for (a, b) in vars[i].iter().zip(&vars[k]) {
+                    solver.add_expr(!(a.expr() & b.expr()));
+                }
+            }
+        }
+        vars
+    }
+
+    #[test]
+    fn test_integration_portfolio() {
+        // a 4x4 latin square with a unique solution
+        let solve_with_portfolio = |portfolio: usize| {
+            let mut config = Config::default();
+            config.portfolio = portfolio;
+            let mut solver = IntegratedSolver::with_config(config);
+            let cells = build_latin_square(&mut solver, 4);
+            for i in 0..4 {
+                solver.add_expr(cells[0][i].expr().eq(IntExpr::Const(i as i32 + 1)));
+                solver.add_expr(cells[i][0].expr().eq(IntExpr::Const(i as i32 + 1)));
+            }
+            solver.add_expr(cells[1][1].expr().eq(IntExpr::Const(1)));
+            solver.add_expr(cells[2][2].expr().eq(IntExpr::Const(1)));
+            let model = solver.solve().unwrap();
+            cells
+                .iter()
+                .map(|row| row.iter().map(|&v| model.get_int(v)).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        let expected = vec![
+            vec![1, 2, 3, 4],
+            vec![2, 1, 4, 3],
+            vec![3, 4, 1, 2],
+            vec![4, 3, 2, 1],
+        ];
+        assert_eq!(solve_with_portfolio(1), expected);
+        assert_eq!(solve_with_portfolio(4), expected);
+
+        // repeated solving keeps working with the instance of the winner
+        let mut config = Config::default();
+        config.portfolio = 3;
+        let mut solver = IntegratedSolver::with_config(config);
+        let cells = build_latin_square(&mut solver, 3);
+        assert_eq!(solver.count_solutions(None), 12);
+        solver.add_expr(cells[0][0].expr().eq(cells[1][1].expr()));
+        solver.add_expr(cells[0][0].expr().ne(cells[2][2].expr()));
+        assert!(solver.solve().is_none());
+    }
+
+    #[test]
+    fn test_integration_order_encoding_phase_hints() {
+        let enumerate = |phase_hints: bool| {
+            let mut config = Config::default();
+            config.order_encoding_phase_hints = phase_hints;
+            config.use_direct_encoding = false;
+            config.use_log_encoding = false;
+            let mut solver = IntegratedSolver::with_config(config);
+            let x = solver.new_int_var(Domain::range(0, 6));
+            let y = solver.new_int_var(Domain::range(-2, 4));
+            let z = solver.new_int_var(Domain::range(1, 5));
+            solver.add_expr((x.expr() + y.expr() * 2 - z.expr()).ge(IntExpr::Const(3)));
+            solver.add_expr((x.expr() + z.expr()).le(IntExpr::Const(7)));
+            solver.add_expr(y.expr().ne(x.expr()));
+
+            let mut answers = solver
+                .answer_iter(&[], &[x, y, z])
+                .map(|a| {
+                    (
+                        a.get_int(x).unwrap(),
+                        a.get_int(y).unwrap(),
+                        a.get_int(z).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            answers.sort();
+            answers
+        };
+
+        let with_hints = enumerate(true);
+        assert!(!with_hints.is_empty());
+        assert_eq!(with_hints, enumerate(false));
+        for &(x, y, z) in &with_hints {
+            assert!(x + y * 2 - z >= 3 && x + z <= 7 && x != y);
+        }
+        let expected = (0..=6)
+            .flat_map(|x| (-2..=4).flat_map(move |y| (1..=5).map(move |z| (x, y, z))))
+            .filter(|&(x, y, z)| x + y * 2 - z >= 3 && x + z <= 7 && x != y)
+            .count();
+        assert_eq!(with_hints.len(), expected);
+    }
+
     #[test]
     fn test_integration_unused_bool() {
         let mut solver = IntegratedSolver::new();
@@ -898,141 +2083,999 @@ mod tests {
         solver.add_expr(x.expr() | x.expr());
         solver.add_expr(!x.expr());
 
-        let res = solver.solve();
-        assert!(res.is_none());
+        let res = solver.solve();
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn test_integration_csp_optimization3() {
+        let mut solver = IntegratedSolver::new();
+
+        let mut vars = vec![];
+        let mut coefs = vec![];
+        for _ in 0..15 {
+            let v = solver.new_bool_var();
+            vars.push(v);
+            coefs.push((
+                Box::new(v.expr().ite(IntExpr::Const(1), IntExpr::Const(0))),
+                1,
+            ));
+        }
+        solver.add_expr(vars[0].expr());
+        solver.add_expr(IntExpr::Linear(coefs).eq(IntExpr::Const(0)));
+
+        let res = solver.solve();
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn test_integration_irrefutable_logic1() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_bool_var();
+        let y = solver.new_bool_var();
+        let z = solver.new_bool_var();
+        solver.add_expr(x.expr() | y.expr());
+        solver.add_expr(y.expr() | z.expr());
+        solver.add_expr(!(x.expr() & z.expr()));
+
+        let res = solver.decide_irrefutable_facts(&[x, y, z], &[]);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        assert_eq!(res.get_bool(x), None);
+        assert_eq!(res.get_bool(y), Some(true));
+        assert_eq!(res.get_bool(z), None);
+    }
+
+    #[test]
+    fn test_integration_irrefutable_complex1() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_bool_var();
+        let a = solver.new_int_var(Domain::range(0, 2));
+        let b = solver.new_int_var(Domain::range(0, 2));
+        solver.add_expr(x.expr().ite(a.expr(), b.expr()).eq(a.expr()));
+        solver.add_expr(a.expr().ne(b.expr()));
+
+        let res = solver.decide_irrefutable_facts(&[x], &[a, b]);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        assert_eq!(res.get_bool(x), Some(true));
+        assert_eq!(res.get_int(a), None);
+        assert_eq!(res.get_int(b), None);
+    }
+
+    #[test]
+    fn test_integration_irrefutable_complex2() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_bool_var();
+        let a = solver.new_int_var(Domain::range(0, 2));
+        let b = solver.new_int_var(Domain::range(0, 2));
+        let c = solver.new_int_var(Domain::range(0, 2));
+        solver.add_expr(
+            x.expr()
+                .ite(a.expr(), b.expr())
+                .lt(c.expr() - IntExpr::Const(1)),
+        );
+        solver.add_expr(a.expr().ne(b.expr()));
+
+        let res = solver.decide_irrefutable_facts(&[x], &[a, b, c]);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        assert_eq!(res.get_bool(x), None);
+        assert_eq!(res.get_int(a), None);
+        assert_eq!(res.get_int(b), None);
+        assert_eq!(res.get_int(c), Some(2));
+    }
+
+    #[test]
+    fn test_integration_irrefutable_many_terms() {
+        let mut solver = IntegratedSolver::new();
+
+        let mut ivars = vec![];
+        for _ in 0..30 {
+            ivars.push(solver.new_int_var(Domain::range(0, 1)));
+        }
+        solver.add_expr(
+            IntExpr::Linear(ivars.iter().map(|v| (Box::new(v.expr()), 1)).collect())
+                .ge(IntExpr::Const(10)),
+        );
+
+        let x = solver.new_bool_var();
+        solver.add_expr(
+            IntExpr::Linear(ivars.iter().map(|v| (Box::new(v.expr()), 1)).collect())
+                .ge(IntExpr::Const(9))
+                .iff(x.expr()),
+        );
+
+        let res = solver.decide_irrefutable_facts(&[x], &ivars);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        assert_eq!(res.get_bool(x), Some(true));
+        assert_eq!(res.get_int(ivars[0]), None);
+    }
+
+    #[test]
+    fn test_integration_irrefutable_alldifferent() {
+        let mut solver = IntegratedSolver::new();
+
+        let a = solver.new_int_var(Domain::range(1, 3));
+        let b = solver.new_int_var(Domain::range(1, 3));
+        let c = solver.new_int_var(Domain::range(1, 3));
+        let d = solver.new_int_var(Domain::range(1, 4));
+        solver.add_constraint(Stmt::AllDifferent(vec![
+            a.expr(),
+            b.expr(),
+            c.expr(),
+            d.expr(),
+        ]));
+
+        let res = solver.decide_irrefutable_facts(&[], &[a, b, c, d]);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        assert_eq!(res.get_int(a), None);
+        assert_eq!(res.get_int(b), None);
+        assert_eq!(res.get_int(c), None);
+        assert_eq!(res.get_int(d), Some(4));
+    }
+
+    #[test]
+    fn test_integration_irrefutable_brute_force() {
+        fn build(solver: &mut IntegratedSolver) -> (Vec<BoolVar>, Vec<IntVar>) {
+            let bools = (0..4).map(|_| solver.new_bool_var()).collect::<Vec<_>>(); // bools[3] is unused
+            let ints = vec![
+                solver.new_int_var(Domain::range(0, 4)),
+                solver.new_int_var(Domain::range(0, 4)),
+                solver.new_int_var(Domain::range(-2, 2)),
+                solver.new_int_var(Domain::range(0, 3)),
+                solver.new_int_var(Domain::range(0, 3)), // unused
+            ];
+            solver.add_expr(bools[0].expr() | bools[1].expr());
+            solver.add_expr(bools[0].expr().imp(ints[0].expr().ge(IntExpr::Const(3))));
+            solver.add_expr(
+                bools[1]
+                    .expr()
+                    .imp((ints[0].expr() + ints[1].expr()).eq(IntExpr::Const(7))),
+            );
+            solver.add_expr((ints[1].expr() + ints[2].expr()).le(IntExpr::Const(3)));
+            solver.add_expr(ints[2].expr().ge(IntExpr::Const(1)).iff(bools[2].expr()));
+            solver.add_expr(ints[1].expr().ge(IntExpr::Const(2)));
+            solver.add_expr(ints[2].expr().gt(IntExpr::Const(0)));
+            solver.add_expr(
+                ints[3]
+                    .expr()
+                    .eq(IntExpr::Const(2))
+                    .iff(ints[0].expr().ne(IntExpr::Const(4))),
+            );
+            (bools, ints)
+        }
+
+        let mut solver = IntegratedSolver::new();
+        let (bools, ints) = build(&mut solver);
+        let mut expected_bool = bools.iter().map(|_| None).collect::<Vec<_>>();
+        let mut expected_int = ints.iter().map(|_| None).collect::<Vec<_>>();
+        let mut is_first = true;
+        for answer in solver.answer_iter(&bools, &ints) {
+            for (i, &v) in bools.iter().enumerate() {
+                let b = answer.get_bool(v);
+                if is_first {
+                    expected_bool[i] = b;
+                } else if expected_bool[i] != b {
+                    expected_bool[i] = None;
+                }
+            }
+            for (i, &v) in ints.iter().enumerate() {
+                let n = answer.get_int(v);
+                if is_first {
+                    expected_int[i] = n;
+                } else if expected_int[i] != n {
+                    expected_int[i] = None;
+                }
+            }
+            is_first = false;
+        }
+        assert!(!is_first);
+        assert!(expected_bool.iter().any(|b| b.is_some()));
+        assert!(expected_int.iter().any(|n| n.is_some()));
+
+        let mut solver = IntegratedSolver::new();
+        let (bools, ints) = build(&mut solver);
+        let res = solver.decide_irrefutable_facts(&bools, &ints);
+        assert!(res.is_some());
+        let res = res.unwrap();
+        for (i, &v) in bools.iter().enumerate() {
+            assert_eq!(res.get_bool(v), expected_bool[i]);
+        }
+        for (i, &v) in ints.iter().enumerate() {
+            assert_eq!(res.get_int(v), expected_int[i]);
+        }
+    }
+
+    #[test]
+    fn test_integration_count_solutions() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        let z = solver.new_int_var(Domain::range(0, 2));
+        let b = solver.new_bool_var();
+        let c = solver.new_bool_var();
+        solver.add_expr(x.expr().lt(y.expr()));
+        solver.add_expr(b.expr() | (x.expr() + z.expr()).ge(IntExpr::Const(2)));
+
+        // (b, z) can be chosen in 4, 5 and 6 ways for x = 0, 1 and 2 respectively,
+        // and c is unconstrained
+        let expected = (3 * 4 + 2 * 5 + 6) * 2;
+        assert_eq!(solver.count_solutions(None), expected);
+        assert_eq!(solver.count_solutions(Some(10)), 10);
+        assert_eq!(solver.count_solutions(Some(100)), expected);
+
+        // counting does not affect further solving
+        {
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            assert!(model.get_int(x) < model.get_int(y));
+        }
+        assert_eq!(solver.count_solutions(None), expected);
+
+        solver.add_expr(c.expr());
+        solver.add_expr(x.expr().ge(IntExpr::Const(2)));
+        assert_eq!(solver.count_solutions(None), 6);
+        solver.add_expr(y.expr().le(IntExpr::Const(2)));
+        assert_eq!(solver.count_solutions(None), 0);
+    }
+
+    #[test]
+    fn test_integration_unsat_core() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 5));
+        let y = solver.new_int_var(Domain::range(0, 5));
+        let s = [
+            solver.new_bool_var(),
+            solver.new_bool_var(),
+            solver.new_bool_var(),
+            solver.new_bool_var(),
+        ];
+        solver.add_expr(s[0].expr().imp(x.expr().ge(IntExpr::Const(3))));
+        solver.add_expr(s[1].expr().imp((x.expr() + y.expr()).le(IntExpr::Const(2))));
+        solver.add_expr(s[2].expr().imp(y.expr().ge(IntExpr::Const(1))));
+        solver.add_expr(s[3].expr().imp(y.expr().ne(x.expr())));
+
+        assert!(solver.solve_under_assumptions(&s).is_none());
+        let core = solver.unsat_core();
+        assert!(core.contains(&s[0]));
+        assert!(core.contains(&s[1]));
+        assert!(core.iter().all(|v| s.contains(v)));
+        assert!(solver.solve_under_assumptions(&core).is_none());
+
+        // assumptions are not kept for later calls
+        {
+            let model = solver.solve_under_assumptions(&[s[0], s[2], s[3]]);
+            assert!(model.is_some());
+            let model = model.unwrap();
+            assert!(model.get_bool(s[0]));
+            assert!(model.get_int(x) >= 3);
+        }
+        assert!(solver.unsat_core().is_empty());
+        assert!(solver.solve().is_some());
+
+        solver.add_expr(!s[2].expr());
+        assert!(solver.solve_under_assumptions(&[s[2]]).is_none());
+        assert_eq!(solver.unsat_core(), vec![s[2]]);
+    }
+
+    #[cfg(feature = "backend-external")]
+    #[test]
+    fn test_integration_external_backend() {
+        use crate::sat::SatBackend;
+
+        // This test runs only if a SAT solver binary is given
+        let path = match std::env::var("ENIGMA_CSP_EXTERNAL_SOLVER") {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+        let mut config = Config::default();
+        config.backend = SatBackend::External { path, args: vec![] };
+
+        {
+            let mut solver = IntegratedSolver::with_config(config.clone());
+            let x = solver.new_int_var(Domain::range(0, 5));
+            let y = solver.new_int_var(Domain::range(0, 5));
+            let b = solver.new_bool_var();
+            solver.add_expr((x.expr() + y.expr()).eq(IntExpr::Const(7)));
+            solver.add_expr(b.expr().iff(x.expr().gt(y.expr())));
+            solver.add_expr(!b.expr());
+
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            let xv = model.get_int(x);
+            let yv = model.get_int(y);
+            assert_eq!(xv + yv, 7);
+            assert!(xv <= yv);
+            assert!(!model.get_bool(b));
+        }
+        {
+            let mut solver = IntegratedSolver::with_config(config);
+            let x = solver.new_int_var(Domain::range(0, 5));
+            let y = solver.new_int_var(Domain::range(0, 5));
+            solver.add_expr((x.expr() + y.expr()).eq(IntExpr::Const(7)));
+            solver.add_expr(x.expr().ge(y.expr() + IntExpr::Const(4)));
+
+            assert!(solver.solve().is_none());
+        }
+    }
+
+    #[test]
+    fn test_integration_add_constraint_incremental() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 5));
+        let y = solver.new_int_var(Domain::range(0, 5));
+        let b = solver.new_bool_var();
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(6)));
+        solver.add_expr(b.expr().imp(x.expr().lt(y.expr())));
+
+        {
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            assert!(model.get_int(x) + model.get_int(y) >= 6);
+        }
+
+        let num_var = solver.sat.num_var();
+        assert!(solver.add_constraint_incremental(Stmt::Expr(b.expr())));
+        assert!(solver.add_constraint_incremental(Stmt::Expr(x.expr().ge(IntExpr::Const(2)))));
+        // `x` and `y` are not re-encoded
+        assert_eq!(solver.sat.num_var(), num_var);
+
+        {
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            let xv = model.get_int(x);
+            let yv = model.get_int(y);
+            assert!(xv + yv >= 6);
+            assert!(model.get_bool(b));
+            assert!(xv < yv);
+            assert!(xv >= 2);
+        }
+
+        solver.add_constraint_incremental(Stmt::Expr(y.expr().le(IntExpr::Const(3))));
+        assert!(solver.solve().is_none());
+    }
+
+    #[test]
+    fn test_integration_minimize() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 10));
+        let y = solver.new_int_var(Domain::range(0, 10));
+        let z = solver.new_int_var(Domain::range(0, 10));
+        let s = solver.new_int_var(Domain::range(0, 30));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(7)));
+        solver.add_expr((y.expr() + z.expr()).ge(IntExpr::Const(5)));
+        solver.add_expr(x.expr().ne(IntExpr::Const(0)));
+        solver.add_expr(s.expr().eq(x.expr() + y.expr() + z.expr()));
+
+        {
+            let res = solver.minimize(s);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 7);
+            assert_eq!(model.get_int(s), 7);
+            let (xv, yv, zv) = (model.get_int(x), model.get_int(y), model.get_int(z));
+            assert_eq!(xv + yv + zv, 7);
+            assert!(xv + yv >= 7 && yv + zv >= 5 && xv != 0);
+        }
+
+        // bounds used in the optimization do not remain
+        solver.add_expr(s.expr().ge(IntExpr::Const(9)));
+        {
+            let res = solver.minimize(s);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 9);
+            assert_eq!(model.get_int(s), 9);
+        }
+
+        solver.add_expr(s.expr().le(IntExpr::Const(8)));
+        assert!(solver.minimize(s).is_none());
+    }
+
+    #[test]
+    fn test_integration_maximize() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 10));
+        let y = solver.new_int_var(Domain::range(0, 10));
+        let s = solver.new_int_var(Domain::range(-20, 20));
+        solver.add_expr((x.expr() * 2 + y.expr() * 3).le(IntExpr::Const(22)));
+        solver.add_expr(x.expr().ne(y.expr()));
+        solver.add_expr(s.expr().eq(x.expr() + y.expr()));
+
+        // (x, y) = (10, 0), (9, 1) or (8, 2)
+        {
+            let res = solver.maximize(s);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 10);
+            assert_eq!(model.get_int(s), 10);
+            let (xv, yv) = (model.get_int(x), model.get_int(y));
+            assert_eq!(xv + yv, 10);
+            assert!(xv * 2 + yv * 3 <= 22 && xv != yv);
+        }
+
+        // bounds used in the optimization do not remain
+        solver.add_expr(s.expr().le(IntExpr::Const(6)));
+        {
+            let res = solver.maximize(s);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 6);
+            assert_eq!(model.get_int(s), 6);
+        }
+
+        solver.add_expr(s.expr().ge(IntExpr::Const(7)));
+        assert!(solver.maximize(s).is_none());
+    }
+
+    #[test]
+    fn test_integration_maximize_log_encoding() {
+        let mut config = Config::default();
+        config.force_use_log_encoding = true;
+        let mut solver = IntegratedSolver::with_config(config);
+
+        let x = solver.new_int_var(Domain::range(0, 50));
+        let y = solver.new_int_var(Domain::range(0, 50));
+        let s = solver.new_int_var(Domain::range(0, 100));
+        solver.add_expr((x.expr() * 3 + y.expr() * 5).le(IntExpr::Const(101)));
+        solver.add_expr(y.expr().ge(IntExpr::Const(3)));
+        solver.add_expr(s.expr().eq(x.expr() * 2 + y.expr()));
+        assert_eq!(solver.count_solutions(None), 273);
+
+        // (x, y) = (28, 3)
+        {
+            let res = solver.maximize(s);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 59);
+            assert_eq!(model.get_int(x), 28);
+            assert_eq!(model.get_int(y), 3);
+            assert_eq!(model.get_int(s), 59);
+        }
+        // the bounds used in the optimization do not change the solutions
+        assert_eq!(solver.count_solutions(None), 273);
+
+        {
+            let res = solver.minimize(x);
+            assert!(res.is_some());
+            let (model, value) = res.unwrap();
+            assert_eq!(value, 0);
+            assert_eq!(model.get_int(x), 0);
+        }
+        assert_eq!(solver.count_solutions(None), 273);
+    }
+
+    #[test]
+    fn test_integration_minimize_log_encoding_count_solutions() {
+        let mut config = Config::default();
+        config.force_use_log_encoding = true;
+        let mut solver = IntegratedSolver::with_config(config);
+
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(5)));
+        assert_eq!(solver.count_solutions(None), 3);
+
+        let res = solver.minimize(x);
+        assert_eq!(res.map(|(_, value)| value), Some(2));
+
+        // the variables guarding the bounds in the optimization are not counted
+        assert_eq!(solver.count_solutions(None), 3);
+    }
+
+    #[test]
+    fn test_integration_minimize_lex() {
+        // the minimum of x alone is 0, and that of y alone is 1
+        let build = || {
+            let mut solver = IntegratedSolver::new();
+            let x = solver.new_int_var(Domain::range(0, 5));
+            let y = solver.new_int_var(Domain::range(0, 5));
+            solver.add_expr((x.expr() + y.expr() * 2).ge(IntExpr::Const(6)));
+            (solver, x, y)
+        };
+
+        {
+            let (mut solver, x, y) = build();
+            let (model, values) = solver.minimize_lex(&[x, y]).unwrap();
+            assert_eq!(values, vec![0, 3]);
+            assert_eq!(model.get_int(x), 0);
+            assert_eq!(model.get_int(y), 3);
+        }
+        {
+            let (mut solver, x, y) = build();
+            let (model, values) = solver.minimize_lex(&[y, x]).unwrap();
+            assert_eq!(values, vec![1, 4]);
+            assert_eq!(model.get_int(x), 4);
+            assert_eq!(model.get_int(y), 1);
+        }
+        {
+            let (mut solver, x, y) = build();
+            solver.add_expr((x.expr() + y.expr()).le(IntExpr::Const(1)));
+            assert!(solver.minimize_lex(&[x, y]).is_none());
+        }
+    }
+
+    #[test]
+    fn test_integration_minimize_expr() {
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(-4, 4));
+        let y = solver.new_int_var(Domain::range(-4, 4));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(3)));
+
+        {
+            let (model, value) = solver
+                .minimize_expr((x.expr() - y.expr()).abs() * 2 + y.expr())
+                .unwrap();
+            // (x, y) = (2, 2)
+            assert_eq!(value, 2);
+            assert_eq!(model.get_int(x), 2);
+            assert_eq!(model.get_int(y), 2);
+        }
+        {
+            let (model, value) = solver.maximize_expr(x.expr() * y.expr()).unwrap();
+            // (x, y) = (4, 4)
+            assert_eq!(value, 16);
+            assert_eq!(model.get_int(x), 4);
+            assert_eq!(model.get_int(y), 4);
+        }
+        {
+            let (model, value) = solver.minimize_expr(y.expr()).unwrap();
+            assert_eq!(value, -1);
+            assert_eq!(model.get_int(y), -1);
+        }
+    }
+
+    #[test]
+    fn test_integration_solve_with_timeout() {
+        for portfolio in [1, 2] {
+            let mut config = Config::default();
+            config.portfolio = portfolio;
+            let mut solver = IntegratedSolver::with_config(config);
+
+            // pigeonhole principle with 13 pigeons and 12 holes, which is hard for CDCL solvers
+            let vars = pigeonhole(&mut solver, 12);
+
+            let start = std::time::Instant::now();
+            assert!(matches!(
+                solver.solve_with_timeout(Duration::from_millis(100)),
+                SolveOutcome::Timeout
+            ));
+            assert!(start.elapsed() < Duration::from_secs(10));
+
+            // the solver is still usable after timeout
+            for v in &vars[0] {
+                solver.add_expr(!v.expr());
+            }
+            assert!(matches!(
+                solver.solve_with_timeout(Duration::from_secs(100)),
+                SolveOutcome::Unsat
+            ));
+        }
+    }
+
+    #[test]
+    fn test_integration_irrefutable_facts_with_timeout() {
+        // pigeonhole principle with 13 pigeons and 12 holes
+        let mut solver = IntegratedSolver::new();
+        let vars = pigeonhole(&mut solver, 12).concat();
+        let start = std::time::Instant::now();
+        assert!(matches!(
+            solver.decide_irrefutable_facts_with_timeout(
+                &vars,
+                &[],
+                Some(Duration::from_millis(100))
+            ),
+            IrrefutableFactsOutcome::Timeout(facts) if facts.bool_iter().next().is_none()
+        ));
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_bool_var();
+        let y = solver.new_bool_var();
+        solver.add_expr(x.expr() | y.expr());
+        solver.add_expr(!y.expr());
+        match solver.decide_irrefutable_facts_with_timeout(
+            &[x, y],
+            &[],
+            Some(Duration::from_secs(100)),
+        ) {
+            IrrefutableFactsOutcome::Facts(assignment) => {
+                assert_eq!(assignment.get_bool(x), Some(true));
+                assert_eq!(assignment.get_bool(y), Some(false));
+            }
+            _ => panic!(),
+        }
+        // the first solve and the refutation attempt for `x`
+        assert!(solver.total_statistics().solve_time >= solver.statistics().solve_time);
+
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_bool_var();
+        solver.add_expr(x.expr());
+        solver.add_expr(!x.expr());
+        assert!(matches!(
+            solver.decide_irrefutable_facts_with_timeout(&[x], &[], None),
+            IrrefutableFactsOutcome::Unsat
+        ));
+    }
+
+    #[test]
+    fn test_integration_interrupt_handle() {
+        let mut solver = IntegratedSolver::new();
+
+        // pigeonhole principle with 13 pigeons and 12 holes
+        let vars = pigeonhole(&mut solver, 12);
+
+        let flag = solver.interrupt_handle();
+        let start = std::time::Instant::now();
+        let canceller = {
+            let flag = flag.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100));
+                flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            })
+        };
+        assert!(matches!(
+            solver.solve_cancellable(),
+            SolveOutcome::Cancelled
+        ));
+        assert!(start.elapsed() < Duration::from_secs(10));
+        canceller.join().unwrap();
+
+        // plain `solve` also returns immediately while the flag is set
+        assert!(solver.solve().is_none());
+        assert!(solver.was_cancelled());
+
+        // the methods reporting cancellation do not mistake it for unsatisfiability
+        let all_vars = vars.concat();
+        {
+            let mut iter = solver.enumerate(&all_vars, &[]);
+            assert!(iter.next().is_none());
+            assert!(iter.was_cancelled());
+        }
+        assert_eq!(solver.count_solutions(None), 0);
+        assert!(solver.was_cancelled());
+        let x = solver.new_int_var(Domain::range(0, 3));
+        assert!(solver.minimize(x).is_none());
+        assert!(solver.was_cancelled());
+        assert!(matches!(
+            solver.decide_irrefutable_facts_with_timeout(&all_vars, &[], None),
+            IrrefutableFactsOutcome::Cancelled
+        ));
+        assert!(matches!(
+            solver.is_unique(&all_vars, &[]),
+            Uniqueness::Cancelled
+        ));
+
+        flag.store(false, std::sync::atomic::Ordering::SeqCst);
+        for v in &vars[0] {
+            solver.add_expr(!v.expr());
+        }
+        assert!(matches!(solver.solve_cancellable(), SolveOutcome::Unsat));
+        let mut iter = solver.enumerate(&all_vars, &[]);
+        assert!(iter.next().is_none());
+        assert!(!iter.was_cancelled());
+    }
+
+    #[test]
+    fn test_integration_portfolio_interrupt_handle() {
+        let mut config = Config::default();
+        config.portfolio = 2;
+        let mut solver = IntegratedSolver::with_config(config);
+        pigeonhole(&mut solver, 12);
+
+        let flag = solver.interrupt_handle();
+        let start = std::time::Instant::now();
+        let canceller = {
+            let flag = flag.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100));
+                flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            })
+        };
+        assert!(matches!(
+            solver.solve_cancellable(),
+            SolveOutcome::Cancelled
+        ));
+        assert!(start.elapsed() < Duration::from_secs(10));
+        canceller.join().unwrap();
+
+        flag.store(false, std::sync::atomic::Ordering::SeqCst);
+        let x = solver.new_bool_var();
+        solver.add_expr(x.expr());
+        solver.add_expr(!x.expr());
+        assert!(matches!(solver.solve_cancellable(), SolveOutcome::Unsat));
+    }
+
+    #[test]
+    fn test_integration_solve_with_timeout_sat() {
+        let mut solver = IntegratedSolver::new();
+
+        let x = solver.new_int_var(Domain::range(0, 5));
+        let y = solver.new_int_var(Domain::range(0, 5));
+        solver.add_expr((x.expr() + y.expr()).eq(IntExpr::Const(7)));
+        solver.add_expr(x.expr().gt(y.expr()));
+
+        match solver.solve_with_timeout(Duration::from_secs(100)) {
+            SolveOutcome::Sat(model) => {
+                let (xv, yv) = (model.get_int(x), model.get_int(y));
+                assert_eq!(xv + yv, 7);
+                assert!(xv > yv);
+            }
+            _ => panic!(),
+        }
+
+        solver.add_expr(x.expr().le(IntExpr::Const(3)));
+        assert!(matches!(
+            solver.solve_with_timeout(Duration::from_secs(100)),
+            SolveOutcome::Unsat
+        ));
     }
 
     #[test]
-    fn test_integration_csp_optimization3() {
+    fn test_integration_enumerate() {
         let mut solver = IntegratedSolver::new();
 
-        let mut vars = vec![];
-        let mut coefs = vec![];
-        for _ in 0..15 {
-            let v = solver.new_bool_var();
-            vars.push(v);
-            coefs.push((
-                Box::new(v.expr().ite(IntExpr::Const(1), IntExpr::Const(0))),
-                1,
-            ));
+        let b = solver.new_bool_var();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        solver.add_expr((x.expr() + y.expr()).le(IntExpr::Const(3)));
+        solver.add_expr(b.expr().imp(x.expr().eq(y.expr())));
+
+        let mut expected = vec![];
+        for xv in 0..=3 {
+            expected.push((false, xv));
         }
-        solver.add_expr(vars[0].expr());
-        solver.add_expr(IntExpr::Linear(coefs).eq(IntExpr::Const(0)));
+        expected.push((true, 0));
+        expected.push((true, 1));
 
-        let res = solver.solve();
-        assert!(res.is_none());
+        for _ in 0..2 {
+            let mut answers = solver
+                .enumerate(&[b], &[x])
+                .map(|a| (a.get_bool(b).unwrap(), a.get_int(x).unwrap()))
+                .collect::<Vec<_>>();
+            answers.sort();
+            assert_eq!(answers, expected);
+        }
+
+        // the iterator can be stopped midway
+        assert_eq!(solver.enumerate(&[], &[x, y]).take(3).count(), 3);
+        assert_eq!(solver.enumerate(&[], &[x, y]).count(), 10);
+        assert!(solver.solve().is_some());
     }
 
     #[test]
-    fn test_integration_irrefutable_logic1() {
+    fn test_integration_for_each_solution() {
         let mut solver = IntegratedSolver::new();
 
-        let x = solver.new_bool_var();
-        let y = solver.new_bool_var();
-        let z = solver.new_bool_var();
-        solver.add_expr(x.expr() | y.expr());
-        solver.add_expr(y.expr() | z.expr());
-        solver.add_expr(!(x.expr() & z.expr()));
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(4)));
 
-        let res = solver.decide_irrefutable_facts(&[x, y, z], &[]);
-        assert!(res.is_some());
-        let res = res.unwrap();
-        assert_eq!(res.get_bool(x), None);
-        assert_eq!(res.get_bool(y), Some(true));
-        assert_eq!(res.get_bool(z), None);
+        let mut answers = vec![];
+        let n_calls = solver.for_each_solution(&[], &[x, y], |model| {
+            answers.push((model.get_int(x), model.get_int(y)));
+            true
+        });
+        assert_eq!(n_calls, 6);
+        answers.sort();
+        assert_eq!(
+            answers,
+            vec![(1, 3), (2, 2), (2, 3), (3, 1), (3, 2), (3, 3)]
+        );
+
+        let mut n_found = 0;
+        let n_calls = solver.for_each_solution(&[], &[x, y], |model| {
+            n_found += 1;
+            model.get_int(x) != 3
+        });
+        assert_eq!(n_calls, n_found);
+        assert!(n_calls <= 4);
+
+        solver.add_expr(x.expr().eq(IntExpr::Const(3)));
+        let mut n_found = 0;
+        let n_calls = solver.for_each_solution(&[], &[x, y], |_| {
+            n_found += 1;
+            false
+        });
+        assert_eq!(n_calls, 1);
+        assert_eq!(n_found, 1);
+        assert_eq!(solver.for_each_solution(&[], &[x, y], |_| true), 3);
     }
 
     #[test]
-    fn test_integration_irrefutable_complex1() {
+    fn test_integration_is_unique() {
         let mut solver = IntegratedSolver::new();
 
-        let x = solver.new_bool_var();
-        let a = solver.new_int_var(Domain::range(0, 2));
-        let b = solver.new_int_var(Domain::range(0, 2));
-        solver.add_expr(x.expr().ite(a.expr(), b.expr()).eq(a.expr()));
-        solver.add_expr(a.expr().ne(b.expr()));
+        let b = solver.new_bool_var();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        solver.add_expr(b.expr().iff(x.expr().lt(y.expr())));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(5)));
+
+        match solver.is_unique(&[b], &[x, y]) {
+            Uniqueness::Multiple(first, second) => {
+                let mut answers = vec![
+                    (first.get_int(x).unwrap(), first.get_int(y).unwrap()),
+                    (second.get_int(x).unwrap(), second.get_int(y).unwrap()),
+                ];
+                answers.sort();
+                assert!(answers[0] != answers[1]);
+                for (xv, yv) in answers {
+                    assert!(xv + yv >= 5);
+                }
+            }
+            _ => panic!(),
+        }
 
-        let res = solver.decide_irrefutable_facts(&[x], &[a, b]);
-        assert!(res.is_some());
-        let res = res.unwrap();
-        assert_eq!(res.get_bool(x), Some(true));
-        assert_eq!(res.get_int(a), None);
-        assert_eq!(res.get_int(b), None);
+        // (x, y) = (2, 3) for b = true and (3, 2), (3, 3) for b = false
+        assert!(matches!(
+            solver.is_unique(&[b], &[]),
+            Uniqueness::Multiple(_, _)
+        ));
+
+        solver.add_expr(x.expr().ge(y.expr()));
+        match solver.is_unique(&[b], &[x, y]) {
+            Uniqueness::Multiple(first, second) => {
+                assert!(!first.get_bool(b).unwrap());
+                assert!(!second.get_bool(b).unwrap());
+            }
+            _ => panic!(),
+        }
+        match solver.is_unique(&[b], &[]) {
+            Uniqueness::Unique(answer) => assert!(!answer.get_bool(b).unwrap()),
+            _ => panic!(),
+        }
+
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(6)));
+        match solver.is_unique(&[b], &[x, y]) {
+            Uniqueness::Unique(answer) => {
+                assert_eq!(answer.get_int(x), Some(3));
+                assert_eq!(answer.get_int(y), Some(3));
+            }
+            _ => panic!(),
+        }
+
+        solver.add_expr(x.expr().ne(y.expr()));
+        assert!(matches!(solver.is_unique(&[b], &[x, y]), Uniqueness::None));
     }
 
     #[test]
-    fn test_integration_irrefutable_complex2() {
-        let mut solver = IntegratedSolver::new();
+    fn test_integration_push_pop() {
+        let mut config = Config::default();
+        config.enable_push_pop = true;
+        let mut solver = IntegratedSolver::with_config(config);
 
-        let x = solver.new_bool_var();
-        let a = solver.new_int_var(Domain::range(0, 2));
-        let b = solver.new_int_var(Domain::range(0, 2));
-        let c = solver.new_int_var(Domain::range(0, 2));
-        solver.add_expr(
-            x.expr()
-                .ite(a.expr(), b.expr())
-                .lt(c.expr() - IntExpr::Const(1)),
-        );
-        solver.add_expr(a.expr().ne(b.expr()));
+        let x = solver.new_int_var(Domain::range(0, 3));
+        let y = solver.new_int_var(Domain::range(0, 3));
+        solver.add_expr((x.expr() + y.expr()).ge(IntExpr::Const(4)));
 
-        let res = solver.decide_irrefutable_facts(&[x], &[a, b, c]);
-        assert!(res.is_some());
-        let res = res.unwrap();
-        assert_eq!(res.get_bool(x), None);
-        assert_eq!(res.get_int(a), None);
-        assert_eq!(res.get_int(b), None);
-        assert_eq!(res.get_int(c), Some(2));
+        let solutions = |solver: &mut IntegratedSolver| {
+            let mut ret = solver
+                .enumerate(&[], &[x, y])
+                .map(|a| (a.get_int(x).unwrap(), a.get_int(y).unwrap()))
+                .collect::<Vec<_>>();
+            ret.sort();
+            ret
+        };
+        let expected = vec![(1, 3), (2, 2), (2, 3), (3, 1), (3, 2), (3, 3)];
+        assert_eq!(solutions(&mut solver), expected);
+
+        let num_var = solver.sat.num_var();
+        solver.push();
+        let b = solver.new_bool_var();
+        solver.add_expr(b.expr().iff(x.expr().lt(y.expr())));
+        solver.add_expr(b.expr());
+        assert_eq!(solutions(&mut solver), vec![(1, 3), (2, 3)]);
+        assert!(solver.sat.num_var() > num_var);
+
+        solver.push();
+        solver.add_expr(x.expr().eq(IntExpr::Const(2)));
+        assert_eq!(solutions(&mut solver), vec![(2, 3)]);
+        solver.pop();
+        assert_eq!(solutions(&mut solver), vec![(1, 3), (2, 3)]);
+
+        solver.pop();
+        assert_eq!(solver.sat.num_var(), num_var);
+        assert_eq!(solutions(&mut solver), expected);
+
+        solver.push();
+        solver.add_expr(x.expr().eq(y.expr()));
+        solver.add_expr(x.expr().lt(IntExpr::Const(2)));
+        assert!(solver.solve().is_none());
+        solver.pop();
+        assert_eq!(solutions(&mut solver), expected);
     }
 
     #[test]
-    fn test_integration_irrefutable_many_terms() {
+    fn test_integration_solve_max() {
         let mut solver = IntegratedSolver::new();
 
-        let mut ivars = vec![];
-        for _ in 0..30 {
-            ivars.push(solver.new_int_var(Domain::range(0, 1)));
+        let a = solver.new_bool_var();
+        let b = solver.new_bool_var();
+        let c = solver.new_bool_var();
+        let x = solver.new_int_var(Domain::range(0, 5));
+        solver.add_expr(!(a.expr() & b.expr()));
+        solver.add_expr(!(a.expr() & c.expr()));
+        solver.add_expr(a.expr() | x.expr().ge(IntExpr::Const(2)));
+        solver.add_soft_expr(a.expr(), 3);
+        solver.add_soft_expr(b.expr(), 2);
+        solver.add_soft_expr(c.expr(), 2);
+        solver.add_soft_expr(x.expr().le(IntExpr::Const(1)), 2);
+        solver.add_soft_expr(x.expr().eq(IntExpr::Const(4)), 2);
+
+        // a = (x <= 1) = true (3 + 2) is worse than b = c = (x == 4) = true (2 + 2 + 2)
+        {
+            let res = solver.solve_max();
+            assert!(res.is_some());
+            let (model, weight) = res.unwrap();
+            assert_eq!(weight, 6);
+            assert!(!model.get_bool(a));
+            assert!(model.get_bool(b));
+            assert!(model.get_bool(c));
+            assert_eq!(model.get_int(x), 4);
         }
-        solver.add_expr(
-            IntExpr::Linear(ivars.iter().map(|v| (Box::new(v.expr()), 1)).collect())
-                .ge(IntExpr::Const(10)),
-        );
 
-        let x = solver.new_bool_var();
-        solver.add_expr(
-            IntExpr::Linear(ivars.iter().map(|v| (Box::new(v.expr()), 1)).collect())
-                .ge(IntExpr::Const(9))
-                .iff(x.expr()),
-        );
+        // bounds used in the optimization do not remain
+        solver.add_expr(x.expr().le(IntExpr::Const(2)));
+        assert!(solver.solve().is_some());
+        {
+            let res = solver.solve_max();
+            assert!(res.is_some());
+            let (model, weight) = res.unwrap();
+            assert_eq!(weight, 5);
+            let xv = model.get_int(x);
+            assert!(model.get_bool(a));
+            assert!(xv <= 1);
+        }
 
-        let res = solver.decide_irrefutable_facts(&[x], &ivars);
-        assert!(res.is_some());
-        let res = res.unwrap();
-        assert_eq!(res.get_bool(x), Some(true));
-        assert_eq!(res.get_int(ivars[0]), None);
+        solver.add_expr(a.expr() & b.expr());
+        assert!(solver.solve_max().is_none());
+
+        // large weights are not expanded into as many literals
+        let mut solver = IntegratedSolver::new();
+        let p = solver.new_bool_var();
+        let q = solver.new_bool_var();
+        let r = solver.new_bool_var();
+        solver.add_expr(!(p.expr() & q.expr()));
+        solver.add_expr(!(p.expr() & r.expr()));
+        solver.add_soft_expr(p.expr(), 1_000_000_000);
+        solver.add_soft_expr(q.expr(), 600_000_000);
+        solver.add_soft_expr(r.expr(), 600_000_000);
+        let (model, weight) = solver.solve_max().unwrap();
+        assert_eq!(weight, 1_200_000_000);
+        assert!(!model.get_bool(p));
     }
 
     #[test]
-    fn test_integration_irrefutable_alldifferent() {
+    fn test_integration_soft_expr_count_solutions() {
         let mut solver = IntegratedSolver::new();
 
-        let a = solver.new_int_var(Domain::range(1, 3));
-        let b = solver.new_int_var(Domain::range(1, 3));
-        let c = solver.new_int_var(Domain::range(1, 3));
-        let d = solver.new_int_var(Domain::range(1, 4));
-        solver.add_constraint(Stmt::AllDifferent(vec![
-            a.expr(),
-            b.expr(),
-            c.expr(),
-            d.expr(),
-        ]));
+        let a = solver.new_bool_var();
+        let b = solver.new_bool_var();
+        solver.add_expr(!(a.expr() & b.expr()));
+        solver.add_soft_expr(a.expr(), 1);
+        solver.add_soft_expr(b.expr(), 1);
 
-        let res = solver.decide_irrefutable_facts(&[], &[a, b, c, d]);
-        assert!(res.is_some());
-        let res = res.unwrap();
-        assert_eq!(res.get_int(a), None);
-        assert_eq!(res.get_int(b), None);
-        assert_eq!(res.get_int(c), None);
-        assert_eq!(res.get_int(d), Some(4));
+        // the variables introduced by the soft constraints are not counted
+        assert_eq!(solver.count_solutions(None), 3);
+        assert_eq!(solver.solve_max().unwrap().1, 1);
+        assert_eq!(solver.count_solutions(None), 3);
+        assert_eq!(solver.enumerate_valid_assignments().len(), 3);
     }
 
     #[test]
@@ -1074,6 +3117,29 @@ mod tests {
         assert_eq!(n_ans, 21);
     }
 
+    #[test]
+    fn test_integration_statistics() {
+        let mut solver = IntegratedSolver::new();
+        assert!(solver.statistics().decisions.is_none());
+
+        // pigeonhole principle with 7 pigeons and 6 holes
+        pigeonhole(&mut solver, 6);
+
+        assert!(solver.solve().is_none());
+        let stats = solver.statistics();
+        assert!(stats.decisions.unwrap() > 0);
+        assert!(stats.conflicts.unwrap() > 0);
+        assert!(stats.propagations.unwrap() > 0);
+        assert!(stats.restarts.is_some());
+        assert!(stats.learnts.is_some());
+
+        // the instance is already known to be unsatisfiable, so no search is needed
+        assert!(solver.solve().is_none());
+        let stats = solver.statistics();
+        assert_eq!(stats.decisions, Some(0));
+        assert_eq!(stats.conflicts, Some(0));
+    }
+
     #[test]
     fn test_integration_seed() {
         let mut propagations = vec![];
@@ -1187,6 +3253,34 @@ mod tests {
         tester.check_expect(104);
     }
 
+    #[test]
+    fn test_integration_exhaustive_div_mod_const() {
+        let mut tester = IntegrationTester::new();
+
+        let a = tester.new_int_var(Domain::range(-7, 7));
+        let b = tester.new_int_var(Domain::range(-4, 4));
+        let c = tester.new_int_var(Domain::range(-3, 3));
+        tester.add_expr((a.expr() / IntExpr::Const(3)).eq(b.expr()));
+        tester.add_expr((a.expr() % IntExpr::Const(-2)).ne(c.expr()));
+
+        tester.check();
+    }
+
+    #[cfg(feature = "csp-extra-constraints")]
+    #[test]
+    fn test_integration_exhaustive_div_mod() {
+        let mut tester = IntegrationTester::new();
+
+        let a = tester.new_int_var(Domain::range(-5, 5));
+        let b = tester.new_int_var_from_list(vec![-3, -2, 2, 3]);
+        let c = tester.new_int_var(Domain::range(-3, 3));
+        let d = tester.new_int_var(Domain::range(-2, 2));
+        tester.add_expr((a.expr() / b.expr()).eq(c.expr()));
+        tester.add_expr((a.expr() % b.expr()).ge(d.expr()));
+
+        tester.check();
+    }
+
     #[test]
     fn test_integration_exhaustive_complex1() {
         let mut tester = IntegrationTester::new();
@@ -1523,6 +3617,191 @@ mod tests {
         tester.check();
     }
 
+    #[test]
+    fn test_integration_cardinality() {
+        let mut tester = IntegrationTester::new();
+
+        let vars = (0..5)
+            .map(|_| tester.new_bool_var().expr())
+            .collect::<Vec<_>>();
+        let x = tester.new_int_var(Domain::range(0, 2));
+        tester.add_constraint(Stmt::Cardinality(
+            vec![
+                vars[0].clone(),
+                vars[1].clone() | vars[2].clone(),
+                !vars[3].clone(),
+                x.expr().eq(IntExpr::Const(1)),
+                vars[4].clone(),
+            ],
+            2,
+            3,
+        ));
+        tester.add_constraint(Stmt::Cardinality(vars[2..].to_vec(), 1, 1));
+
+        tester.check();
+    }
+
+    #[test]
+    fn test_integration_directed_reachable1() {
+        let mut tester = IntegrationTester::new();
+
+        let mut vars = vec![];
+        for _ in 0..9 {
+            vars.push(tester.new_bool_var().expr());
+        }
+
+        // 3x3 grid whose horizontal edges go right and vertical edges go down, except that
+        // the vertical edges in the middle column go up
+        tester.add_constraint(Stmt::DirectedReachable(
+            0,
+            vars,
+            vec![
+                (0, 1),
+                (1, 2),
+                (3, 4),
+                (4, 5),
+                (6, 7),
+                (7, 8),
+                (0, 3),
+                (4, 1),
+                (2, 5),
+                (3, 6),
+                (7, 4),
+                (5, 8),
+            ],
+        ));
+
+        tester.check();
+    }
+
+    #[test]
+    fn test_integration_directed_reachable2() {
+        let mut solver = IntegratedSolver::new();
+
+        let vars = (0..6).map(|_| solver.new_bool_var()).collect::<Vec<_>>();
+        // 0 -> 1 -> 2 -> 0, 3 -> 4 -> 5, 2 -> 3
+        solver.add_constraint(Stmt::DirectedReachable(
+            0,
+            vars.iter().map(|v| v.expr()).collect(),
+            vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (2, 3)],
+        ));
+        solver.add_expr(vars[5].expr());
+
+        {
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            for &v in &vars {
+                assert!(model.get_bool(v));
+            }
+        }
+
+        // 5 is not reachable without 4
+        solver.add_expr(!vars[4].expr());
+        assert!(solver.solve().is_none());
+    }
+
+    fn grid_edges(height: usize, width: usize) -> Vec<(usize, usize)> {
+        let mut edges = vec![];
+        for y in 0..height {
+            for x in 0..width {
+                if y + 1 < height {
+                    edges.push((y * width + x, (y + 1) * width + x));
+                }
+                if x + 1 < width {
+                    edges.push((y * width + x, y * width + x + 1));
+                }
+            }
+        }
+        edges
+    }
+
+    #[test]
+    fn test_integration_single_cycle1() {
+        let mut tester = IntegrationTester::new();
+
+        let edges = grid_edges(3, 3);
+        let mut vars = vec![];
+        for _ in 0..edges.len() {
+            vars.push(tester.new_bool_var().expr());
+        }
+        tester.add_constraint(Stmt::SingleCycle(vars, edges));
+
+        tester.check();
+    }
+
+    #[test]
+    fn test_integration_single_cycle2() {
+        let mut solver = IntegratedSolver::new();
+
+        let edges = grid_edges(3, 3);
+        let vars = (0..edges.len())
+            .map(|_| solver.new_bool_var())
+            .collect::<Vec<_>>();
+        solver.add_constraint(Stmt::SingleCycle(
+            vars.iter().map(|v| v.expr()).collect(),
+            edges,
+        ));
+
+        // 4 unit squares, 4 rectangles of 2 cells, 4 L-shapes of 3 cells and the outer boundary
+        let mut n_cycles = 0;
+        while let Some(model) = solver.solve() {
+            n_cycles += 1;
+            let blocking = vars
+                .iter()
+                .map(|&v| {
+                    if model.get_bool(v) {
+                        Box::new(!v.expr())
+                    } else {
+                        Box::new(v.expr())
+                    }
+                })
+                .collect::<Vec<_>>();
+            solver.add_expr(BoolExpr::Or(blocking));
+        }
+        assert_eq!(n_cycles, 13);
+    }
+
+    #[test]
+    fn test_integration_acyclic1() {
+        let mut tester = IntegrationTester::new();
+
+        let edges = grid_edges(2, 3);
+        let mut vars = vec![];
+        for _ in 0..edges.len() {
+            vars.push(tester.new_bool_var().expr());
+        }
+        tester.add_constraint(Stmt::Acyclic(vars, edges));
+
+        tester.check();
+    }
+
+    #[test]
+    fn test_integration_acyclic2() {
+        let mut solver = IntegratedSolver::new();
+
+        // a triangle 0-1-2 with a pendant vertex 3, and a self-loop on 3
+        let edges = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 3)];
+        let vars = (0..edges.len())
+            .map(|_| solver.new_bool_var())
+            .collect::<Vec<_>>();
+        solver.add_constraint(Stmt::Acyclic(
+            vars.iter().map(|v| v.expr()).collect(),
+            edges,
+        ));
+        solver.add_expr(vars[0].expr() & vars[1].expr() & vars[3].expr());
+        {
+            let model = solver.solve();
+            assert!(model.is_some());
+            let model = model.unwrap();
+            assert!(!model.get_bool(vars[2]));
+            assert!(!model.get_bool(vars[4]));
+        }
+
+        solver.add_expr(vars[2].expr() | vars[4].expr());
+        assert!(solver.solve().is_none());
+    }
+
     #[test]
     fn test_integration_graph_division1() {
         let mut tester = IntegrationTester::new();
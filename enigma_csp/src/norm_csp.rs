@@ -1,11 +1,12 @@
 // Normalized CSP
 
 use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
 use std::ops::Not;
 
 use super::domain::Domain;
 use crate::arithmetic::{CheckedInt, CmpOp, Range};
-use crate::util::{ConvertMapIndex, UpdateStatus};
+use crate::util::{ConvertMap, ConvertMapIndex, UpdateStatus};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub struct BoolVar(usize);
@@ -82,7 +83,7 @@ impl LinearLit {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Constraint {
     pub(super) bool_lit: Vec<BoolLit>,
     pub(super) linear_lit: Vec<LinearLit>,
@@ -140,6 +141,7 @@ impl Constraint {
     }
 }
 
+#[derive(Clone)]
 pub(super) enum IntVarRepresentation {
     Domain(super::domain::Domain),
     Binary(BoolLit, CheckedInt, CheckedInt), // condition, false, true (order encoding)
@@ -162,7 +164,6 @@ impl IntVarRepresentation {
         }
     }
 
-    #[allow(dead_code)]
     pub(super) fn is_domain(&self) -> bool {
         match self {
             IntVarRepresentation::Domain(_) => true,
@@ -170,7 +171,6 @@ impl IntVarRepresentation {
         }
     }
 
-    #[allow(dead_code)]
     fn as_domain(&self) -> &super::domain::Domain {
         match self {
             IntVarRepresentation::Domain(domain) => domain,
@@ -193,6 +193,7 @@ impl IntVarRepresentation {
     }
 }
 
+#[derive(Clone)]
 pub(super) struct NormCSPVars {
     num_bool_var: usize,
     int_var: Vec<IntVarRepresentation>,
@@ -335,18 +336,30 @@ impl NormCSPVars {
     }
 }
 
+#[derive(Clone)]
 pub enum ExtraConstraint {
     ActiveVerticesConnected(Vec<BoolLit>, Vec<(usize, usize)>),
+    /// `DirectedReachable(source, vertices, edges)`: see `Stmt::DirectedReachable`.
+    DirectedReachable(usize, Vec<BoolLit>, Vec<(usize, usize)>),
     Mul(IntVar, IntVar, IntVar),
+    /// `Cardinality(lits, low, high)`: see `Stmt::Cardinality`.
+    Cardinality(Vec<BoolLit>, usize, usize),
     ExtensionSupports(Vec<IntVar>, Vec<Vec<Option<CheckedInt>>>),
     GraphDivision(Vec<Option<IntVar>>, Vec<(usize, usize)>, Vec<BoolLit>),
+    /// `Implies(trigger, constraint)`: `constraint` must hold if `trigger` is true.
+    /// Unlike full reification, `constraint` may hold even if `trigger` is false.
+    Implies(BoolLit, Constraint),
 }
 
+#[derive(Clone)]
 pub struct NormCSP {
     pub(super) vars: NormCSPVars,
     pub(super) constraints: Vec<Constraint>,
     pub(super) extra_constraints: Vec<ExtraConstraint>,
     pub(super) num_encoded_vars: usize,
+    /// Union-find of integer variables merged by `merge_equal_int_vars`.
+    /// `int_var_parent[v]` is `None` iff `v` is the representative of its class.
+    int_var_parent: ConvertMap<IntVar, IntVar>,
     inconsistent: bool,
 }
 
@@ -360,6 +373,7 @@ impl NormCSP {
             constraints: vec![],
             extra_constraints: vec![],
             num_encoded_vars: 0,
+            int_var_parent: ConvertMap::new(),
             inconsistent: false,
         }
     }
@@ -417,6 +431,174 @@ impl NormCSP {
         self.inconsistent
     }
 
+    /// Returns the representative of the integer variables merged with `var`.
+    pub fn representative_int_var(&self, var: IntVar) -> IntVar {
+        let mut var = var;
+        while let Some(parent) = self.int_var_parent[var] {
+            var = parent;
+        }
+        var
+    }
+
+    /// Merges unencoded integer variables which are forced to be equal by a constraint of the
+    /// form `x - y == 0`, and rewrites the remaining constraints so that only the representative
+    /// of each class is used.
+    /// Values of merged variables are still available through `EncodeMap`, since the encoder
+    /// lets every merged variable share the encoding of its representative.
+    pub fn merge_equal_int_vars(&mut self) {
+        let constraints = std::mem::take(&mut self.constraints);
+        let mut remaining = vec![];
+        let mut merged = false;
+
+        for constraint in constraints {
+            if let Some((x, y)) = as_equality_of_vars(&constraint) {
+                let x = self.representative_int_var(x);
+                let y = self.representative_int_var(y);
+                if x == y {
+                    continue;
+                }
+                if self.is_mergeable_int_var(x) && self.is_mergeable_int_var(y) {
+                    let (repr, other) = if x < y { (x, y) } else { (y, x) };
+                    self.int_var_parent[other] = Some(repr);
+                    merged = true;
+                    continue;
+                }
+            }
+            remaining.push(constraint);
+        }
+        self.constraints = remaining;
+
+        if !merged {
+            return;
+        }
+
+        let unencoded_vars = self.unencoded_int_vars().collect::<Vec<_>>();
+        for &var in &unencoded_vars {
+            let repr = self.representative_int_var(var);
+            if repr == var {
+                continue;
+            }
+            let domain = self.vars.int_var(var).as_domain().clone();
+            if let IntVarRepresentation::Domain(repr_domain) = &mut self.vars.int_var[repr.0] {
+                let refined = repr_domain.clone() & domain;
+                if refined.is_infeasible() {
+                    self.inconsistent = true;
+                }
+                *repr_domain = refined;
+            }
+        }
+        for &var in &unencoded_vars {
+            let repr = self.representative_int_var(var);
+            if repr != var {
+                let domain = self.vars.int_var(repr).as_domain().clone();
+                self.vars.int_var[var.0] = IntVarRepresentation::Domain(domain);
+            }
+        }
+
+        let mut constraints = std::mem::take(&mut self.constraints);
+        for constraint in &mut constraints {
+            self.substitute_representatives(constraint);
+        }
+        self.constraints = constraints;
+
+        let mut extra_constraints = std::mem::take(&mut self.extra_constraints);
+        for constraint in &mut extra_constraints {
+            match constraint {
+                ExtraConstraint::ActiveVerticesConnected(_, _) => (),
+                ExtraConstraint::DirectedReachable(_, _, _) => (),
+                ExtraConstraint::Cardinality(_, _, _) => (),
+                ExtraConstraint::Mul(x, y, m) => {
+                    *x = self.representative_int_var(*x);
+                    *y = self.representative_int_var(*y);
+                    *m = self.representative_int_var(*m);
+                }
+                ExtraConstraint::ExtensionSupports(vars, _) => {
+                    for v in vars {
+                        *v = self.representative_int_var(*v);
+                    }
+                }
+                ExtraConstraint::GraphDivision(sizes, _, _) => {
+                    for v in sizes.iter_mut().flatten() {
+                        *v = self.representative_int_var(*v);
+                    }
+                }
+                ExtraConstraint::Implies(_, constraint) => {
+                    self.substitute_representatives(constraint);
+                }
+            }
+        }
+        self.extra_constraints = extra_constraints;
+    }
+
+    /// Replaces integer variables with a singleton domain by their values in all the pending
+    /// constraints. Linear literals which become constant are evaluated, so constraints
+    /// containing a satisfied one are removed.
+    /// The variables themselves are kept (and encoded without any literal) so that their values
+    /// are still available.
+    pub fn inline_constant_int_vars(&mut self) {
+        let constraints = std::mem::take(&mut self.constraints);
+        self.constraints = constraints
+            .into_iter()
+            .filter_map(|constraint| self.fold_constant_int_vars(constraint))
+            .collect();
+
+        let extra_constraints = std::mem::take(&mut self.extra_constraints);
+        for constraint in extra_constraints {
+            match constraint {
+                ExtraConstraint::Implies(trigger, constraint) => {
+                    if let Some(constraint) = self.fold_constant_int_vars(constraint) {
+                        self.extra_constraints
+                            .push(ExtraConstraint::Implies(trigger, constraint));
+                    }
+                }
+                constraint => self.extra_constraints.push(constraint),
+            }
+        }
+    }
+
+    /// Returns `None` if `constraint` is always satisfied after inlining constant variables.
+    fn fold_constant_int_vars(&self, constraint: Constraint) -> Option<Constraint> {
+        let mut linear_lit = vec![];
+        for lit in constraint.linear_lit {
+            let mut sum = LinearSum::constant(lit.sum.constant);
+            for (&var, &coef) in lit.sum.iter() {
+                let value = match self.vars.int_var(var) {
+                    IntVarRepresentation::Domain(domain) => domain.as_constant(),
+                    IntVarRepresentation::Binary(_, _, _) => None,
+                };
+                match value {
+                    Some(value) => sum.add_constant(value * coef),
+                    None => sum.add_coef(var, coef),
+                }
+            }
+            if sum.is_constant() {
+                if lit.op.compare(sum.constant, CheckedInt::new(0)) {
+                    return None;
+                }
+            } else {
+                linear_lit.push(LinearLit::new(sum, lit.op));
+            }
+        }
+        Some(Constraint {
+            bool_lit: constraint.bool_lit,
+            linear_lit,
+        })
+    }
+
+    fn is_mergeable_int_var(&self, var: IntVar) -> bool {
+        var.0 >= self.num_encoded_vars && self.vars.int_var(var).is_domain()
+    }
+
+    fn substitute_representatives(&self, constraint: &mut Constraint) {
+        for lit in &mut constraint.linear_lit {
+            let mut sum = LinearSum::constant(lit.sum.constant);
+            for (&v, &c) in lit.sum.iter() {
+                sum.add_coef(self.representative_int_var(v), c);
+            }
+            lit.sum = sum;
+        }
+    }
+
     pub fn refine_domain(&mut self) {
         loop {
             let mut update_status = UpdateStatus::NotUpdated;
@@ -437,6 +619,25 @@ impl NormCSP {
     }
 }
 
+/// Returns `(x, y)` if `constraint` is exactly `x - y == 0`.
+fn as_equality_of_vars(constraint: &Constraint) -> Option<(IntVar, IntVar)> {
+    if !constraint.bool_lit.is_empty() || constraint.linear_lit.len() != 1 {
+        return None;
+    }
+    let lit = &constraint.linear_lit[0];
+    if lit.op != CmpOp::Eq || lit.sum.constant != 0 || lit.sum.len() != 2 {
+        return None;
+    }
+    let mut terms = lit.sum.iter();
+    let (&x, &cx) = terms.next().unwrap();
+    let (&y, &cy) = terms.next().unwrap();
+    if (cx == 1 && cy == -1) || (cx == -1 && cy == 1) {
+        Some((x, y))
+    } else {
+        None
+    }
+}
+
 #[derive(Clone)]
 pub struct Assignment {
     bool_val: BTreeMap<BoolVar, bool>,
@@ -488,6 +689,431 @@ impl Assignment {
     }
 }
 
+const SERIALIZATION_MAGIC: &[u8] = b"NCSP\x01";
+
+impl NormCSP {
+    /// Serializes the whole state of this CSP, including the variables and the constraints which
+    /// are not encoded yet. Variable ids are preserved by `from_bytes`, so the deserialized CSP
+    /// is encoded in exactly the same way as this one.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = ByteWriter(SERIALIZATION_MAGIC.to_vec());
+
+        out.usize(self.vars.num_bool_var);
+        out.usize(self.vars.int_var.len());
+        for repr in &self.vars.int_var {
+            match repr {
+                IntVarRepresentation::Domain(Domain::Range(low, high)) => {
+                    out.u8(0);
+                    out.int(*low);
+                    out.int(*high);
+                }
+                IntVarRepresentation::Domain(Domain::Enumerative(cands)) => {
+                    out.u8(1);
+                    out.usize(cands.len());
+                    for &c in cands {
+                        out.int(c);
+                    }
+                }
+                IntVarRepresentation::Binary(cond, f, t) => {
+                    out.u8(2);
+                    out.bool_lit(*cond);
+                    out.int(*f);
+                    out.int(*t);
+                }
+            }
+        }
+
+        out.usize(self.constraints.len());
+        for constraint in &self.constraints {
+            out.constraint(constraint);
+        }
+
+        out.usize(self.extra_constraints.len());
+        for constraint in &self.extra_constraints {
+            match constraint {
+                ExtraConstraint::ActiveVerticesConnected(vertices, edges) => {
+                    out.u8(0);
+                    out.bool_lits(vertices);
+                    out.edges(edges);
+                }
+                ExtraConstraint::DirectedReachable(source, vertices, edges) => {
+                    out.u8(1);
+                    out.usize(*source);
+                    out.bool_lits(vertices);
+                    out.edges(edges);
+                }
+                ExtraConstraint::Mul(x, y, m) => {
+                    out.u8(2);
+                    out.usize(x.0);
+                    out.usize(y.0);
+                    out.usize(m.0);
+                }
+                ExtraConstraint::ExtensionSupports(vars, supports) => {
+                    out.u8(3);
+                    out.usize(vars.len());
+                    for v in vars {
+                        out.usize(v.0);
+                    }
+                    out.usize(supports.len());
+                    for support in supports {
+                        for x in support {
+                            match x {
+                                Some(x) => {
+                                    out.u8(1);
+                                    out.int(*x);
+                                }
+                                None => out.u8(0),
+                            }
+                        }
+                    }
+                }
+                ExtraConstraint::GraphDivision(sizes, edges, edge_lits) => {
+                    out.u8(4);
+                    out.usize(sizes.len());
+                    for size in sizes {
+                        match size {
+                            Some(v) => {
+                                out.u8(1);
+                                out.usize(v.0);
+                            }
+                            None => out.u8(0),
+                        }
+                    }
+                    out.edges(edges);
+                    out.bool_lits(edge_lits);
+                }
+                ExtraConstraint::Implies(trigger, constraint) => {
+                    out.u8(5);
+                    out.bool_lit(*trigger);
+                    out.constraint(constraint);
+                }
+                ExtraConstraint::Cardinality(lits, low, high) => {
+                    out.u8(6);
+                    out.bool_lits(lits);
+                    out.usize(*low);
+                    out.usize(*high);
+                }
+            }
+        }
+
+        out.usize(self.num_encoded_vars);
+        for i in 0..self.vars.int_var.len() {
+            match self.int_var_parent[IntVar(i)] {
+                Some(parent) => {
+                    out.u8(1);
+                    out.usize(parent.0);
+                }
+                None => out.u8(0),
+            }
+        }
+        out.u8(self.inconsistent as u8);
+
+        out.0
+    }
+
+    /// Restores a CSP serialized by `to_bytes`. Returns `None` if `data` is malformed.
+    pub fn from_bytes(data: &[u8]) -> Option<NormCSP> {
+        if !data.starts_with(SERIALIZATION_MAGIC) {
+            return None;
+        }
+        let mut input = ByteReader {
+            data,
+            pos: SERIALIZATION_MAGIC.len(),
+            num_bool_var: 0,
+            num_int_var: 0,
+        };
+
+        let num_bool_var = input.usize()?;
+        input.num_bool_var = num_bool_var;
+        let num_int_var = input.usize()?;
+        let mut int_var = vec![];
+        for _ in 0..num_int_var {
+            let repr = match input.u8()? {
+                0 => {
+                    let low = input.int()?;
+                    let high = input.int()?;
+                    IntVarRepresentation::Domain(Domain::Range(low, high))
+                }
+                1 => {
+                    let n = input.usize()?;
+                    let mut cands = vec![];
+                    for _ in 0..n {
+                        cands.push(input.int()?);
+                    }
+                    IntVarRepresentation::Domain(Domain::Enumerative(cands))
+                }
+                2 => {
+                    let cond = input.bool_lit()?;
+                    let f = input.int()?;
+                    let t = input.int()?;
+                    IntVarRepresentation::Binary(cond, f, t)
+                }
+                _ => return None,
+            };
+            int_var.push(repr);
+        }
+        input.num_int_var = num_int_var;
+
+        let mut constraints = vec![];
+        for _ in 0..input.usize()? {
+            constraints.push(input.constraint()?);
+        }
+
+        let mut extra_constraints = vec![];
+        for _ in 0..input.usize()? {
+            let constraint = match input.u8()? {
+                0 => {
+                    let vertices = input.bool_lits()?;
+                    let edges = input.edges()?;
+                    ExtraConstraint::ActiveVerticesConnected(vertices, edges)
+                }
+                1 => {
+                    let source = input.usize()?;
+                    let vertices = input.bool_lits()?;
+                    let edges = input.edges()?;
+                    ExtraConstraint::DirectedReachable(source, vertices, edges)
+                }
+                2 => {
+                    let x = input.int_var()?;
+                    let y = input.int_var()?;
+                    let m = input.int_var()?;
+                    ExtraConstraint::Mul(x, y, m)
+                }
+                3 => {
+                    let mut vars = vec![];
+                    for _ in 0..input.usize()? {
+                        vars.push(input.int_var()?);
+                    }
+                    let mut supports = vec![];
+                    for _ in 0..input.usize()? {
+                        let mut support = vec![];
+                        for _ in 0..vars.len() {
+                            support.push(match input.u8()? {
+                                0 => None,
+                                1 => Some(input.int()?),
+                                _ => return None,
+                            });
+                        }
+                        supports.push(support);
+                    }
+                    ExtraConstraint::ExtensionSupports(vars, supports)
+                }
+                4 => {
+                    let mut sizes = vec![];
+                    for _ in 0..input.usize()? {
+                        sizes.push(match input.u8()? {
+                            0 => None,
+                            1 => Some(input.int_var()?),
+                            _ => return None,
+                        });
+                    }
+                    let edges = input.edges()?;
+                    let edge_lits = input.bool_lits()?;
+                    ExtraConstraint::GraphDivision(sizes, edges, edge_lits)
+                }
+                5 => {
+                    let trigger = input.bool_lit()?;
+                    let constraint = input.constraint()?;
+                    ExtraConstraint::Implies(trigger, constraint)
+                }
+                6 => {
+                    let lits = input.bool_lits()?;
+                    let low = input.usize()?;
+                    let high = input.usize()?;
+                    ExtraConstraint::Cardinality(lits, low, high)
+                }
+                _ => return None,
+            };
+            extra_constraints.push(constraint);
+        }
+
+        let num_encoded_vars = input.usize()?;
+        if num_encoded_vars > num_int_var {
+            return None;
+        }
+        let mut int_var_parent = ConvertMap::new();
+        for i in 0..num_int_var {
+            match input.u8()? {
+                0 => (),
+                1 => int_var_parent[IntVar(i)] = Some(input.int_var()?),
+                _ => return None,
+            }
+        }
+        let inconsistent = match input.u8()? {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        if input.pos != data.len() {
+            return None;
+        }
+
+        Some(NormCSP {
+            vars: NormCSPVars {
+                num_bool_var,
+                int_var,
+            },
+            constraints,
+            extra_constraints,
+            num_encoded_vars,
+            int_var_parent,
+            inconsistent,
+        })
+    }
+}
+
+/// Writer of the format of `NormCSP::to_bytes`. All integers are written in little endian.
+struct ByteWriter(Vec<u8>);
+
+impl ByteWriter {
+    fn u8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+
+    fn usize(&mut self, v: usize) {
+        self.0.extend_from_slice(&(v as u64).to_le_bytes());
+    }
+
+    fn int(&mut self, v: CheckedInt) {
+        self.0.extend_from_slice(&v.get().to_le_bytes());
+    }
+
+    fn bool_lit(&mut self, lit: BoolLit) {
+        self.usize(lit.var.0);
+        self.u8(lit.negated as u8);
+    }
+
+    fn bool_lits(&mut self, lits: &[BoolLit]) {
+        self.usize(lits.len());
+        for &lit in lits {
+            self.bool_lit(lit);
+        }
+    }
+
+    fn edges(&mut self, edges: &[(usize, usize)]) {
+        self.usize(edges.len());
+        for &(u, v) in edges {
+            self.usize(u);
+            self.usize(v);
+        }
+    }
+
+    fn constraint(&mut self, constraint: &Constraint) {
+        self.bool_lits(&constraint.bool_lit);
+        self.usize(constraint.linear_lit.len());
+        for lit in &constraint.linear_lit {
+            self.usize(lit.sum.len());
+            for (var, &coef) in lit.sum.iter() {
+                self.usize(var.0);
+                self.int(coef);
+            }
+            self.int(lit.sum.constant);
+            self.u8(match lit.op {
+                CmpOp::Eq => 0,
+                CmpOp::Ne => 1,
+                CmpOp::Le => 2,
+                CmpOp::Lt => 3,
+                CmpOp::Ge => 4,
+                CmpOp::Gt => 5,
+            });
+        }
+    }
+}
+
+/// Reader of the format of `NormCSP::to_bytes`, which rejects references to variables beyond
+/// `num_bool_var` or `num_int_var`.
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    num_bool_var: usize,
+    num_int_var: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn bytes<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let ret = self.data.get(self.pos..(self.pos + N))?.try_into().ok()?;
+        self.pos += N;
+        Some(ret)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes::<1>()?[0])
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        usize::try_from(u64::from_le_bytes(self.bytes()?)).ok()
+    }
+
+    fn int(&mut self) -> Option<CheckedInt> {
+        Some(CheckedInt::new(i32::from_le_bytes(self.bytes()?)))
+    }
+
+    fn int_var(&mut self) -> Option<IntVar> {
+        let id = self.usize()?;
+        if id < self.num_int_var {
+            Some(IntVar(id))
+        } else {
+            None
+        }
+    }
+
+    fn bool_lit(&mut self) -> Option<BoolLit> {
+        let id = self.usize()?;
+        if id >= self.num_bool_var {
+            return None;
+        }
+        let negated = match self.u8()? {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Some(BoolLit::new(BoolVar(id), negated))
+    }
+
+    fn bool_lits(&mut self) -> Option<Vec<BoolLit>> {
+        let mut ret = vec![];
+        for _ in 0..self.usize()? {
+            ret.push(self.bool_lit()?);
+        }
+        Some(ret)
+    }
+
+    fn edges(&mut self) -> Option<Vec<(usize, usize)>> {
+        let mut ret = vec![];
+        for _ in 0..self.usize()? {
+            let u = self.usize()?;
+            let v = self.usize()?;
+            ret.push((u, v));
+        }
+        Some(ret)
+    }
+
+    fn constraint(&mut self) -> Option<Constraint> {
+        let mut ret = Constraint::new();
+        ret.bool_lit = self.bool_lits()?;
+        for _ in 0..self.usize()? {
+            let mut sum = LinearSum::new();
+            for _ in 0..self.usize()? {
+                let var = self.int_var()?;
+                let coef = self.int()?;
+                sum.add_coef(var, coef);
+            }
+            sum.constant = self.int()?;
+            let op = match self.u8()? {
+                0 => CmpOp::Eq,
+                1 => CmpOp::Ne,
+                2 => CmpOp::Le,
+                3 => CmpOp::Lt,
+                4 => CmpOp::Ge,
+                5 => CmpOp::Gt,
+                _ => return None,
+            };
+            ret.add_linear(LinearLit::new(sum, op));
+        }
+        Some(ret)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -606,4 +1232,90 @@ mod tests {
 
         norm_csp.vars.refine_domain(&constraint1);
     }
+
+    fn enumerate_encoded_assignments(mut norm_csp: NormCSP) -> Vec<(Vec<bool>, Vec<i32>)> {
+        use crate::config::Config;
+        use crate::encoder::{encode, EncodeMap};
+        use crate::sat::SAT;
+
+        let bool_vars = norm_csp.bool_vars_iter().collect::<Vec<_>>();
+        let int_vars = norm_csp.int_vars_iter().collect::<Vec<_>>();
+        let mut sat = SAT::new();
+        let mut map = EncodeMap::new();
+        encode(&mut norm_csp, &mut sat, &mut map, &Config::default());
+
+        let mut lits = bool_vars
+            .iter()
+            .map(|&v| map.get_bool_var(v).unwrap())
+            .collect::<Vec<_>>();
+        for &v in &int_vars {
+            lits.extend_from_slice(map.int_var_lits(v).unwrap());
+        }
+
+        let mut ret = vec![];
+        while let Some(model) = sat.solve() {
+            let bool_values = bool_vars
+                .iter()
+                .map(|&v| model.assignment_lit(map.get_bool_var(v).unwrap()))
+                .collect();
+            let int_values = int_vars
+                .iter()
+                .map(|&v| map.get_int_value(&model, v).unwrap())
+                .collect();
+            ret.push((bool_values, int_values));
+
+            let refutation_clause = lits
+                .iter()
+                .map(|&l| if model.assignment_lit(l) { !l } else { l })
+                .collect::<Vec<_>>();
+            sat.add_clause(&refutation_clause);
+        }
+        ret.sort();
+        ret
+    }
+
+    #[test]
+    fn test_norm_csp_serialization() {
+        let mut norm_csp = NormCSP::new();
+
+        let p = norm_csp.new_bool_var();
+        let q = norm_csp.new_bool_var();
+        let a = norm_csp.new_int_var(Domain::range(0, 3));
+        let b = norm_csp.new_int_var(Domain::enumerative(vec![-1, 2, 4]));
+        let c = norm_csp.new_binary_int_var(
+            BoolLit::new(q, false),
+            CheckedInt::new(5),
+            CheckedInt::new(1),
+        );
+        let m = norm_csp.new_int_var(Domain::range(-4, 12));
+
+        let mut constraint = Constraint::new();
+        constraint.add_bool(BoolLit::new(p, true));
+        constraint.add_linear(LinearLit::new(
+            construct_linear_sum(&[(a, 1), (b, 2)], -3),
+            CmpOp::Ge,
+        ));
+        norm_csp.add_constraint(constraint);
+
+        let mut constraint = Constraint::new();
+        constraint.add_linear(LinearLit::new(
+            construct_linear_sum(&[(a, 1), (c, -1)], 0),
+            CmpOp::Ne,
+        ));
+        norm_csp.add_extra_constraint(ExtraConstraint::Implies(BoolLit::new(p, false), constraint));
+        norm_csp.add_extra_constraint(ExtraConstraint::Mul(a, b, m));
+
+        let bytes = norm_csp.to_bytes();
+        let restored = NormCSP::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(restored.bool_vars_iter().count(), 2);
+        assert_eq!(restored.int_vars_iter().count(), 4);
+
+        let expected = enumerate_encoded_assignments(norm_csp);
+        assert!(!expected.is_empty());
+        assert_eq!(enumerate_encoded_assignments(restored), expected);
+
+        assert!(NormCSP::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(NormCSP::from_bytes(&bytes[1..]).is_none());
+    }
 }
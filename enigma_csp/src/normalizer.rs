@@ -18,6 +18,7 @@ fn new_hash_map<K, V>() -> HashMap<K, V> {
 
 #[cfg(target_arch = "wasm32")]
 mod deterministic_hash_map {
+    #[derive(Clone)]
     pub struct DetState;
 
     impl std::hash::BuildHasher for DetState {
@@ -39,6 +40,7 @@ type HashMap<K, V> = std::collections::HashMap<K, V, deterministic_hash_map::Det
 #[cfg(target_arch = "wasm32")]
 use deterministic_hash_map::new_hash_map;
 
+#[derive(Clone)]
 pub struct NormalizeMap {
     bool_map: ConvertMap<BoolVar, NBoolLit>,
     int_map: ConvertMap<IntVar, NIntVar>,
@@ -265,6 +267,28 @@ fn normalize_stmt(env: &mut NormalizerEnv, stmt: Stmt) {
                     edges,
                 ));
         }
+        Stmt::DirectedReachable(source, vertices, edges) => {
+            let vertices_converted = vertices
+                .into_iter()
+                .map(|e| equivalent_bool_lit(env, e))
+                .collect::<Vec<_>>();
+            env.norm
+                .add_extra_constraint(ExtraConstraint::DirectedReachable(
+                    source,
+                    vertices_converted,
+                    edges,
+                ));
+        }
+        Stmt::Cardinality(exprs, low, high) => {
+            let lits = exprs
+                .into_iter()
+                .map(|e| equivalent_bool_lit(env, e))
+                .collect::<Vec<_>>();
+            env.norm
+                .add_extra_constraint(ExtraConstraint::Cardinality(lits, low, high));
+        }
+        Stmt::SingleCycle(edge_exprs, edges) => normalize_single_cycle(env, edge_exprs, edges),
+        Stmt::Acyclic(edge_exprs, edges) => normalize_acyclic(env, edge_exprs, edges),
         Stmt::Circuit(vars) => normalize_circuit(env, vars),
         Stmt::ExtensionSupports(vars, supports) => {
             normalize_extension_supports(env, vars, supports)
@@ -371,7 +395,7 @@ fn tseitin_transformation_int(
             tseitin_transformation_int(env, extra, f);
         }
         IntExpr::Abs(x) => tseitin_transformation_int(env, extra, x),
-        IntExpr::Mul(x, y) => {
+        IntExpr::Mul(x, y) | IntExpr::Div(x, y) | IntExpr::Mod(x, y) => {
             tseitin_transformation_int(env, extra, x);
             tseitin_transformation_int(env, extra, y);
         }
@@ -708,7 +732,79 @@ fn normalize_int_expr(env: &mut NormalizerEnv, expr: &IntExpr) -> LinearSum {
                 .add_extra_constraint(ExtraConstraint::Mul(xvar, yvar, zvar));
             LinearSum::singleton(zvar)
         }
+        IntExpr::Div(x, y) | IntExpr::Mod(x, y) => {
+            if let Some(&v) = env.map.int_expr_equivalence.get(expr) {
+                return LinearSum::singleton(v);
+            }
+            let (q, r) = normalize_div_mod(env, x, y);
+
+            // both of the quotient and the remainder are registered for reuse
+            let div_expr = IntExpr::Div(x.clone(), y.clone());
+            let mod_expr = IntExpr::Mod(x.clone(), y.clone());
+            assert!(env.map.int_expr_equivalence.insert(div_expr, q).is_none());
+            assert!(env.map.int_expr_equivalence.insert(mod_expr, r).is_none());
+
+            match expr {
+                IntExpr::Div(_, _) => LinearSum::singleton(q),
+                _ => LinearSum::singleton(r),
+            }
+        }
+    }
+}
+
+/// Introduces the quotient `q` and the remainder `r` of the floor division of `x` by `y`, that is,
+/// `x == q * y + r` where `r` has the same sign as `y` and `|r| < |y|`. `y != 0` is also enforced.
+/// A non-constant `y` requires the multiplication constraint.
+fn normalize_div_mod(env: &mut NormalizerEnv, x: &IntExpr, y: &IntExpr) -> (NIntVar, NIntVar) {
+    let xvar = equivalent_int_var(env, x);
+    let (y, y_low, y_high) = match y {
+        &IntExpr::Const(c) => (IntExpr::Const(c), CheckedInt::new(c), CheckedInt::new(c)),
+        _ => {
+            let yvar = equivalent_int_var(env, y);
+            let repr = env.norm.vars.int_var(yvar);
+            let (low, high) = (repr.lower_bound_checked(), repr.upper_bound_checked());
+            (IntExpr::NVar(yvar), low, high)
+        }
+    };
+
+    // |q| <= |x| since |y| >= 1
+    let x_repr = env.norm.vars.int_var(xvar);
+    let x_abs_max = x_repr
+        .lower_bound_checked()
+        .abs()
+        .max(x_repr.upper_bound_checked().abs());
+    let y_abs_max = y_low.abs().max(y_high.abs());
+    let q = env
+        .norm
+        .new_int_var(Domain::range_from_checked(-x_abs_max, x_abs_max));
+    let r_abs_max = (y_abs_max - CheckedInt::new(1)).max(CheckedInt::new(0));
+    let r = env
+        .norm
+        .new_int_var(Domain::range_from_checked(-r_abs_max, r_abs_max));
+
+    let x = IntExpr::NVar(xvar);
+    let q_expr = IntExpr::NVar(q);
+    let r_expr = IntExpr::NVar(r);
+    let zero = || IntExpr::Const(0);
+    let cond = BoolExpr::And(vec![
+        Box::new(x.eq(q_expr * y.clone() + r_expr.clone())),
+        Box::new(y.clone().ne(zero())),
+        Box::new(
+            y.clone()
+                .gt(zero())
+                .imp(r_expr.clone().ge(zero()) & r_expr.clone().lt(y.clone())),
+        ),
+        Box::new(
+            y.clone()
+                .lt(zero())
+                .imp(r_expr.clone().le(zero()) & r_expr.gt(y)),
+        ),
+    ]);
+    for c in normalize_bool_expr(env, &cond, false) {
+        env.norm.add_constraint(c);
     }
+
+    (q, r)
 }
 
 #[cfg(not(feature = "csp-extra-constraints"))]
@@ -849,6 +945,138 @@ fn normalize_circuit(env: &mut NormalizerEnv, vars: Vec<IntVar>) {
         ));
 }
 
+fn bool_lit_expr(lit: NBoolLit) -> BoolExpr {
+    if lit.negated {
+        !BoolExpr::NVar(lit.var)
+    } else {
+        BoolExpr::NVar(lit.var)
+    }
+}
+
+fn normalize_single_cycle(
+    env: &mut NormalizerEnv,
+    edge_exprs: Vec<BoolExpr>,
+    edges: Vec<(usize, usize)>,
+) {
+    assert_eq!(edge_exprs.len(), edges.len());
+
+    let edge_lits = edge_exprs
+        .into_iter()
+        .map(|e| equivalent_bool_lit(env, e))
+        .collect::<Vec<_>>();
+    let n = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+    let mut adj_edges: Vec<Vec<usize>> = vec![vec![]; n];
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        assert_ne!(u, v);
+        adj_edges[u].push(i);
+        adj_edges[v].push(i);
+    }
+
+    // every vertex has degree 0 or 2
+    for adj in &adj_edges {
+        if adj.is_empty() {
+            continue;
+        }
+        let degree = IntExpr::Linear(
+            adj.iter()
+                .map(|&e| {
+                    (
+                        Box::new(
+                            bool_lit_expr(edge_lits[e]).ite(IntExpr::Const(1), IntExpr::Const(0)),
+                        ),
+                        1,
+                    )
+                })
+                .collect(),
+        );
+        normalize_and_register_expr(
+            env,
+            degree.clone().eq(IntExpr::Const(0)) | degree.eq(IntExpr::Const(2)),
+        );
+    }
+
+    // the cycle is nonempty
+    env.norm.add_constraint(Constraint {
+        bool_lit: edge_lits.clone(),
+        linear_lit: vec![],
+    });
+
+    // the active edges are connected, which also excludes multiple subtours
+    let mut line_graph_edges: Vec<(usize, usize)> = vec![];
+    for adj in &adj_edges {
+        for j in 0..adj.len() {
+            for k in 0..j {
+                line_graph_edges.push((adj[j], adj[k]));
+            }
+        }
+    }
+    env.norm
+        .add_extra_constraint(ExtraConstraint::ActiveVerticesConnected(
+            edge_lits,
+            line_graph_edges,
+        ));
+}
+
+/// Each active edge is oriented towards the "parent" of one of its endpoints so that every
+/// vertex has at most one parent and the parent always has a larger label. This is possible
+/// if and only if the active edges form a forest.
+fn normalize_acyclic(
+    env: &mut NormalizerEnv,
+    edge_exprs: Vec<BoolExpr>,
+    edges: Vec<(usize, usize)>,
+) {
+    assert_eq!(edge_exprs.len(), edges.len());
+
+    let n = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+    if n == 0 {
+        return;
+    }
+    let labels = (0..n)
+        .map(|_| env.norm.new_int_var(Domain::range(0, n as i32 - 1)))
+        .collect::<Vec<_>>();
+    let mut out_lits: Vec<Vec<NBoolLit>> = vec![vec![]; n];
+
+    for (expr, (u, v)) in edge_exprs.into_iter().zip(edges) {
+        let lit = equivalent_bool_lit(env, expr);
+        if u == v {
+            env.norm.add_constraint(Constraint {
+                bool_lit: vec![!lit],
+                linear_lit: vec![],
+            });
+            continue;
+        }
+
+        // `fwd` (resp. `bwd`) means that `v` (resp. `u`) is the parent of `u` (resp. `v`)
+        let fwd = env.norm.new_bool_var();
+        let bwd = env.norm.new_bool_var();
+        normalize_and_register_expr(
+            env,
+            bool_lit_expr(lit).iff(BoolExpr::NVar(fwd) | BoolExpr::NVar(bwd)),
+        );
+        normalize_and_register_expr(
+            env,
+            BoolExpr::NVar(fwd).imp(IntExpr::NVar(labels[u]).lt(IntExpr::NVar(labels[v]))),
+        );
+        normalize_and_register_expr(
+            env,
+            BoolExpr::NVar(bwd).imp(IntExpr::NVar(labels[v]).lt(IntExpr::NVar(labels[u]))),
+        );
+        out_lits[u].push(NBoolLit::new(fwd, false));
+        out_lits[v].push(NBoolLit::new(bwd, false));
+    }
+
+    for lits in &out_lits {
+        for p in 0..lits.len() {
+            for q in (p + 1)..lits.len() {
+                env.norm.add_constraint(Constraint {
+                    bool_lit: vec![!lits[p], !lits[q]],
+                    linear_lit: vec![],
+                });
+            }
+        }
+    }
+}
+
 #[cfg(not(feature = "csp-extra-constraints"))]
 fn normalize_extension_supports(_: &mut NormalizerEnv, _: Vec<IntVar>, _: Vec<Vec<Option<i32>>>) {
     panic!("feature not enabled");
@@ -1197,6 +1425,18 @@ mod tests {
                         }
                     }
                     Stmt::ActiveVerticesConnected(_, _) => todo!(),
+                    Stmt::DirectedReachable(_, _, _) => todo!(),
+                    Stmt::SingleCycle(_, _) => todo!(),
+                    Stmt::Acyclic(_, _) => todo!(),
+                    Stmt::Cardinality(exprs, low, high) => {
+                        let n = exprs
+                            .iter()
+                            .filter(|e| assignment.eval_bool_expr(e))
+                            .count();
+                        if !(*low <= n && n <= *high) {
+                            return false;
+                        }
+                    }
                     Stmt::Circuit(vars) => {
                         let values = vars
                             .iter()
@@ -1285,7 +1525,32 @@ mod tests {
                             return false;
                         }
                     }
+                    ExtraConstraint::DirectedReachable(source, is_active, edges) => {
+                        let is_active = is_active
+                            .iter()
+                            .map(|&v| assignment.get_bool(v.var).unwrap() ^ v.negated)
+                            .collect::<Vec<_>>();
+                        if !test_util::check_graph_directed_reachable(*source, &is_active, edges) {
+                            return false;
+                        }
+                    }
+                    ExtraConstraint::Cardinality(lits, low, high) => {
+                        let n = lits
+                            .iter()
+                            .filter(|l| assignment.get_bool(l.var).unwrap() ^ l.negated)
+                            .count();
+                        if !(*low <= n && n <= *high) {
+                            return false;
+                        }
+                    }
                     ExtraConstraint::GraphDivision(_, _, _) => todo!(),
+                    ExtraConstraint::Implies(trigger, constr) => {
+                        let trigger_value =
+                            assignment.get_bool(trigger.var).unwrap() ^ trigger.negated;
+                        if trigger_value && !assignment.eval_constraint(constr) {
+                            return false;
+                        }
+                    }
                 }
             }
             true
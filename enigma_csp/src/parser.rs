@@ -1,4 +1,5 @@
 extern crate nom;
+use std::cell::RefCell;
 use std::collections::{btree_map, BTreeMap};
 
 use nom::{
@@ -21,42 +22,90 @@ enum SyntaxTree<'a> {
     Node(Vec<SyntaxTree<'a>>),
 }
 
+/// Error found while converting a syntax tree into a statement.
+/// `at` is the slice of the input at which the error is reported.
+struct TreeError<'a> {
+    at: &'a str,
+    message: String,
+}
+
+type TreeResult<'a, T> = Result<T, TreeError<'a>>;
+
+fn tree_error<'a, T>(at: &'a str, message: String) -> TreeResult<'a, T> {
+    Err(TreeError { at, message })
+}
+
 impl<'a> SyntaxTree<'a> {
-    fn as_op_name(&self) -> &'a str {
+    fn as_ident(&self, at: &'a str) -> TreeResult<'a, &'a str> {
         match self {
-            &SyntaxTree::Ident(s) => s,
-            _ => panic!("op name expected"),
+            &SyntaxTree::Ident(s) => Ok(s),
+            _ => tree_error(at, String::from("identifier expected")),
         }
     }
 
-    fn as_ident(&self) -> &'a str {
+    fn as_int(&self, at: &'a str) -> TreeResult<'a, i32> {
         match self {
-            &SyntaxTree::Ident(s) => s,
-            _ => panic!("identifier expected"),
+            SyntaxTree::Int(n) => Ok(*n),
+            SyntaxTree::Ident(s) => tree_error(s, format!("int expected, but {} is given", s)),
+            _ => tree_error(at, String::from("int expected")),
         }
     }
 
-    fn as_int(&self) -> i32 {
-        match self {
-            &SyntaxTree::Int(n) => n,
-            _ => panic!("int expected"),
+    fn as_usize(&self, at: &'a str) -> TreeResult<'a, usize> {
+        let n = self.as_int(at)?;
+        if n < 0 {
+            return tree_error(at, format!("non-negative int expected, but {} is given", n));
         }
+        Ok(n as usize)
     }
 
-    fn as_usize(&self) -> usize {
-        let n = self.as_int();
-        assert!(n >= 0);
-        n as usize
+    fn as_node(&self, at: &'a str) -> TreeResult<'a, &Vec<SyntaxTree<'a>>> {
+        match self {
+            SyntaxTree::Node(ch) => Ok(ch),
+            &SyntaxTree::Ident(s) => tree_error(s, String::from("node expected")),
+            _ => tree_error(at, String::from("node expected")),
+        }
     }
 
-    fn as_node(&self) -> &Vec<SyntaxTree<'a>> {
+    /// Returns the leftmost identifier in this tree, which is used as the position of errors.
+    fn position(&self) -> Option<&'a str> {
         match self {
-            SyntaxTree::Node(ch) => ch,
-            _ => panic!("node expected"),
+            &SyntaxTree::Ident(s) => Some(s),
+            SyntaxTree::Int(_) => None,
+            SyntaxTree::Node(child) => child.iter().find_map(|c| c.position()),
         }
     }
 }
 
+/// Returns the op name of `child` (the children of a node) with its position.
+fn node_op_name<'a>(child: &[SyntaxTree<'a>], at: &'a str) -> TreeResult<'a, (&'a str, &'a str)> {
+    match child.first() {
+        Some(&SyntaxTree::Ident(s)) => Ok((s, s)),
+        Some(c) => tree_error(c.position().unwrap_or(at), String::from("op name expected")),
+        None => tree_error(at, String::from("empty node")),
+    }
+}
+
+fn check_num_args<'a>(
+    op_name: &'a str,
+    child: &[SyntaxTree<'a>],
+    expected: usize,
+) -> TreeResult<'a, ()> {
+    if child.len() != expected + 1 {
+        tree_error(
+            op_name,
+            format!(
+                "{} expects {} argument(s), but {} are given",
+                op_name,
+                expected,
+                child.len() - 1
+            ),
+        )
+    } else {
+        Ok(())
+    }
+}
+
 fn is_ident_char(c: char) -> bool {
     c.is_alphanumeric() || c == '[' || c == ']' || c == '_'
 }
@@ -65,6 +114,7 @@ fn parse_to_tree(input: &str) -> Result<SyntaxTree, nom::error::Error<&str>> {
     fn rec_parser(input: &str) -> IResult<&str, SyntaxTree> {
         let ident_or_op = recognize(pair(alpha1, take_while(is_ident_char)));
         let op = alt((
+            tag("<=>"),
             tag("&&"),
             tag("||"),
             tag("^"),
@@ -80,6 +130,8 @@ fn parse_to_tree(input: &str) -> Result<SyntaxTree, nom::error::Error<&str>> {
             tag("+"),
             tag("-"),
             tag("*"),
+            tag("/"),
+            tag("%"),
         ));
         alt((
             delimited(
@@ -116,255 +168,613 @@ pub enum ParseResult<'a> {
     BoolVarDecl(&'a str),
     IntVarDecl(&'a str, Domain),
     IntVarWithListDomDecl(&'a str, Vec<i32>),
+    /// `(bool y[n])`: declares `y[0]`, ..., `y[n-1]`.
+    BoolArrayDecl(&'a str, usize),
+    /// `(int x[n] low high)`: declares `x[0]`, ..., `x[n-1]`.
+    IntArrayDecl(&'a str, usize, Domain),
+    /// `(int x[n] (v1 v2 ...))`: declares `x[0]`, ..., `x[n-1]`.
+    IntArrayWithListDomDecl(&'a str, usize, Vec<i32>),
     Stmt(Stmt),
+    /// `(minimize e)`: `e` should be minimized.
+    Minimize(IntExpr),
+    /// `(maximize e)`: `e` should be maximized.
+    Maximize(IntExpr),
+    /// The input is malformed. `line` and `col` are 1-based and point to the offending token
+    /// in the input given to `parse`.
+    Error {
+        line: usize,
+        col: usize,
+        message: String,
+    },
 }
 
-pub struct VarMap(BTreeMap<String, Var>);
+pub struct VarMap {
+    vars: BTreeMap<String, Var>,
+    array_sizes: BTreeMap<String, usize>,
+}
 
 impl VarMap {
     pub fn new() -> VarMap {
-        VarMap(BTreeMap::new())
+        VarMap {
+            vars: BTreeMap::new(),
+            array_sizes: BTreeMap::new(),
+        }
     }
 
     pub fn add_bool_var(&mut self, name: &str, var: BoolVar) {
-        assert!(self.0.insert(String::from(name), Var::Bool(var)).is_none());
+        assert!(self
+            .vars
+            .insert(String::from(name), Var::Bool(var))
+            .is_none());
     }
 
     pub fn add_int_var(&mut self, name: &str, var: IntVar) {
-        assert!(self.0.insert(String::from(name), Var::Int(var)).is_none());
+        assert!(self
+            .vars
+            .insert(String::from(name), Var::Int(var))
+            .is_none());
+    }
+
+    /// Registers `vars[i]` as `name[i]`, and `name` as an array of them.
+    pub fn add_bool_array(&mut self, name: &str, vars: &[BoolVar]) {
+        assert!(self
+            .array_sizes
+            .insert(String::from(name), vars.len())
+            .is_none());
+        for (i, &v) in vars.iter().enumerate() {
+            self.add_bool_var(&format!("{}[{}]", name, i), v);
+        }
+    }
+
+    /// Registers `vars[i]` as `name[i]`, and `name` as an array of them.
+    pub fn add_int_array(&mut self, name: &str, vars: &[IntVar]) {
+        assert!(self
+            .array_sizes
+            .insert(String::from(name), vars.len())
+            .is_none());
+        for (i, &v) in vars.iter().enumerate() {
+            self.add_int_var(&format!("{}[{}]", name, i), v);
+        }
     }
 
     pub fn get_var(&self, name: &str) -> Option<Var> {
-        self.0.get(name).copied()
+        self.vars.get(name).copied()
+    }
+
+    pub fn get_array_size(&self, name: &str) -> Option<usize> {
+        self.array_sizes.get(name).copied()
     }
 
     pub fn iter<'a>(&'a self) -> btree_map::Iter<'a, String, Var> {
-        self.0.iter()
+        self.vars.iter()
+    }
+}
+
+/// Variables available in expressions, and the conditions required by the expressions parsed so
+/// far (namely, that variable indices are within the ranges of the arrays), which are added to
+/// the statement containing them.
+struct ExprEnv<'m> {
+    var_map: &'m VarMap,
+    conditions: RefCell<Vec<BoolExpr>>,
+}
+
+enum VarRef {
+    Var(Var),
+    /// `a[i]` for an array `a` (given as the list of its elements) and an int var `i`.
+    Element(Vec<Var>, IntVar),
+}
+
+/// Splits `a[i]` into `a` and `i`.
+fn split_index(id: &str) -> Option<(&str, &str)> {
+    let open = id.find('[')?;
+    if open == 0 || !id.ends_with(']') || id.len() < open + 3 {
+        return None;
+    }
+    let index = &id[(open + 1)..(id.len() - 1)];
+    if index.contains(['[', ']']) {
+        return None;
+    }
+    Some((&id[..open], index))
+}
+
+/// Returns the name and the size of the array if `id` is of the form `a[n]` (used in array
+/// declarations).
+fn array_decl<'b>(id: &'b str) -> TreeResult<'b, Option<(&'b str, usize)>> {
+    let (name, size) = match split_index(id) {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+    match size.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(Some((name, n))),
+        _ => tree_error(id, format!("invalid array size: {}", size)),
+    }
+}
+
+fn lookup_var<'b>(env: &ExprEnv, id: &'b str) -> TreeResult<'b, VarRef> {
+    if let Some(var) = env.var_map.get_var(id) {
+        return Ok(VarRef::Var(var));
+    }
+    let (name, index) = match split_index(id) {
+        Some((name, index)) if env.var_map.get_array_size(name).is_some() => (name, index),
+        _ => return tree_error(id, format!("unknown identifier: {}", id)),
+    };
+    let size = env.var_map.get_array_size(name).unwrap();
+    if let Ok(i) = index.parse::<i64>() {
+        // otherwise `id` would have been found as a variable
+        return tree_error(
+            id,
+            format!("index {} is out of range for {} of size {}", i, name, size),
+        );
     }
+    let index = match env.var_map.get_var(index) {
+        Some(Var::Int(v)) => v,
+        Some(Var::Bool(_)) => {
+            return tree_error(id, format!("bool var {} is used as an index", index))
+        }
+        None => return tree_error(id, format!("unknown identifier: {}", index)),
+    };
+    let elems = (0..size)
+        .map(|i| env.var_map.get_var(&format!("{}[{}]", name, i)).unwrap())
+        .collect::<Vec<_>>();
+    let mut conditions = env.conditions.borrow_mut();
+    conditions.push(index.expr().ge(IntExpr::Const(0)));
+    conditions.push(index.expr().lt(IntExpr::Const(size as i32)));
+    Ok(VarRef::Element(elems, index))
+}
+
+fn error_result<'b>(input: &'b str, at: &'b str, message: String) -> ParseResult<'b> {
+    let offset = (at.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+    let offset = offset.min(input.len());
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let col = match before.rfind('\n') {
+        Some(p) => before[(p + 1)..].chars().count() + 1,
+        None => before.chars().count() + 1,
+    };
+    ParseResult::Error { line, col, message }
 }
 
 pub fn parse<'a, 'b>(var_map: &'a VarMap, input: &'b str) -> ParseResult<'b> {
-    // TODO: return error info
-    let tree = parse_to_tree(input).unwrap();
-    let child = match &tree {
+    let tree = match parse_to_tree(input) {
+        Ok(tree) => tree,
+        Err(err) => {
+            // nom reports the start of the failing alternative, which is often the whole input.
+            // Unbalanced parentheses are the most common mistake, so point them out precisely.
+            let mut open_parens = vec![];
+            for (i, c) in input.char_indices() {
+                if c == '(' {
+                    open_parens.push(i);
+                } else if c == ')' && open_parens.pop().is_none() {
+                    return error_result(input, &input[i..], String::from("unbalanced `)`"));
+                }
+            }
+            if let Some(&i) = open_parens.last() {
+                return error_result(input, &input[i..], String::from("unclosed `(`"));
+            }
+            let message = if err.input.is_empty() {
+                String::from("unexpected end of input")
+            } else {
+                format!("syntax error at `{}`", err.input)
+            };
+            return error_result(input, err.input, message);
+        }
+    };
+    match parse_tree(var_map, &tree, input) {
+        Ok(res) => res,
+        Err(err) => error_result(input, err.at, err.message),
+    }
+}
+
+fn parse_tree<'b>(
+    var_map: &VarMap,
+    tree: &SyntaxTree<'b>,
+    input: &'b str,
+) -> TreeResult<'b, ParseResult<'b>> {
+    let env = ExprEnv {
+        var_map,
+        conditions: RefCell::new(vec![]),
+    };
+    let res = parse_tree_with_env(&env, tree, input)?;
+
+    let conditions = env.conditions.into_inner();
+    if conditions.is_empty() {
+        return Ok(res);
+    }
+    match res {
+        ParseResult::Stmt(Stmt::Expr(e)) => Ok(ParseResult::Stmt(Stmt::Expr(BoolExpr::And(
+            std::iter::once(e).chain(conditions).map(Box::new).collect(),
+        )))),
+        _ => tree_error(
+            tree.position().unwrap_or(input),
+            String::from("indexing with a variable is supported only in expressions"),
+        ),
+    }
+}
+
+fn parse_tree_with_env<'b>(
+    env: &ExprEnv,
+    tree: &SyntaxTree<'b>,
+    input: &'b str,
+) -> TreeResult<'b, ParseResult<'b>> {
+    let var_map = env.var_map;
+    let child = match tree {
         SyntaxTree::Node(child) => child,
-        _ => return ParseResult::Stmt(Stmt::Expr(parse_bool_expr(var_map, &tree))),
+        _ => {
+            return Ok(ParseResult::Stmt(Stmt::Expr(parse_bool_expr(
+                env, tree, input,
+            )?)))
+        }
     };
-    assert!(child.len() >= 1);
-    let op_name = child[0].as_op_name();
+    let (op_name, at) = node_op_name(child, input)?;
 
-    if op_name == "bool" {
-        assert_eq!(child.len(), 2);
-        let var_name = child[1].as_ident();
-        ParseResult::BoolVarDecl(var_name)
+    let res = if op_name == "bool" {
+        check_num_args(op_name, child, 1)?;
+        let var_name = child[1].as_ident(at)?;
+        match array_decl(var_name)? {
+            Some((name, size)) => ParseResult::BoolArrayDecl(name, size),
+            None => ParseResult::BoolVarDecl(var_name),
+        }
     } else if op_name == "int" {
         if child.len() == 4 {
-            let var_name = child[1].as_ident();
-            let low = child[2].as_int();
-            let high = child[3].as_int();
-            ParseResult::IntVarDecl(var_name, Domain::range(low, high))
+            let var_name = child[1].as_ident(at)?;
+            let low = child[2].as_int(at)?;
+            let high = child[3].as_int(at)?;
+            let domain = Domain::range(low, high);
+            match array_decl(var_name)? {
+                Some((name, size)) => ParseResult::IntArrayDecl(name, size, domain),
+                None => ParseResult::IntVarDecl(var_name, domain),
+            }
         } else if child.len() == 3 {
-            let var_name = child[1].as_ident();
-            match &child[2] {
-                SyntaxTree::Node(child) => {
-                    let domain = child.iter().map(|x| x.as_int()).collect::<Vec<_>>();
-                    ParseResult::IntVarWithListDomDecl(var_name, domain)
-                }
-                _ => panic!(),
+            let var_name = child[1].as_ident(at)?;
+            let domain = child[2]
+                .as_node(at)?
+                .iter()
+                .map(|x| x.as_int(at))
+                .collect::<Result<Vec<_>, _>>()?;
+            match array_decl(var_name)? {
+                Some((name, size)) => ParseResult::IntArrayWithListDomDecl(name, size, domain),
+                None => ParseResult::IntVarWithListDomDecl(var_name, domain),
             }
         } else {
-            panic!();
+            return tree_error(at, String::from("int expects 2 or 3 arguments"));
+        }
+    } else if op_name == "minimize" || op_name == "maximize" {
+        check_num_args(op_name, child, 1)?;
+        let expr = parse_int_expr(env, &child[1], at)?;
+        if op_name == "minimize" {
+            ParseResult::Minimize(expr)
+        } else {
+            ParseResult::Maximize(expr)
         }
     } else if op_name == "alldifferent" {
         let exprs = child[1..]
             .iter()
-            .map(|c| parse_int_expr(var_map, c))
-            .collect::<Vec<_>>();
+            .map(|c| {
+                if let &SyntaxTree::Ident(id) = c {
+                    match var_map.get_var(id) {
+                        Some(Var::Int(v)) => Ok(v.expr()),
+                        Some(Var::Bool(_)) => tree_error(
+                            id,
+                            format!("alldifferent expects int vars, but {} is a bool var", id),
+                        ),
+                        None if split_index(id)
+                            .and_then(|(name, _)| var_map.get_array_size(name))
+                            .is_some() =>
+                        {
+                            parse_int_expr(env, c, at)
+                        }
+                        None => tree_error(
+                            id,
+                            format!("alldifferent refers to an undeclared var: {}", id),
+                        ),
+                    }
+                } else {
+                    parse_int_expr(env, c, at)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         ParseResult::Stmt(Stmt::AllDifferent(exprs))
     } else if op_name == "circuit" {
         let mut vars = vec![];
         for c in &child[1..] {
-            match var_map.get_var(c.as_ident()) {
+            let id = c.as_ident(at)?;
+            match var_map.get_var(id) {
                 Some(Var::Int(v)) => vars.push(v),
-                _ => panic!(),
+                _ => return tree_error(id, format!("int var expected, but {} is given", id)),
             }
         }
         ParseResult::Stmt(Stmt::Circuit(vars))
     } else if op_name == "graph-active-vertices-connected" {
-        let num_vertices = child[1].as_usize();
-        let num_edges = child[2].as_usize();
-        let num_vertices = num_vertices as usize;
-        let num_edges = num_edges as usize;
-        assert_eq!(child.len(), 3 + num_vertices + num_edges * 2);
+        if child.len() < 3 {
+            return tree_error(at, format!("{} expects at least 2 arguments", op_name));
+        }
+        let num_vertices = child[1].as_usize(at)?;
+        let num_edges = child[2].as_usize(at)?;
+        check_num_args(op_name, child, 2 + num_vertices + num_edges * 2)?;
 
         let vertices = (0..num_vertices)
-            .map(|i| parse_bool_expr(var_map, &child[i + 3]))
-            .collect::<Vec<_>>();
+            .map(|i| parse_bool_expr(env, &child[i + 3], at))
+            .collect::<Result<Vec<_>, _>>()?;
         let edges = (0..num_edges)
             .map(|i| {
-                (
-                    child[i * 2 + 3 + num_vertices].as_usize(),
-                    child[i * 2 + 4 + num_vertices].as_usize(),
-                )
+                Ok((
+                    child[i * 2 + 3 + num_vertices].as_usize(at)?,
+                    child[i * 2 + 4 + num_vertices].as_usize(at)?,
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
         ParseResult::Stmt(Stmt::ActiveVerticesConnected(vertices, edges))
     } else if op_name == "graph-division" {
-        let num_vertices = child[1].as_usize();
-        let num_edges = child[2].as_usize();
-        assert_eq!(child.len(), 3 + num_vertices + num_edges * 3);
+        if child.len() < 3 {
+            return tree_error(at, format!("{} expects at least 2 arguments", op_name));
+        }
+        let num_vertices = child[1].as_usize(at)?;
+        let num_edges = child[2].as_usize(at)?;
+        check_num_args(op_name, child, 2 + num_vertices + num_edges * 3)?;
 
         let vertices = (0..num_vertices)
             .map(|i| {
                 if child[i + 3] == SyntaxTree::Ident("*") {
-                    None
+                    Ok(None)
                 } else {
-                    Some(parse_int_expr(var_map, &child[i + 3]))
+                    Ok(Some(parse_int_expr(env, &child[i + 3], at)?))
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
         let edges = (0..num_edges)
             .map(|i| {
-                (
-                    child[i * 2 + 3 + num_vertices].as_usize(),
-                    child[i * 2 + 4 + num_vertices].as_usize(),
-                )
+                Ok((
+                    child[i * 2 + 3 + num_vertices].as_usize(at)?,
+                    child[i * 2 + 4 + num_vertices].as_usize(at)?,
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
         let edge_exprs = (0..num_edges)
-            .map(|i| parse_bool_expr(var_map, &child[i + 3 + num_vertices + num_edges * 2]))
-            .collect::<Vec<_>>();
+            .map(|i| parse_bool_expr(env, &child[i + 3 + num_vertices + num_edges * 2], at))
+            .collect::<Result<Vec<_>, _>>()?;
         ParseResult::Stmt(Stmt::GraphDivision(vertices, edges, edge_exprs))
     } else if op_name == "extension-supports" {
-        assert_eq!(child.len(), 3);
+        check_num_args(op_name, child, 2)?;
         let mut vars = vec![];
-        for c in child[1].as_node() {
-            match var_map.get_var(c.as_ident()) {
+        for c in child[1].as_node(at)? {
+            let id = c.as_ident(at)?;
+            match var_map.get_var(id) {
                 Some(Var::Int(v)) => vars.push(v),
-                _ => panic!(),
+                _ => return tree_error(id, format!("int var expected, but {} is given", id)),
             }
         }
         let mut supports = vec![];
-        for s in child[2].as_node() {
+        for s in child[2].as_node(at)? {
             let mut support = vec![];
-            for v in s.as_node() {
+            for v in s.as_node(at)? {
                 match v {
                     SyntaxTree::Int(v) => support.push(Some(*v)),
                     SyntaxTree::Ident("*") => support.push(None),
-                    _ => panic!(),
+                    _ => return tree_error(at, String::from("int or * expected in supports")),
                 }
             }
             supports.push(support);
         }
         ParseResult::Stmt(Stmt::ExtensionSupports(vars, supports))
+    } else if let Some(stmt) = parse_cardinality(env, op_name, child, at)? {
+        ParseResult::Stmt(stmt)
     } else {
-        ParseResult::Stmt(Stmt::Expr(parse_bool_expr(var_map, &tree)))
-    }
+        ParseResult::Stmt(Stmt::Expr(parse_bool_expr(env, tree, input)?))
+    };
+    Ok(res)
 }
 
-fn parse_bool_expr(var_map: &VarMap, tree: &SyntaxTree) -> BoolExpr {
+/// Returns the arguments of `tree` if it is `(count e1 e2 ...)`.
+fn count_args<'a, 'b>(tree: &'a SyntaxTree<'b>) -> Option<&'a [SyntaxTree<'b>]> {
     match tree {
+        SyntaxTree::Node(child) if child.first() == Some(&SyntaxTree::Ident("count")) => {
+            Some(&child[1..])
+        }
+        _ => None,
+    }
+}
+
+/// Parses a comparison between `(count e1 e2 ...)` and a constant as `Stmt::Cardinality`, which
+/// is encoded with a sequential counter rather than as a generic linear constraint.
+/// Returns `None` if the statement is not of this form.
+fn parse_cardinality<'b>(
+    env: &ExprEnv,
+    op_name: &str,
+    child: &[SyntaxTree<'b>],
+    at: &'b str,
+) -> TreeResult<'b, Option<Stmt>> {
+    if child.len() != 3 {
+        return Ok(None);
+    }
+    let (args, k, flipped) = match (count_args(&child[1]), count_args(&child[2])) {
+        (Some(args), None) => match child[2] {
+            SyntaxTree::Int(k) => (args, k as i64, false),
+            _ => return Ok(None),
+        },
+        (None, Some(args)) => match child[1] {
+            SyntaxTree::Int(k) => (args, k as i64, true),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let (low, high) = match (op_name, flipped) {
+        ("=" | "==" | "eq", _) => (k, k),
+        ("<=" | "le", false) | (">=" | "ge", true) => (i64::MIN, k),
+        ("<" | "lt", false) | (">" | "gt", true) => (i64::MIN, k - 1),
+        (">=" | "ge", false) | ("<=" | "le", true) => (k, i64::MAX),
+        (">" | "gt", false) | ("<" | "lt", true) => (k + 1, i64::MAX),
+        _ => return Ok(None),
+    };
+    let exprs = args
+        .iter()
+        .map(|t| parse_bool_expr(env, t, at))
+        .collect::<Result<Vec<_>, _>>()?;
+    let low = low.max(0);
+    let high = high.min(exprs.len() as i64);
+    if low > high {
+        return Ok(Some(Stmt::Expr(BoolExpr::Const(false))));
+    }
+    Ok(Some(Stmt::Cardinality(exprs, low as usize, high as usize)))
+}
+
+/// `at` is the position used for errors on `tree` when it has no identifier of its own.
+fn parse_bool_expr<'b>(
+    env: &ExprEnv,
+    tree: &SyntaxTree<'b>,
+    at: &'b str,
+) -> TreeResult<'b, BoolExpr> {
+    let expr = match tree {
         &SyntaxTree::Ident(id) => {
             if id == "true" {
-                return BoolExpr::Const(true);
+                return Ok(BoolExpr::Const(true));
             } else if id == "false" {
-                return BoolExpr::Const(false);
+                return Ok(BoolExpr::Const(false));
             }
 
-            let var = var_map.get_var(id).unwrap();
-            match var {
-                Var::Bool(b) => b.expr(),
-                Var::Int(_) => panic!("int var is given while bool expr is expected"),
+            match lookup_var(env, id)? {
+                VarRef::Var(Var::Bool(b)) => b.expr(),
+                VarRef::Element(elems, index) if matches!(elems[0], Var::Bool(_)) => BoolExpr::Or(
+                    elems
+                        .iter()
+                        .enumerate()
+                        .map(|(k, e)| match *e {
+                            Var::Bool(e) => {
+                                Box::new(index.expr().eq(IntExpr::Const(k as i32)) & e.expr())
+                            }
+                            Var::Int(_) => unreachable!(),
+                        })
+                        .collect(),
+                ),
+                _ => {
+                    return tree_error(
+                        id,
+                        format!("int var {} is given while bool expr is expected", id),
+                    )
+                }
             }
         }
-        &SyntaxTree::Int(_) => panic!("int constant is given while bool expr is expected"),
+        &SyntaxTree::Int(_) => {
+            return tree_error(
+                at,
+                String::from("int constant is given while bool expr is expected"),
+            )
+        }
         SyntaxTree::Node(child) => {
-            let op_name = child[0].as_op_name();
+            let (op_name, at) = node_op_name(child, at)?;
             if op_name == "not" || op_name == "!" {
-                assert_eq!(child.len(), 2);
-                !parse_bool_expr(var_map, &child[1])
+                check_num_args(op_name, child, 1)?;
+                !parse_bool_expr(env, &child[1], at)?
             } else if op_name == "and" || op_name == "&&" {
                 BoolExpr::And(
                     child[1..]
                         .iter()
-                        .map(|t| Box::new(parse_bool_expr(var_map, t)))
-                        .collect(),
+                        .map(|t| Ok(Box::new(parse_bool_expr(env, t, at)?)))
+                        .collect::<Result<Vec<_>, _>>()?,
                 )
             } else if op_name == "or" || op_name == "||" {
                 BoolExpr::Or(
                     child[1..]
                         .iter()
-                        .map(|t| Box::new(parse_bool_expr(var_map, t)))
-                        .collect(),
+                        .map(|t| Ok(Box::new(parse_bool_expr(env, t, at)?)))
+                        .collect::<Result<Vec<_>, _>>()?,
                 )
             } else if op_name == "xor" || op_name == "^" {
-                assert_eq!(child.len(), 3);
-                parse_bool_expr(var_map, &child[1]) ^ parse_bool_expr(var_map, &child[2])
-            } else if op_name == "iff" {
-                assert_eq!(child.len(), 3);
-                parse_bool_expr(var_map, &child[1]).iff(parse_bool_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_bool_expr(env, &child[1], at)? ^ parse_bool_expr(env, &child[2], at)?
+            } else if op_name == "iff" || op_name == "<=>" {
+                check_num_args(op_name, child, 2)?;
+                parse_bool_expr(env, &child[1], at)?.iff(parse_bool_expr(env, &child[2], at)?)
             } else if op_name == "imp" || op_name == "=>" {
-                assert_eq!(child.len(), 3);
-                parse_bool_expr(var_map, &child[1]).imp(parse_bool_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_bool_expr(env, &child[1], at)?.imp(parse_bool_expr(env, &child[2], at)?)
             } else if op_name == "=" || op_name == "==" || op_name == "eq" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).eq(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.eq(parse_int_expr(env, &child[2], at)?)
             } else if op_name == "!=" || op_name == "ne" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).ne(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.ne(parse_int_expr(env, &child[2], at)?)
             } else if op_name == "<=" || op_name == "le" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).le(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.le(parse_int_expr(env, &child[2], at)?)
             } else if op_name == "<" || op_name == "lt" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).lt(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.lt(parse_int_expr(env, &child[2], at)?)
             } else if op_name == ">=" || op_name == "ge" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).ge(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.ge(parse_int_expr(env, &child[2], at)?)
             } else if op_name == ">" || op_name == "gt" {
-                assert_eq!(child.len(), 3);
-                parse_int_expr(var_map, &child[1]).gt(parse_int_expr(var_map, &child[2]))
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)?.gt(parse_int_expr(env, &child[2], at)?)
             } else {
-                panic!("unknown operator: {}", op_name);
+                return tree_error(op_name, format!("unknown operator: {}", op_name));
             }
         }
-    }
+    };
+    Ok(expr)
 }
 
-fn parse_int_expr(var_map: &VarMap, tree: &SyntaxTree) -> IntExpr {
-    match tree {
+/// `at` is the position used for errors on `tree` when it has no identifier of its own.
+fn parse_int_expr<'b>(
+    env: &ExprEnv,
+    tree: &SyntaxTree<'b>,
+    at: &'b str,
+) -> TreeResult<'b, IntExpr> {
+    let expr = match tree {
         &SyntaxTree::Ident(id) => {
             if id == "true" || id == "false" {
-                panic!("bool constant is given while int expr is expected");
+                return tree_error(
+                    id,
+                    String::from("bool constant is given while int expr is expected"),
+                );
             }
 
-            let var = var_map.get_var(id).unwrap();
-            match var {
-                Var::Bool(_) => panic!("int var is given while bool expr is expected"),
-                Var::Int(i) => i.expr(),
+            match lookup_var(env, id)? {
+                VarRef::Var(Var::Int(i)) => i.expr(),
+                VarRef::Element(elems, index) if matches!(elems[0], Var::Int(_)) => {
+                    let as_int = |e: &Var| match *e {
+                        Var::Int(e) => e.expr(),
+                        Var::Bool(_) => unreachable!(),
+                    };
+                    // the last element is chosen if the index is none of the others, which is
+                    // correct since the index is constrained to be within the range
+                    let mut ret = as_int(&elems[elems.len() - 1]);
+                    for k in (0..(elems.len() - 1)).rev() {
+                        ret = index
+                            .expr()
+                            .eq(IntExpr::Const(k as i32))
+                            .ite(as_int(&elems[k]), ret);
+                    }
+                    ret
+                }
+                _ => {
+                    return tree_error(
+                        id,
+                        format!("bool var {} is given while int expr is expected", id),
+                    )
+                }
             }
         }
         &SyntaxTree::Int(n) => IntExpr::Const(n),
         SyntaxTree::Node(child) => {
-            let op_name = child[0].as_op_name();
+            let (op_name, at) = node_op_name(child, at)?;
             if op_name == "+" || op_name == "add" {
                 IntExpr::Linear(
                     child[1..]
                         .iter()
-                        .map(|t| (Box::new(parse_int_expr(var_map, t)), 1))
-                        .collect(),
+                        .map(|t| Ok((Box::new(parse_int_expr(env, t, at)?), 1)))
+                        .collect::<Result<Vec<_>, _>>()?,
                 )
             } else if op_name == "-" || op_name == "sub" {
-                assert!(child.len() == 2 || child.len() == 3);
                 if child.len() == 2 {
-                    IntExpr::Linear(vec![(Box::new(parse_int_expr(var_map, &child[1])), -1)])
+                    IntExpr::Linear(vec![(Box::new(parse_int_expr(env, &child[1], at)?), -1)])
                 } else {
-                    parse_int_expr(var_map, &child[1]) - parse_int_expr(var_map, &child[2])
+                    check_num_args(op_name, child, 2)?;
+                    parse_int_expr(env, &child[1], at)? - parse_int_expr(env, &child[2], at)?
                 }
             } else if op_name == "*" || op_name == "mul" {
-                assert!(child.len() == 3);
-                let lhs = parse_int_expr(var_map, &child[1]);
-                let rhs = parse_int_expr(var_map, &child[2]);
+                check_num_args(op_name, child, 2)?;
+                let lhs = parse_int_expr(env, &child[1], at)?;
+                let rhs = parse_int_expr(env, &child[2], at)?;
                 if let IntExpr::Const(c) = lhs {
                     IntExpr::Linear(vec![(Box::new(rhs), c)])
                 } else if let IntExpr::Const(c) = rhs {
@@ -373,19 +783,75 @@ fn parse_int_expr(var_map: &VarMap, tree: &SyntaxTree) -> IntExpr {
                     IntExpr::Mul(Box::new(lhs), Box::new(rhs))
                 }
             } else if op_name == "if" {
-                assert_eq!(child.len(), 4);
-                parse_bool_expr(var_map, &child[1]).ite(
-                    parse_int_expr(var_map, &child[2]),
-                    parse_int_expr(var_map, &child[3]),
+                check_num_args(op_name, child, 3)?;
+                parse_bool_expr(env, &child[1], at)?.ite(
+                    parse_int_expr(env, &child[2], at)?,
+                    parse_int_expr(env, &child[3], at)?,
                 )
             } else if op_name == "abs" {
-                assert_eq!(child.len(), 2);
-                parse_int_expr(var_map, &child[1]).abs()
+                check_num_args(op_name, child, 1)?;
+                parse_int_expr(env, &child[1], at)?.abs()
+            } else if op_name == "div" || op_name == "/" {
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)? / parse_int_expr(env, &child[2], at)?
+            } else if op_name == "mod" || op_name == "%" {
+                check_num_args(op_name, child, 2)?;
+                parse_int_expr(env, &child[1], at)? % parse_int_expr(env, &child[2], at)?
+            } else if op_name == "count" {
+                let terms = child[1..]
+                    .iter()
+                    .map(|t| {
+                        Ok((
+                            Box::new(
+                                parse_bool_expr(env, t, at)?
+                                    .ite(IntExpr::Const(1), IntExpr::Const(0)),
+                            ),
+                            1,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                IntExpr::Linear(terms)
+            } else if op_name == "min" || op_name == "max" {
+                if child.len() < 2 {
+                    return tree_error(op_name, format!("{} expects at least 1 argument", op_name));
+                }
+                let args = child[1..]
+                    .iter()
+                    .map(|t| parse_int_expr(env, t, at))
+                    .collect::<Result<Vec<_>, _>>()?;
+                min_max_expr(args, op_name == "max")
             } else {
-                panic!("unknown operator: {}", op_name);
+                return tree_error(op_name, format!("unknown operator: {}", op_name));
             }
         }
+    };
+    Ok(expr)
+}
+
+/// Returns the minimum (or maximum if `is_max` is true) of `args` as nested `if` expressions.
+/// `args[i]` is chosen if it is not larger (smaller) than any of `args[i+1..]`, which is enough
+/// since the previous arguments are not chosen only if one of the later ones is strictly smaller
+/// (larger). This uses O(n^2) copies of the arguments, unlike a fold of binary min (max), which
+/// uses O(2^n) copies.
+fn min_max_expr(args: Vec<IntExpr>, is_max: bool) -> IntExpr {
+    let n = args.len();
+    let mut ret = args[n - 1].clone();
+    for i in (0..(n - 1)).rev() {
+        let cond = BoolExpr::And(
+            args[(i + 1)..]
+                .iter()
+                .map(|other| {
+                    Box::new(if is_max {
+                        args[i].clone().ge(other.clone())
+                    } else {
+                        args[i].clone().le(other.clone())
+                    })
+                })
+                .collect(),
+        );
+        ret = cond.ite(args[i].clone(), ret);
     }
+    ret
 }
 
 #[cfg(test)]
@@ -443,4 +909,562 @@ mod tests {
         let result = parse(&var_map, "foo");
         assert_eq!(result, ParseResult::Stmt(Stmt::Expr(foo.expr())));
     }
+
+    #[test]
+    fn test_parser_alldifferent() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("y", y);
+        let z = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("z", z);
+        let b = solver.new_bool_var();
+        var_map.add_bool_var("b", b);
+
+        assert_eq!(
+            parse(&var_map, "(alldifferent x y z)"),
+            ParseResult::Stmt(Stmt::AllDifferent(vec![x.expr(), y.expr(), z.expr()]))
+        );
+        assert_eq!(
+            parse(&var_map, "(alldifferent x (+ y 1) 2)"),
+            ParseResult::Stmt(Stmt::AllDifferent(vec![
+                x.expr(),
+                IntExpr::Linear(vec![
+                    (Box::new(y.expr()), 1),
+                    (Box::new(IntExpr::Const(1)), 1)
+                ]),
+                IntExpr::Const(2)
+            ]))
+        );
+
+        assert_eq!(
+            parse(&var_map, "(alldifferent x b)"),
+            ParseResult::Error {
+                line: 1,
+                col: 17,
+                message: String::from("alldifferent expects int vars, but b is a bool var"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(alldifferent x w)"),
+            ParseResult::Error {
+                line: 1,
+                col: 17,
+                message: String::from("alldifferent refers to an undeclared var: w"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parser_optimization() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("y", y);
+
+        assert_eq!(
+            parse(&var_map, "(minimize x)"),
+            ParseResult::Minimize(x.expr())
+        );
+        assert_eq!(
+            parse(&var_map, "(maximize (- x y))"),
+            ParseResult::Maximize(x.expr() - y.expr())
+        );
+        assert_eq!(
+            parse(&var_map, "(minimize x y)"),
+            ParseResult::Error {
+                line: 1,
+                col: 2,
+                message: String::from("minimize expects 1 argument(s), but 2 are given"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parser_reification() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("y", y);
+        let b = solver.new_bool_var();
+        var_map.add_bool_var("b", b);
+        let c = solver.new_bool_var();
+        var_map.add_bool_var("c", c);
+        let d = solver.new_bool_var();
+        var_map.add_bool_var("d", d);
+
+        assert_eq!(
+            parse(&var_map, "(<=> b (== x y))"),
+            ParseResult::Stmt(Stmt::Expr(b.expr().iff(x.expr().eq(y.expr()))))
+        );
+        assert_eq!(
+            parse(&var_map, "(<=> c (<= (+ x y) 2))"),
+            ParseResult::Stmt(Stmt::Expr(
+                c.expr().iff(
+                    IntExpr::Linear(vec![(Box::new(x.expr()), 1), (Box::new(y.expr()), 1)])
+                        .le(IntExpr::Const(2))
+                )
+            ))
+        );
+
+        for line in [
+            "(<=> b (== x y))",
+            "(<=> c (<= (+ x y) 2))",
+            "(<=> d (>= x 2))",
+            // the reified variables can be used in other constraints
+            "(== (+ (if b 1 0) (if c 1 0) (if d 1 0)) 2)",
+        ] {
+            match parse(&var_map, line) {
+                ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+                _ => panic!(),
+            }
+        }
+        let mut answers = vec![];
+        for ans in solver.answer_iter(&[b, c, d], &[x, y]) {
+            let (xv, yv) = (ans.get_int(x).unwrap(), ans.get_int(y).unwrap());
+            let (bv, cv, dv) = (
+                ans.get_bool(b).unwrap(),
+                ans.get_bool(c).unwrap(),
+                ans.get_bool(d).unwrap(),
+            );
+            assert_eq!(bv, xv == yv);
+            assert_eq!(cv, xv + yv <= 2);
+            assert_eq!(dv, xv >= 2);
+            answers.push((xv, yv));
+        }
+        answers.sort();
+        assert_eq!(answers, vec![(0, 0), (1, 1), (2, 0), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn test_parser_arrays() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+
+        assert_eq!(
+            parse(&var_map, "(int x[3] 1 3)"),
+            ParseResult::IntArrayDecl("x", 3, Domain::range(1, 3))
+        );
+        assert_eq!(
+            parse(&var_map, "(int x[3] (1 3 5))"),
+            ParseResult::IntArrayWithListDomDecl("x", 3, vec![1, 3, 5])
+        );
+        assert_eq!(
+            parse(&var_map, "(bool y[2])"),
+            ParseResult::BoolArrayDecl("y", 2)
+        );
+        assert_eq!(
+            parse(&var_map, "(int x[0] 1 3)"),
+            ParseResult::Error {
+                line: 1,
+                col: 6,
+                message: String::from("invalid array size: 0"),
+            }
+        );
+
+        let x = (0..3)
+            .map(|_| solver.new_int_var(Domain::range(1, 3)))
+            .collect::<Vec<_>>();
+        var_map.add_int_array("x", &x);
+        let y = (0..2).map(|_| solver.new_bool_var()).collect::<Vec<_>>();
+        var_map.add_bool_array("y", &y);
+        let i = solver.new_int_var(Domain::range(-1, 3));
+        var_map.add_int_var("i", i);
+
+        assert_eq!(
+            parse(&var_map, "(== x[1] 2)"),
+            ParseResult::Stmt(Stmt::Expr(x[1].expr().eq(IntExpr::Const(2))))
+        );
+        assert_eq!(
+            parse(&var_map, "(== x[3] 2)"),
+            ParseResult::Error {
+                line: 1,
+                col: 5,
+                message: String::from("index 3 is out of range for x of size 3"),
+            }
+        );
+        for line in ["(alldifferent x[0] x[1] x[2])", "(== x[i] 3)", "y[i]"] {
+            match parse(&var_map, line) {
+                ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+                _ => panic!(),
+            }
+        }
+        let mut answers = vec![];
+        for ans in solver.answer_iter(&[], &[x[0], x[1], x[2], i]) {
+            let xv = x
+                .iter()
+                .map(|&v| ans.get_int(v).unwrap())
+                .collect::<Vec<_>>();
+            let iv = ans.get_int(i).unwrap();
+            assert!((0..2).contains(&iv));
+            assert_eq!(xv[iv as usize], 3);
+            answers.push((xv, iv));
+        }
+        // i is 0 or 1 because of `y[i]`, and x[i] is 3
+        assert_eq!(answers.len(), 4);
+    }
+
+    #[test]
+    fn test_parser_if() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("y", y);
+        let b = solver.new_bool_var();
+        var_map.add_bool_var("b", b);
+
+        assert_eq!(
+            parse(&var_map, "(== (+ (if b x y) 1) 3)"),
+            ParseResult::Stmt(Stmt::Expr(
+                IntExpr::Linear(vec![
+                    (Box::new(b.expr().ite(x.expr(), y.expr())), 1),
+                    (Box::new(IntExpr::Const(1)), 1)
+                ])
+                .eq(IntExpr::Const(3))
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(== (if b x) 1)"),
+            ParseResult::Error {
+                line: 1,
+                col: 6,
+                message: String::from("if expects 3 argument(s), but 2 are given"),
+            }
+        );
+
+        match parse(&var_map, "(== (+ (if b x y) 1) 3)") {
+            ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+            _ => panic!(),
+        }
+        let mut answers = vec![];
+        for ans in solver.answer_iter(&[b], &[x, y]) {
+            answers.push((
+                ans.get_bool(b).unwrap(),
+                ans.get_int(x).unwrap(),
+                ans.get_int(y).unwrap(),
+            ));
+        }
+        answers.sort();
+        // x == 2 if b holds, and y == 2 otherwise
+        let mut expected = vec![];
+        for v in 0..=3 {
+            expected.push((false, v, 2));
+            expected.push((true, 2, v));
+        }
+        expected.sort();
+        assert_eq!(answers, expected);
+    }
+
+    #[test]
+    fn test_parser_count() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let bs = (0..4).map(|_| solver.new_bool_var()).collect::<Vec<_>>();
+        var_map.add_bool_array("b", &bs);
+
+        let count = |exprs: Vec<BoolExpr>| {
+            IntExpr::Linear(
+                exprs
+                    .into_iter()
+                    .map(|e| (Box::new(e.ite(IntExpr::Const(1), IntExpr::Const(0))), 1))
+                    .collect(),
+            )
+        };
+        assert_eq!(
+            parse(&var_map, "(== (count b[0] b[1] (>= x 2)) 2)"),
+            ParseResult::Stmt(Stmt::Cardinality(
+                vec![bs[0].expr(), bs[1].expr(), x.expr().ge(IntExpr::Const(2))],
+                2,
+                2
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(< 1 (count b[0] b[1] b[2]))"),
+            ParseResult::Stmt(Stmt::Cardinality(
+                vec![bs[0].expr(), bs[1].expr(), bs[2].expr()],
+                2,
+                3
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(<= (count b[0] b[1]) -1)"),
+            ParseResult::Stmt(Stmt::Expr(BoolExpr::Const(false)))
+        );
+        // comparisons with non-constants are parsed as linear constraints
+        assert_eq!(
+            parse(&var_map, "(== (count b[0] b[1]) x)"),
+            ParseResult::Stmt(Stmt::Expr(
+                count(vec![bs[0].expr(), bs[1].expr()]).eq(x.expr())
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(== (count b[0] x) 1)"),
+            ParseResult::Error {
+                line: 1,
+                col: 17,
+                message: String::from("int var x is given while bool expr is expected"),
+            }
+        );
+
+        for line in [
+            "(== (count b[0] b[1] b[2] b[3]) 3)",
+            "(== x (count b[0] b[1]))",
+            "(>= (count (== x 2) b[3]) 2)",
+        ] {
+            match parse(&var_map, line) {
+                ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+                _ => panic!(),
+            }
+        }
+        let mut answers = vec![];
+        for ans in solver.answer_iter(&bs, &[x]) {
+            let bv = bs
+                .iter()
+                .map(|&b| ans.get_bool(b).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(bv.iter().filter(|&&b| b).count(), 3);
+            answers.push((bv, ans.get_int(x).unwrap()));
+        }
+        assert_eq!(answers, vec![(vec![true, true, false, true], 2)]);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(0, 3));
+        var_map.add_int_var("x", x);
+        let b = solver.new_bool_var();
+        var_map.add_bool_var("b", b);
+
+        assert_eq!(
+            parse(&var_map, "(&& b (== x unknown))"),
+            ParseResult::Error {
+                line: 1,
+                col: 13,
+                message: String::from("unknown identifier: unknown"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(frobnicate x 1)"),
+            ParseResult::Error {
+                line: 1,
+                col: 2,
+                message: String::from("unknown operator: frobnicate"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(== x 1 2)"),
+            ParseResult::Error {
+                line: 1,
+                col: 2,
+                message: String::from("== expects 2 argument(s), but 3 are given"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(|| b (== x 1)"),
+            ParseResult::Error {
+                line: 1,
+                col: 1,
+                message: String::from("unclosed `(`"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(|| b (== x 1)))"),
+            ParseResult::Error {
+                line: 1,
+                col: 16,
+                message: String::from("unbalanced `)`"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(&& b (== x b))"),
+            ParseResult::Error {
+                line: 1,
+                col: 13,
+                message: String::from("bool var b is given while int expr is expected"),
+            }
+        );
+        assert_eq!(
+            parse(&var_map, "(== x 1a)"),
+            ParseResult::Error {
+                line: 1,
+                col: 1,
+                message: String::from("syntax error at `(== x 1a)`"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parser_abs() {
+        assert_eq!(
+            parse_to_tree("(abs (- x y))"),
+            Result::Ok(SyntaxTree::Node(vec![
+                SyntaxTree::Ident("abs"),
+                SyntaxTree::Node(vec![
+                    SyntaxTree::Ident("-"),
+                    SyntaxTree::Ident("x"),
+                    SyntaxTree::Ident("y")
+                ])
+            ]))
+        );
+
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(-5, 5));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(-5, 5));
+        var_map.add_int_var("y", y);
+
+        let result = parse(&var_map, "(== (abs (- x y)) 3)");
+        assert_eq!(
+            result,
+            ParseResult::Stmt(Stmt::Expr(
+                (x.expr() - y.expr()).abs().eq(IntExpr::Const(3))
+            ))
+        );
+
+        let result = parse(&var_map, "(<= (+ (abs x) (abs y)) 5)");
+        assert_eq!(
+            result,
+            ParseResult::Stmt(Stmt::Expr(
+                IntExpr::Linear(vec![
+                    (Box::new(x.expr().abs()), 1),
+                    (Box::new(y.expr().abs()), 1)
+                ])
+                .le(IntExpr::Const(5))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parser_min_max() {
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let names = ["a", "b", "c", "d", "z"];
+        let vars = names
+            .iter()
+            .map(|&name| {
+                let v = solver.new_int_var(Domain::range(0, 2));
+                var_map.add_int_var(name, v);
+                v
+            })
+            .collect::<Vec<_>>();
+        let (a, b) = (vars[0], vars[1]);
+
+        assert_eq!(
+            parse(&var_map, "(== (min a b) 0)"),
+            ParseResult::Stmt(Stmt::Expr(
+                BoolExpr::And(vec![Box::new(a.expr().le(b.expr()))])
+                    .ite(a.expr(), b.expr())
+                    .eq(IntExpr::Const(0))
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(== (max a b) 0)"),
+            ParseResult::Stmt(Stmt::Expr(
+                BoolExpr::And(vec![Box::new(a.expr().ge(b.expr()))])
+                    .ite(a.expr(), b.expr())
+                    .eq(IntExpr::Const(0))
+            ))
+        );
+
+        // max(a, b, c, d) + z == 4 forces max(a, b, c, d) = 2, which holds for 3^4 - 2^4 cases
+        for (input, is_max, n_ans_expected) in [
+            ("(== z (min a b c d))", false, 81),
+            ("(== (+ (max a b c d) z) 4)", true, 65),
+        ] {
+            let mut solver = IntegratedSolver::new();
+            let mut var_map = VarMap::new();
+            let vars = names
+                .iter()
+                .map(|&name| {
+                    let v = solver.new_int_var(Domain::range(0, 2));
+                    var_map.add_int_var(name, v);
+                    v
+                })
+                .collect::<Vec<_>>();
+            match parse(&var_map, input) {
+                ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+                _ => panic!(),
+            }
+
+            let mut n_ans = 0;
+            for ans in solver.answer_iter(&[], &vars) {
+                let values = vars
+                    .iter()
+                    .map(|&v| ans.get_int(v).unwrap())
+                    .collect::<Vec<_>>();
+                if is_max {
+                    assert_eq!(values[..4].iter().max().unwrap() + values[4], 4);
+                } else {
+                    assert_eq!(values[..4].iter().min().unwrap(), &values[4]);
+                }
+                n_ans += 1;
+            }
+            assert_eq!(n_ans, n_ans_expected);
+        }
+    }
+
+    #[test]
+    fn test_parser_div_mod() {
+        assert_eq!(
+            parse_to_tree("(% (/ x y) 3)"),
+            Result::Ok(SyntaxTree::Node(vec![
+                SyntaxTree::Ident("%"),
+                SyntaxTree::Node(vec![
+                    SyntaxTree::Ident("/"),
+                    SyntaxTree::Ident("x"),
+                    SyntaxTree::Ident("y")
+                ]),
+                SyntaxTree::Int(3)
+            ]))
+        );
+
+        let mut var_map = VarMap::new();
+        let mut solver = IntegratedSolver::new();
+        let x = solver.new_int_var(Domain::range(-5, 5));
+        var_map.add_int_var("x", x);
+        let y = solver.new_int_var(Domain::range(1, 3));
+        var_map.add_int_var("y", y);
+
+        assert_eq!(
+            parse(&var_map, "(== (% x 3) 1)"),
+            ParseResult::Stmt(Stmt::Expr(
+                (x.expr() % IntExpr::Const(3)).eq(IntExpr::Const(1))
+            ))
+        );
+        assert_eq!(
+            parse(&var_map, "(<= (+ (div x y) (mod x y)) 2)"),
+            ParseResult::Stmt(Stmt::Expr(
+                IntExpr::Linear(vec![
+                    (Box::new(x.expr() / y.expr()), 1),
+                    (Box::new(x.expr() % y.expr()), 1)
+                ])
+                .le(IntExpr::Const(2))
+            ))
+        );
+
+        // x % 3 == 1 holds for x = -5, -2, 1, 4 since the remainder is non-negative
+        match parse(&var_map, "(== (% x 3) 1)") {
+            ParseResult::Stmt(stmt) => solver.add_constraint(stmt),
+            _ => panic!(),
+        }
+        let mut answers = solver
+            .answer_iter(&[], &[x])
+            .map(|a| a.get_int(x).unwrap())
+            .collect::<Vec<_>>();
+        answers.sort();
+        assert_eq!(answers, vec![-5, -2, 1, 4]);
+    }
 }
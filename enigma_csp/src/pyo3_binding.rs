@@ -91,6 +91,18 @@ impl PyConfig {
         Ok(())
     }
 
+    #[getter]
+    fn get_log_encoding_max_terms(&self) -> PyResult<i32> {
+        Ok(self.config.log_encoding_max_terms as i32)
+    }
+
+    #[setter]
+    fn set_log_encoding_max_terms(&mut self, value: i32) -> PyResult<()> {
+        assert!(value >= 2);
+        self.config.log_encoding_max_terms = value as usize;
+        Ok(())
+    }
+
     #[getter]
     fn get_use_direct_encoding(&self) -> PyResult<bool> {
         Ok(self.config.use_direct_encoding)
@@ -102,6 +114,17 @@ impl PyConfig {
         Ok(())
     }
 
+    #[getter]
+    fn get_dual_encoding(&self) -> PyResult<bool> {
+        Ok(self.config.dual_encoding)
+    }
+
+    #[setter]
+    fn set_dual_encoding(&mut self, value: bool) -> PyResult<()> {
+        self.config.dual_encoding = value;
+        Ok(())
+    }
+
     #[getter]
     fn get_merge_equivalent_variables(&self) -> PyResult<bool> {
         Ok(self.config.merge_equivalent_variables)
@@ -124,6 +147,17 @@ impl PyConfig {
         Ok(())
     }
 
+    #[getter]
+    fn get_order_encoding_phase_hints(&self) -> PyResult<bool> {
+        Ok(self.config.order_encoding_phase_hints)
+    }
+
+    #[setter]
+    fn set_order_encoding_phase_hints(&mut self, value: bool) -> PyResult<()> {
+        self.config.order_encoding_phase_hints = value;
+        Ok(())
+    }
+
     #[getter]
     fn get_glucose_random_seed(&self) -> PyResult<Option<f64>> {
         Ok(self.config.glucose_random_seed)
@@ -146,6 +180,18 @@ impl PyConfig {
         Ok(())
     }
 
+    #[getter]
+    fn get_portfolio(&self) -> PyResult<i32> {
+        Ok(self.config.portfolio as i32)
+    }
+
+    #[setter]
+    fn set_portfolio(&mut self, value: i32) -> PyResult<()> {
+        assert!(value >= 1);
+        self.config.portfolio = value as usize;
+        Ok(())
+    }
+
     #[getter]
     fn get_verbose(&self) -> PyResult<bool> {
         Ok(self.config.verbose)
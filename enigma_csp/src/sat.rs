@@ -1,4 +1,7 @@
 use std::ops::Not;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "backend-cadical")]
 use crate::backend::cadical;
@@ -31,6 +34,11 @@ impl Lit {
     pub fn is_negated(self) -> bool {
         self.0 % 2 == 1
     }
+
+    /// Returns this literal in DIMACS format (see `SAT::to_dimacs`).
+    pub fn to_dimacs(self) -> i32 {
+        (self.var().0 + 1) * if self.is_negated() { -1 } else { 1 }
+    }
 }
 
 impl Not for Lit {
@@ -41,16 +49,30 @@ impl Not for Lit {
     }
 }
 
+/// Size of a `SAT` instance at some point, used for removing everything added afterwards.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SATMark {
+    num_var: usize,
+    clauses: usize,
+    order_encoding_linear: usize,
+    active_vertices_connected: usize,
+    extension_supports: usize,
+    graph_division: usize,
+    default_phases: usize,
+}
+
 pub struct SATSolverStats {
     pub decisions: Option<u64>,
     pub propagations: Option<u64>,
     pub conflicts: Option<u64>,
+    pub restarts: Option<u64>,
+    /// Number of learnt clauses currently kept by the solver (not cumulative, unlike others).
+    pub learnts: Option<u64>,
+    /// Number of problem clauses currently kept by the solver (not cumulative).
+    pub clauses: Option<u64>,
 }
 
-/// Adapter to SAT solver.
-/// To support other SAT solver without changing previous stages, we introduce an adapter instead of
-/// using `glucose::Solver` directly from the encoder.
-pub enum SAT {
+enum SATSolver {
     Glucose(glucose::Solver),
     #[cfg(feature = "backend-external")]
     External(external::Solver),
@@ -58,10 +80,29 @@ pub enum SAT {
     CaDiCaL(cadical::Solver),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub enum Backend {
+/// Adapter to SAT solver.
+/// To support other SAT solver without changing previous stages, we introduce an adapter instead of
+/// using `glucose::Solver` directly from the encoder.
+pub struct SAT {
+    solver: SATSolver,
+    record: Option<ClauseRecord>,
+    portfolio: usize,
+    // Glucose instances other than `solver` used in portfolio solving, which contain everything
+    // recorded until `portfolio_mark`
+    portfolio_workers: Vec<glucose::Solver>,
+    portfolio_mark: SATMark,
+    interrupt: Option<Arc<AtomicBool>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SatBackend {
     Glucose,
-    External,
+    /// An external SAT solver binary, which is run as `path args... <DIMACS file>` and reports
+    /// the result in the standard `s`/`v` line format.
+    External {
+        path: String,
+        args: Vec<String>,
+    },
     CaDiCaL,
 }
 
@@ -70,57 +111,188 @@ impl SAT {
         SAT::new_glucose()
     }
 
+    fn from_solver(solver: SATSolver) -> SAT {
+        SAT {
+            solver,
+            record: None,
+            portfolio: 1,
+            portfolio_workers: vec![],
+            portfolio_mark: SATMark::default(),
+            interrupt: None,
+        }
+    }
+
     pub fn new_glucose() -> SAT {
-        SAT::Glucose(glucose::Solver::new())
+        SAT::from_solver(SATSolver::Glucose(glucose::Solver::new()))
     }
 
     #[cfg(feature = "backend-external")]
-    pub fn new_external() -> SAT {
-        SAT::External(external::Solver::new())
+    pub fn new_external(path: &str, args: &[String]) -> SAT {
+        SAT::from_solver(SATSolver::External(external::Solver::new(path, args)))
     }
 
     #[cfg(feature = "backend-cadical")]
     pub fn new_cadical() -> SAT {
-        SAT::CaDiCaL(cadical::Solver::new())
+        SAT::from_solver(SATSolver::CaDiCaL(cadical::Solver::new()))
     }
 
-    pub fn new_with_backend(backend: Backend) -> SAT {
+    pub fn new_with_backend(backend: &SatBackend) -> SAT {
         match backend {
-            Backend::Glucose => SAT::new_glucose(),
+            SatBackend::Glucose => SAT::new_glucose(),
             #[cfg(feature = "backend-external")]
-            Backend::External => SAT::new_external(),
+            SatBackend::External { path, args } => SAT::new_external(path, args),
             #[cfg(not(feature = "backend-external"))]
-            Backend::External => panic!("external backend is not enabled"),
+            SatBackend::External { .. } => panic!("external backend is not enabled"),
             #[cfg(feature = "backend-cadical")]
-            Backend::CaDiCaL => SAT::new_cadical(),
+            SatBackend::CaDiCaL => SAT::new_cadical(),
             #[cfg(not(feature = "backend-cadical"))]
-            Backend::CaDiCaL => panic!("CaDiCaL backend is not enabled"),
+            SatBackend::CaDiCaL => panic!("CaDiCaL backend is not enabled"),
+        }
+    }
+
+    /// Starts recording everything added to this instance, which is required for `to_dimacs`.
+    /// This must be called before any variable is created.
+    pub fn enable_recording(&mut self) {
+        assert_eq!(self.num_var(), 0);
+        self.record = Some(ClauseRecord::new());
+    }
+
+    /// Makes the subsequent calls of `solve` run `workers` Glucose instances in parallel and
+    /// adopt the result of the first one to finish.
+    /// Worker 0 is this instance itself, and the others are built from the recorded constraints
+    /// (and phase hints) in the first call and use random initial activities with different
+    /// seeds. The other workers are kept for later calls, in which only the constraints added
+    /// since the previous call are fed to them, so learned clauses of all the workers are kept.
+    /// The winner swaps places with this instance.
+    /// This enables recording, and therefore must be called before any variable is created.
+    /// Only the Glucose backend is supported.
+    pub fn set_portfolio(&mut self, workers: usize) {
+        assert!(workers >= 1);
+        assert!(matches!(self.solver, SATSolver::Glucose(_)));
+        if workers > 1 && self.record.is_none() {
+            self.enable_recording();
+        }
+        self.portfolio = workers;
+    }
+
+    /// Returns the current size of the instance, which can be passed to `truncate` later.
+    /// Recording must be enabled.
+    pub fn mark(&self) -> SATMark {
+        let record = self
+            .record
+            .as_ref()
+            .expect("mark requires recording to be enabled");
+        SATMark {
+            num_var: self.num_var(),
+            clauses: record.clauses.len(),
+            order_encoding_linear: record.order_encoding_linear.len(),
+            active_vertices_connected: record.active_vertices_connected.len(),
+            extension_supports: record.extension_supports.len(),
+            graph_division: record.graph_division.len(),
+            default_phases: record.default_phases.len(),
+        }
+    }
+
+    /// Removes all the variables and constraints added after `mark` was taken.
+    /// The solver is rebuilt from the recorded constraints and phase hints, so learned clauses and
+    /// solver settings (such as the random seed) are discarded and must be set again if needed.
+    /// Only the Glucose backend supports this.
+    pub fn truncate(&mut self, mark: SATMark) {
+        assert!(matches!(self.solver, SATSolver::Glucose(_)));
+        assert!(mark.num_var <= self.num_var());
+        let record = self
+            .record
+            .as_mut()
+            .expect("truncate requires recording to be enabled");
+        record.clauses.truncate(mark.clauses);
+        record
+            .order_encoding_linear
+            .truncate(mark.order_encoding_linear);
+        record
+            .active_vertices_connected
+            .truncate(mark.active_vertices_connected);
+        record.extension_supports.truncate(mark.extension_supports);
+        record.graph_division.truncate(mark.graph_division);
+        record.default_phases.truncate(mark.default_phases);
+        self.solver = SATSolver::Glucose(record.replay_glucose(mark.num_var));
+        // the workers are rebuilt in the next call of `solve_portfolio`
+        self.portfolio_workers.clear();
+        self.portfolio_mark = SATMark::default();
+    }
+
+    /// Returns the instance in DIMACS CNF format. SAT variable `i` (0-based) corresponds to
+    /// DIMACS variable `i + 1`.
+    /// Constraints which are not plain clauses are expanded into clauses with auxiliary
+    /// variables, which are numbered after all the variables of this instance:
+    /// - `add_order_encoding_linear` is expanded into the usual order encoding of the linear
+    ///   constraint, whose size is proportional to the product of the domain sizes of all the
+    ///   terms except one.
+    /// - `add_active_vertices_connected` is expanded into a reachability encoding from the active
+    ///   vertex with the smallest index, which uses O(n^2) auxiliary variables for n vertices.
+    /// - `add_direct_encoding_extension_supports` introduces an auxiliary variable for each support.
+    ///
+    /// `add_graph_division` is not supported. Also, `enable_recording` must have been called
+    /// before constructing the instance.
+    pub fn to_dimacs(&self) -> String {
+        let record = self
+            .record
+            .as_ref()
+            .expect("to_dimacs requires enable_recording");
+        assert!(
+            record.graph_division.is_empty(),
+            "add_graph_division cannot be exported to DIMACS"
+        );
+
+        let mut num_var = self.num_var();
+        let mut extra_clauses = vec![];
+        for (lits, domain, coefs, constant) in &record.order_encoding_linear {
+            expand_order_encoding_linear(lits, domain, coefs, *constant, &mut extra_clauses);
         }
+        for (lits, edges) in &record.active_vertices_connected {
+            expand_active_vertices_connected(lits, edges, &mut num_var, &mut extra_clauses);
+        }
+        for (vars, supports) in &record.extension_supports {
+            expand_extension_supports(vars, supports, &mut num_var, &mut extra_clauses);
+        }
+
+        let num_clauses = record.clauses.len() + extra_clauses.len();
+        let mut ret = String::new();
+        ret.push_str(&format!("c variables: {}\n", num_var));
+        ret.push_str(&format!("c clauses: {}\n", num_clauses));
+        ret.push_str(&format!("p cnf {} {}\n", num_var, num_clauses));
+        for clause in record.clauses.iter().chain(extra_clauses.iter()) {
+            for &l in clause {
+                ret.push_str(&l.to_dimacs().to_string());
+                ret.push(' ');
+            }
+            ret.push_str("0\n");
+        }
+        ret
     }
 
     pub fn num_var(&self) -> usize {
-        match self {
-            SAT::Glucose(solver) => solver.num_var() as usize,
+        match &self.solver {
+            SATSolver::Glucose(solver) => solver.num_var() as usize,
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => solver.num_var() as usize,
+            SATSolver::External(solver) => solver.num_var() as usize,
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => solver.num_var() as usize,
+            SATSolver::CaDiCaL(solver) => solver.num_var() as usize,
         }
     }
 
     pub fn all_vars(&self) -> Vec<Var> {
-        match self {
-            SAT::Glucose(solver) => {
+        match &self.solver {
+            SATSolver::Glucose(solver) => {
                 let ret = solver.all_vars();
                 unsafe { std::mem::transmute::<_, Vec<Var>>(ret) }
             }
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => {
+            SATSolver::External(solver) => {
                 let ret = solver.all_vars();
                 unsafe { std::mem::transmute::<_, Vec<Var>>(ret) }
             }
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => {
+            SATSolver::CaDiCaL(solver) => {
                 let ret = solver.all_vars();
                 unsafe { std::mem::transmute::<_, Vec<Var>>(ret) }
             }
@@ -129,21 +301,21 @@ impl SAT {
 
     #[cfg(feature = "sat-analyzer")]
     pub fn new_var(&mut self, name: &str) -> Var {
-        match self {
-            SAT::Glucose(solver) => solver.new_named_var(name),
-            SAT::External(_) => panic!("new_var is not supported in external backend"),
-            SAT::CaDiCaL(_) => panic!("new_var is not supported in cadical backend"),
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.new_named_var(name),
+            SATSolver::External(_) => panic!("new_var is not supported in external backend"),
+            SATSolver::CaDiCaL(_) => panic!("new_var is not supported in cadical backend"),
         }
     }
 
     #[cfg(not(feature = "sat-analyzer"))]
     pub fn new_var(&mut self) -> Var {
-        match self {
-            SAT::Glucose(solver) => solver.new_var(),
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.new_var(),
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => solver.new_var(),
+            SATSolver::External(solver) => solver.new_var(),
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => solver.new_var(),
+            SATSolver::CaDiCaL(solver) => solver.new_var(),
         }
     }
 
@@ -178,21 +350,81 @@ impl SAT {
     }
 
     pub fn add_clause(&mut self, clause: &[Lit]) {
-        match self {
-            SAT::Glucose(solver) => {
+        if let Some(record) = &mut self.record {
+            record.clauses.push(clause.to_owned());
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => {
                 solver.add_clause(clause);
             }
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => {
+            SATSolver::External(solver) => {
                 solver.add_clause(clause);
             }
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => {
+            SATSolver::CaDiCaL(solver) => {
                 solver.add_clause(clause);
             }
         }
     }
 
+    /// Adds the constraint that at most `k` of `lits` are true.
+    /// This is encoded by a sequential unary counter, which requires O(nk) auxiliary variables and
+    /// clauses (where n is the number of `lits`), instead of the generic linear encoding.
+    pub fn add_at_most_k(&mut self, lits: &[Lit], k: usize) {
+        let n = lits.len();
+        if k >= n {
+            return;
+        }
+        if k == 0 {
+            for &lit in lits {
+                self.add_clause(&[!lit]);
+            }
+            return;
+        }
+
+        // counter[i][j] is true if at least j+1 of lits[0..=i] are true
+        let mut counter: Vec<Vec<Lit>> = vec![];
+        for i in 0..(n - 1) {
+            #[cfg(not(feature = "sat-analyzer"))]
+            let c = self.new_vars_as_lits(k);
+            #[cfg(feature = "sat-analyzer")]
+            let c = self.new_vars_as_lits(k, "at_most_k");
+
+            self.add_clause(&[!lits[i], c[0]]);
+            if i == 0 {
+                for &l in &c[1..] {
+                    self.add_clause(&[!l]);
+                }
+            } else {
+                let prev = &counter[i - 1];
+                self.add_clause(&[!prev[0], c[0]]);
+                for j in 1..k {
+                    self.add_clause(&[!lits[i], !prev[j - 1], c[j]]);
+                    self.add_clause(&[!prev[j], c[j]]);
+                }
+                self.add_clause(&[!lits[i], !prev[k - 1]]);
+            }
+            counter.push(c);
+        }
+        self.add_clause(&[!lits[n - 1], !counter[n - 2][k - 1]]);
+    }
+
+    /// Adds the constraint that at least `k` of `lits` are true.
+    /// Combined with `add_at_most_k`, this gives the constraint that exactly `k` of `lits` are true.
+    pub fn add_at_least_k(&mut self, lits: &[Lit], k: usize) {
+        let n = lits.len();
+        if k == 0 {
+            return;
+        }
+        if k > n {
+            self.add_clause(&[]);
+            return;
+        }
+        let negated = lits.iter().map(|&l| !l).collect::<Vec<_>>();
+        self.add_at_most_k(&negated, n - k);
+    }
+
     pub fn add_order_encoding_linear(
         &mut self,
         lits: Vec<Vec<Lit>>,
@@ -200,16 +432,24 @@ impl SAT {
         coefs: Vec<i32>,
         constant: i32,
     ) -> bool {
-        match self {
-            SAT::Glucose(solver) => {
+        if let Some(record) = &mut self.record {
+            record.order_encoding_linear.push((
+                lits.clone(),
+                domain.clone(),
+                coefs.clone(),
+                constant,
+            ));
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => {
                 solver.add_order_encoding_linear(&lits, &domain, &coefs, constant)
             }
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => {
+            SATSolver::External(_) => {
                 panic!("add_order_encoding_linear is not supported in external backend")
             }
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => todo!(),
+            SATSolver::CaDiCaL(_) => todo!(),
         }
     }
 
@@ -218,14 +458,19 @@ impl SAT {
         lits: Vec<Lit>,
         edges: Vec<(usize, usize)>,
     ) -> bool {
-        match self {
-            SAT::Glucose(solver) => solver.add_active_vertices_connected(&lits, &edges),
+        if let Some(record) = &mut self.record {
+            record
+                .active_vertices_connected
+                .push((lits.clone(), edges.clone()));
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.add_active_vertices_connected(&lits, &edges),
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => {
+            SATSolver::External(_) => {
                 panic!("add_active_vertices_connected is not supported in external backend")
             }
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => {
+            SATSolver::CaDiCaL(solver) => {
                 solver.add_active_vertices_connected(&lits, &edges);
                 true
             }
@@ -247,14 +492,21 @@ impl SAT {
         vars: &[Vec<Lit>],
         supports: &[Vec<Option<usize>>],
     ) -> bool {
-        match self {
-            SAT::Glucose(solver) => solver.add_direct_encoding_extension_supports(&vars, supports),
+        if let Some(record) = &mut self.record {
+            record
+                .extension_supports
+                .push((vars.to_owned(), supports.to_owned()));
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => {
+                solver.add_direct_encoding_extension_supports(&vars, supports)
+            }
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => panic!(
+            SATSolver::External(_) => panic!(
                 "add_direct_encoding_extension_supports is not supported in external backend"
             ),
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => todo!(),
+            SATSolver::CaDiCaL(_) => todo!(),
         }
     }
 
@@ -265,98 +517,607 @@ impl SAT {
         edges: &[(usize, usize)],
         edge_lits: &[Lit],
     ) -> bool {
-        match self {
-            SAT::Glucose(solver) => solver.add_graph_division(domains, dom_lits, edges, edge_lits),
+        if let Some(record) = &mut self.record {
+            record.graph_division.push((
+                domains.to_owned(),
+                dom_lits.to_owned(),
+                edges.to_owned(),
+                edge_lits.to_owned(),
+            ));
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => {
+                solver.add_graph_division(domains, dom_lits, edges, edge_lits)
+            }
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => panic!("add_graph_division is not supported in external backend"),
+            SATSolver::External(_) => {
+                panic!("add_graph_division is not supported in external backend")
+            }
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => todo!(),
+            SATSolver::CaDiCaL(_) => todo!(),
+        }
+    }
+
+    /// Hints the solver to try making `lit` equal to `phase` first when it decides the variable
+    /// of `lit`. This affects only the performance, not the result.
+    /// The hint is ignored by the external backend.
+    pub fn set_default_phase(&mut self, lit: Lit, phase: bool) {
+        if let Some(record) = &mut self.record {
+            record.default_phases.push((lit, phase));
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.set_polarity(lit.var(), phase ^ lit.is_negated()),
+            #[cfg(feature = "backend-external")]
+            SATSolver::External(_) => (),
+            #[cfg(feature = "backend-cadical")]
+            SATSolver::CaDiCaL(solver) => solver.set_phase(if phase { lit } else { !lit }),
         }
     }
 
     pub fn set_seed(&mut self, seed: f64) {
-        match self {
-            SAT::Glucose(solver) => solver.set_seed(seed),
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.set_seed(seed),
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => (), // TODO: add warning
+            SATSolver::External(_) => (), // TODO: add warning
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => (), // TODO
+            SATSolver::CaDiCaL(_) => (), // TODO
         }
     }
 
     pub fn set_rnd_init_act(&mut self, rnd_init_act: bool) {
-        match self {
-            SAT::Glucose(solver) => solver.set_rnd_init_act(rnd_init_act),
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.set_rnd_init_act(rnd_init_act),
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => (), // TODO: add warning
+            SATSolver::External(_) => (), // TODO: add warning
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => (), // TODO
+            SATSolver::CaDiCaL(_) => (), // TODO
         }
     }
 
     pub fn set_dump_analysis_info(&mut self, dump_analysis_info: bool) {
-        match self {
-            SAT::Glucose(solver) => solver.set_dump_analysis_info(dump_analysis_info),
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.set_dump_analysis_info(dump_analysis_info),
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => (), // TODO: add warning
+            SATSolver::External(_) => (), // TODO: add warning
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => (), // TODO: add warning
+            SATSolver::CaDiCaL(_) => (), // TODO: add warning
+        }
+    }
+
+    /// Makes the solver write a DRAT proof to `path` while solving (only supported in Glucose
+    /// backend). When `solve` (without assumptions) finds the instance unsatisfiable, the proof
+    /// is completed with the empty clause and can be checked against the output of `to_dimacs`
+    /// by an external checker such as drat-trim.
+    /// Note that the proof is valid only if no native constraint (`add_order_encoding_linear`,
+    /// `add_active_vertices_connected` and so on) is added, because propagations by them are
+    /// not justified by the clauses.
+    #[cfg(feature = "drat")]
+    pub fn enable_drat(&mut self, path: &str) -> std::io::Result<()> {
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.enable_drat(path),
+            #[cfg(feature = "backend-external")]
+            SATSolver::External(_) => panic!("enable_drat is not supported in external backend"),
+            #[cfg(feature = "backend-cadical")]
+            SATSolver::CaDiCaL(_) => panic!("enable_drat is not supported in cadical backend"),
         }
     }
 
     pub fn solve<'a>(&'a mut self) -> Option<SATModel<'a>> {
-        match self {
-            SAT::Glucose(solver) => solver.solve().map(|model| SATModel::Glucose(model)),
+        if self.portfolio > 1 || self.interrupt.is_some() {
+            return self.solve_under_assumptions(&[]);
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.solve().map(|model| SATModel::Glucose(model)),
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => solver.solve().map(|model| SATModel::External(model)),
+            SATSolver::External(solver) => solver.solve().map(|model| SATModel::External(model)),
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => solver.solve().map(|model| SATModel::CaDiCaL(model)),
+            SATSolver::CaDiCaL(solver) => solver.solve().map(|model| SATModel::CaDiCaL(model)),
         }
     }
 
     pub fn solve_without_model(&mut self) -> bool {
-        match self {
-            SAT::Glucose(solver) => solver.solve_without_model(),
+        if self.portfolio > 1 || self.interrupt.is_some() {
+            return self.solve_without_model_under_assumptions(&[]);
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.solve_without_model(),
+            #[cfg(feature = "backend-external")]
+            SATSolver::External(solver) => solver.solve_without_model(),
+            #[cfg(feature = "backend-cadical")]
+            SATSolver::CaDiCaL(solver) => solver.solve_without_model(),
+        }
+    }
+
+    /// Solves the instance assuming that all literals in `assumptions` are true.
+    /// Assumptions only affect this call and are not kept for later calls.
+    pub fn solve_under_assumptions<'a>(&'a mut self, assumptions: &[Lit]) -> Option<SATModel<'a>> {
+        if self.portfolio > 1 || self.interrupt.is_some() {
+            return if self.solve_without_model_under_assumptions(assumptions) {
+                Some(unsafe { self.model() })
+            } else {
+                None
+            };
+        }
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver
+                .solve_under_assumptions(assumptions)
+                .map(SATModel::Glucose),
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => solver.solve_without_model(),
+            SATSolver::External(solver) => solver
+                .solve_under_assumptions(assumptions)
+                .map(SATModel::External),
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => solver.solve_without_model(),
+            SATSolver::CaDiCaL(solver) => solver
+                .solve_under_assumptions(assumptions)
+                .map(SATModel::CaDiCaL),
+        }
+    }
+
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        // cancellation is reported as unsatisfiability, since there is no way to tell it here
+        if self.portfolio > 1 {
+            return self.solve_portfolio(assumptions, None).unwrap_or(false);
+        }
+        if self.interrupt.is_some() {
+            return self.solve_limited(assumptions, None).unwrap_or(false);
+        }
+        self.solve_without_model_unlimited(assumptions)
+    }
+
+    fn solve_without_model_unlimited(&mut self, assumptions: &[Lit]) -> bool {
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => solver.solve_without_model_under_assumptions(assumptions),
+            #[cfg(feature = "backend-external")]
+            SATSolver::External(solver) => {
+                solver.solve_without_model_under_assumptions(assumptions)
+            }
+            #[cfg(feature = "backend-cadical")]
+            SATSolver::CaDiCaL(solver) => solver.solve_without_model_under_assumptions(assumptions),
+        }
+    }
+
+    /// Same as `solve_without_model_under_assumptions`, but gives up and returns `None` if the
+    /// SAT solver does not finish within `timeout`. The solver remains usable after timeout.
+    /// Only the Glucose backend (including portfolio solving) supports this; otherwise `timeout`
+    /// is ignored and the solver always runs to completion.
+    pub fn solve_without_model_with_timeout(
+        &mut self,
+        assumptions: &[Lit],
+        timeout: Duration,
+    ) -> Option<bool> {
+        let deadline = Instant::now() + timeout;
+        if self.portfolio > 1 {
+            return self.solve_portfolio(assumptions, Some(deadline));
+        }
+        self.solve_limited(assumptions, Some(deadline))
+    }
+
+    /// Returns the flag for cancelling the SAT solver, possibly from another thread.
+    /// While the flag is true, the SAT solver gives up within a bounded number of conflicts:
+    /// `solve_without_model_with_timeout` returns `None` and other `solve` methods behave as
+    /// if the instance were unsatisfiable. The flag is not cleared automatically.
+    /// Only the Glucose backend supports this (including portfolio solving).
+    pub fn interrupt_flag(&mut self) -> Arc<AtomicBool> {
+        self.interrupt
+            .get_or_insert_with(|| Arc::new(AtomicBool::new(false)))
+            .clone()
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupt
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::SeqCst))
+    }
+
+    fn solve_limited(&mut self, assumptions: &[Lit], deadline: Option<Instant>) -> Option<bool> {
+        const CONFLICTS_PER_CHECK: u64 = 1000;
+
+        match &mut self.solver {
+            SATSolver::Glucose(solver) => {
+                // The search is split into chunks with limited number of conflicts, which keeps
+                // the learned clauses, so that the deadline and the interrupt flag can be
+                // checked without threads.
+                let interrupt = &self.interrupt;
+                let is_interrupted = || {
+                    interrupt
+                        .as_ref()
+                        .is_some_and(|flag| flag.load(Ordering::SeqCst))
+                };
+                loop {
+                    if is_interrupted() {
+                        return None;
+                    }
+                    if let Some(res) = solver.solve_limited(assumptions, Some(CONFLICTS_PER_CHECK))
+                    {
+                        return Some(res);
+                    }
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return None;
+                    }
+                }
+            }
+            #[allow(unreachable_patterns)]
+            _ => Some(self.solve_without_model_unlimited(assumptions)),
+        }
+    }
+
+    /// Returns `None` if solving is cancelled by the interrupt flag or `deadline` is passed.
+    fn solve_portfolio(&mut self, assumptions: &[Lit], deadline: Option<Instant>) -> Option<bool> {
+        // how often the interrupt flag and the deadline are checked while the workers are running
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+        if self.is_interrupted() {
+            return None;
+        }
+        let num_var = self.num_var();
+        let mark = self.mark();
+        let portfolio = self.portfolio;
+        let record = self.record.as_ref().unwrap();
+        let interrupt = &self.interrupt;
+        let main = match &mut self.solver {
+            SATSolver::Glucose(solver) => solver,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        };
+        let workers = &mut self.portfolio_workers;
+        if workers.is_empty() {
+            for i in 1..portfolio {
+                let mut worker = glucose::Solver::new();
+                worker.set_seed(i as f64);
+                worker.set_rnd_init_act(true);
+                workers.push(worker);
+            }
+        }
+        for worker in workers.iter_mut() {
+            record.replay_since(worker, &self.portfolio_mark, num_var);
+        }
+        self.portfolio_mark = mark;
+
+        let handles = std::iter::once(main.interrupt_handle())
+            .chain(workers.iter().map(|w| w.interrupt_handle()))
+            .collect::<Vec<_>>();
+        let interrupt_all = || handles.iter().for_each(|handle| handle.interrupt());
+        let winner = std::thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            for (id, solver) in std::iter::once(&mut *main)
+                .chain(workers.iter_mut())
+                .enumerate()
+            {
+                let sender = sender.clone();
+                // an interrupted solver reports `None`
+                scope.spawn(move || sender.send((id, solver.solve_limited(assumptions, None))));
+            }
+
+            let mut winner = None;
+            let mut num_finished = 0;
+            while num_finished < portfolio {
+                match receiver.recv_timeout(POLL_INTERVAL) {
+                    Ok((id, res)) => {
+                        num_finished += 1;
+                        // Losers are interrupted, so only the result of the first finisher is
+                        // valid
+                        if let (None, Some(res)) = (winner, res) {
+                            winner = Some((id, res));
+                            interrupt_all();
+                        }
+                    }
+                    Err(_) => {
+                        let is_interrupted = interrupt
+                            .as_ref()
+                            .is_some_and(|flag| flag.load(Ordering::SeqCst));
+                        let is_timed_out =
+                            deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                        if is_interrupted || is_timed_out {
+                            interrupt_all();
+                        }
+                    }
+                }
+            }
+            winner
+        });
+        main.clear_interrupt();
+        for worker in workers.iter_mut() {
+            worker.clear_interrupt();
+        }
+
+        let (id, res) = winner?;
+        if id > 0 {
+            std::mem::swap(main, &mut workers[id - 1]);
+        }
+        Some(res)
+    }
+
+    /// Returns a subset of the assumptions of the last call to `solve_under_assumptions` which
+    /// is sufficient for the instance to be unsatisfiable.
+    /// The subset is not necessarily minimal. If the last call was not unsatisfiable or the
+    /// instance is unsatisfiable even without assumptions, the result is unspecified (typically
+    /// empty).
+    pub fn unsat_core(&self) -> Vec<Lit> {
+        match &self.solver {
+            SATSolver::Glucose(solver) => solver.unsat_core(),
+            #[cfg(feature = "backend-external")]
+            SATSolver::External(solver) => solver.unsat_core(),
+            #[cfg(feature = "backend-cadical")]
+            SATSolver::CaDiCaL(solver) => solver.unsat_core(),
         }
     }
 
     pub(crate) unsafe fn model<'a>(&'a self) -> SATModel<'a> {
-        match self {
-            SAT::Glucose(solver) => SATModel::Glucose(solver.model()),
+        match &self.solver {
+            SATSolver::Glucose(solver) => SATModel::Glucose(solver.model()),
             #[cfg(feature = "backend-external")]
-            SAT::External(solver) => SATModel::External(solver.model()),
+            SATSolver::External(solver) => SATModel::External(solver.model()),
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(solver) => SATModel::CaDiCaL(solver.model()),
+            SATSolver::CaDiCaL(solver) => SATModel::CaDiCaL(solver.model()),
         }
     }
 
     pub fn stats(&self) -> SATSolverStats {
-        match self {
-            SAT::Glucose(solver) => SATSolverStats {
+        match &self.solver {
+            SATSolver::Glucose(solver) => SATSolverStats {
                 decisions: Some(solver.stats_decisions()),
                 propagations: Some(solver.stats_propagations()),
                 conflicts: Some(solver.stats_conflicts()),
+                restarts: Some(solver.stats_restarts()),
+                learnts: Some(solver.stats_learnts()),
+                clauses: Some(solver.stats_clauses()),
             },
             #[cfg(feature = "backend-external")]
-            SAT::External(_) => SATSolverStats {
+            SATSolver::External(_) => SATSolverStats {
                 decisions: None,
                 propagations: None,
                 conflicts: None,
+                restarts: None,
+                learnts: None,
+                clauses: None,
             },
             #[cfg(feature = "backend-cadical")]
-            SAT::CaDiCaL(_) => SATSolverStats {
+            SATSolver::CaDiCaL(_) => SATSolverStats {
                 decisions: None,
                 propagations: None,
                 conflicts: None,
+                restarts: None,
+                learnts: None,
+                clauses: None,
             }, // TODO
         }
     }
 }
 
+type OrderEncodingLinearRecord = (Vec<Vec<Lit>>, Vec<Vec<i32>>, Vec<i32>, i32);
+type ActiveVerticesConnectedRecord = (Vec<Lit>, Vec<(usize, usize)>);
+type ExtensionSupportsRecord = (Vec<Vec<Lit>>, Vec<Vec<Option<usize>>>);
+type GraphDivisionRecord = (Vec<Vec<i32>>, Vec<Vec<Lit>>, Vec<(usize, usize)>, Vec<Lit>);
+
+struct ClauseRecord {
+    clauses: Vec<Vec<Lit>>,
+    order_encoding_linear: Vec<OrderEncodingLinearRecord>,
+    active_vertices_connected: Vec<ActiveVerticesConnectedRecord>,
+    extension_supports: Vec<ExtensionSupportsRecord>,
+    graph_division: Vec<GraphDivisionRecord>,
+    default_phases: Vec<(Lit, bool)>,
+}
+
+impl ClauseRecord {
+    fn new() -> ClauseRecord {
+        ClauseRecord {
+            clauses: vec![],
+            order_encoding_linear: vec![],
+            active_vertices_connected: vec![],
+            extension_supports: vec![],
+            graph_division: vec![],
+            default_phases: vec![],
+        }
+    }
+
+    /// Builds a Glucose instance equivalent to the recorded one with `num_var` variables.
+    fn replay_glucose(&self, num_var: usize) -> glucose::Solver {
+        let mut solver = glucose::Solver::new();
+        self.replay_since(&mut solver, &SATMark::default(), num_var);
+        solver
+    }
+
+    /// Adds everything recorded after `from` to `solver`, which is supposed to contain everything
+    /// recorded until `from`, so that it becomes equivalent to the recorded one with `num_var`
+    /// variables.
+    fn replay_since(&self, solver: &mut glucose::Solver, from: &SATMark, num_var: usize) {
+        for _ in (solver.num_var() as usize)..num_var {
+            solver.new_var();
+        }
+        for clause in &self.clauses[from.clauses..] {
+            solver.add_clause(clause);
+        }
+        for (lits, domain, coefs, constant) in
+            &self.order_encoding_linear[from.order_encoding_linear..]
+        {
+            solver.add_order_encoding_linear(lits, domain, coefs, *constant);
+        }
+        for (lits, edges) in &self.active_vertices_connected[from.active_vertices_connected..] {
+            solver.add_active_vertices_connected(lits, edges);
+        }
+        for (vars, supports) in &self.extension_supports[from.extension_supports..] {
+            solver.add_direct_encoding_extension_supports(vars, supports);
+        }
+        for (domains, dom_lits, edges, edge_lits) in &self.graph_division[from.graph_division..] {
+            solver.add_graph_division(domains, dom_lits, edges, edge_lits);
+        }
+        for &(lit, phase) in &self.default_phases[from.default_phases..] {
+            solver.set_polarity(lit.var(), phase ^ lit.is_negated());
+        }
+    }
+}
+
+fn new_aux_lit(num_var: &mut usize) -> Lit {
+    let ret = Var(*num_var as i32).as_lit(false);
+    *num_var += 1;
+    ret
+}
+
+/// Expands (sum_i coefs[i] * x_i + constant >= 0) into clauses, where x_i takes a value in
+/// `domain[i]` (sorted in ascending order) and `lits[i][j]` means x_i >= domain[i][j + 1].
+fn expand_order_encoding_linear(
+    lits: &[Vec<Lit>],
+    domain: &[Vec<i32>],
+    coefs: &[i32],
+    constant: i32,
+    out: &mut Vec<Vec<Lit>>,
+) {
+    // For each term, the possible values in ascending order, each paired with the literal
+    // asserting that the term is larger than the value (None if it never is)
+    let mut terms: Vec<Vec<(i64, Option<Lit>)>> = vec![];
+    for i in 0..lits.len() {
+        let coef = coefs[i] as i64;
+        let n = domain[i].len();
+        assert_eq!(lits[i].len() + 1, n);
+        if coef > 0 {
+            terms.push(
+                (0..n)
+                    .map(|j| (domain[i][j] as i64 * coef, lits[i].get(j).copied()))
+                    .collect(),
+            );
+        } else if coef < 0 {
+            terms.push(
+                (0..n)
+                    .rev()
+                    .map(|j| {
+                        let lit = if j > 0 { Some(!lits[i][j - 1]) } else { None };
+                        (domain[i][j] as i64 * coef, lit)
+                    })
+                    .collect(),
+            );
+        }
+    }
+    if terms.is_empty() {
+        if constant < 0 {
+            out.push(vec![]);
+        }
+        return;
+    }
+
+    // A clause is generated for each combination of upper bounds of the terms whose sum
+    // violates the constraint
+    let mut min_suffix_sum = vec![0i64; terms.len() + 1];
+    for i in (0..terms.len()).rev() {
+        min_suffix_sum[i] = min_suffix_sum[i + 1] + terms[i][0].0;
+    }
+    fn visit(
+        terms: &[Vec<(i64, Option<Lit>)>],
+        min_suffix_sum: &[i64],
+        idx: usize,
+        sum: i64,
+        clause: &mut Vec<Lit>,
+        out: &mut Vec<Vec<Lit>>,
+    ) {
+        if sum + min_suffix_sum[idx] >= 0 {
+            return;
+        }
+        if idx == terms.len() - 1 {
+            let last = terms[idx]
+                .iter()
+                .rev()
+                .find(|&&(v, _)| sum + v < 0)
+                .unwrap();
+            let mut c = clause.clone();
+            if let Some(l) = last.1 {
+                c.push(l);
+            }
+            out.push(c);
+            return;
+        }
+        for &(v, l) in &terms[idx] {
+            if let Some(l) = l {
+                clause.push(l);
+            }
+            visit(terms, min_suffix_sum, idx + 1, sum + v, clause, out);
+            if l.is_some() {
+                clause.pop();
+            }
+        }
+    }
+    visit(
+        &terms,
+        &min_suffix_sum,
+        0,
+        constant as i64,
+        &mut vec![],
+        out,
+    );
+}
+
+/// Expands the constraint that the vertices whose `lits` are true are connected.
+/// The active vertex with the smallest index is chosen as the root, and `reach[k][v]` means
+/// that `v` is reachable from the root within `k` steps through active vertices.
+fn expand_active_vertices_connected(
+    lits: &[Lit],
+    edges: &[(usize, usize)],
+    num_var: &mut usize,
+    out: &mut Vec<Vec<Lit>>,
+) {
+    let n = lits.len();
+    if n == 0 {
+        return;
+    }
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+
+    // `has_active_before[v]`: some vertex with index less than `v` is active
+    let mut has_active_before: Vec<Lit> = vec![];
+    for v in 0..n {
+        let lit = new_aux_lit(num_var);
+        if v > 0 {
+            out.push(vec![!has_active_before[v - 1], lit]);
+            out.push(vec![!lits[v - 1], lit]);
+        }
+        has_active_before.push(lit);
+    }
+    let mut reach: Vec<Vec<Lit>> = vec![];
+    for k in 0..n {
+        let mut reach_k = vec![];
+        for v in 0..n {
+            let lit = new_aux_lit(num_var);
+            out.push(vec![!lit, lits[v]]);
+            if k == 0 {
+                out.push(vec![!lit, !has_active_before[v]]);
+            } else {
+                let mut clause = vec![!lit, reach[k - 1][v]];
+                for &u in &adj[v] {
+                    clause.push(reach[k - 1][u]);
+                }
+                out.push(clause);
+            }
+            reach_k.push(lit);
+        }
+        reach.push(reach_k);
+    }
+    for v in 0..n {
+        out.push(vec![!lits[v], reach[n - 1][v]]);
+    }
+}
+
+/// Expands the constraint that the assignment of `vars` (in direct encoding) matches one of
+/// `supports` (where `None` matches any value).
+fn expand_extension_supports(
+    vars: &[Vec<Lit>],
+    supports: &[Vec<Option<usize>>],
+    num_var: &mut usize,
+    out: &mut Vec<Vec<Lit>>,
+) {
+    let mut any_support = vec![];
+    for support in supports {
+        let lit = new_aux_lit(num_var);
+        for i in 0..vars.len() {
+            if let Some(j) = support[i] {
+                out.push(vec![!lit, vars[i][j]]);
+            }
+        }
+        any_support.push(lit);
+    }
+    out.push(any_support);
+}
+
 pub enum SATModel<'a> {
     Glucose(glucose::Model<'a>),
     #[cfg(feature = "backend-external")]
@@ -380,3 +1141,244 @@ impl<'a> SATModel<'a> {
         self.assignment(lit.var()) ^ lit.is_negated()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "sat-analyzer"))]
+    fn new_lits(sat: &mut SAT, count: usize) -> Vec<Lit> {
+        sat.new_vars_as_lits(count)
+    }
+
+    #[cfg(feature = "sat-analyzer")]
+    fn new_lits(sat: &mut SAT, count: usize) -> Vec<Lit> {
+        sat.new_vars_as_lits(count, "test")
+    }
+
+    fn parse_dimacs(dimacs: &str) -> (usize, Vec<Vec<i32>>) {
+        let mut header = None;
+        let mut clauses = vec![];
+        for line in dimacs.lines() {
+            if line.starts_with("c ") {
+                continue;
+            }
+            if let Some(h) = line.strip_prefix("p cnf ") {
+                let h = h
+                    .split(' ')
+                    .map(|t| t.parse::<usize>().unwrap())
+                    .collect::<Vec<_>>();
+                header = Some((h[0], h[1]));
+                continue;
+            }
+            let mut clause = line
+                .split_whitespace()
+                .map(|t| t.parse::<i32>().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(clause.pop(), Some(0));
+            clauses.push(clause);
+        }
+        let (num_vars, num_clauses) = header.unwrap();
+        assert_eq!(clauses.len(), num_clauses);
+        for clause in &clauses {
+            for &l in clause {
+                assert!(l != 0 && l.unsigned_abs() as usize <= num_vars);
+            }
+        }
+        (num_vars, clauses)
+    }
+
+    #[test]
+    fn test_to_dimacs() {
+        let mut sat = SAT::new();
+        sat.enable_recording();
+
+        // x in {0, 1, 2}, y in {0, 1, 3} (order encoding)
+        let x = new_lits(&mut sat, 2);
+        let y = new_lits(&mut sat, 2);
+        sat.add_clause(&[x[0], !x[1]]);
+        sat.add_clause(&[y[0], !y[1]]);
+        // 2x - y - 1 >= 0
+        sat.add_order_encoding_linear(
+            vec![x.clone(), y.clone()],
+            vec![vec![0, 1, 2], vec![0, 1, 3]],
+            vec![2, -1],
+            -1,
+        );
+        // active vertices on the path 0 - 1 - 2 - 3 are connected
+        let a = new_lits(&mut sat, 4);
+        sat.add_active_vertices_connected(a.clone(), vec![(0, 1), (1, 2), (2, 3)]);
+
+        let dimacs = sat.to_dimacs();
+        let (num_vars, clauses) = parse_dimacs(&dimacs);
+        assert!(num_vars > sat.num_var());
+
+        let mut sat2 = SAT::new();
+        let vars = new_lits(&mut sat2, num_vars);
+        for clause in &clauses {
+            let clause = clause
+                .iter()
+                .map(|&l| {
+                    let lit = vars[l.unsigned_abs() as usize - 1];
+                    if l < 0 {
+                        !lit
+                    } else {
+                        lit
+                    }
+                })
+                .collect::<Vec<_>>();
+            sat2.add_clause(&clause);
+        }
+
+        // compare the satisfiability under each assignment of the original variables
+        let orig = x
+            .iter()
+            .chain(y.iter())
+            .chain(a.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        for mask in 0..(1u32 << orig.len()) {
+            let value = |i: usize| (mask >> i) & 1 != 0;
+            let assumptions = (0..orig.len())
+                .map(|i| if value(i) { orig[i] } else { !orig[i] })
+                .collect::<Vec<_>>();
+
+            let xv = if value(1) {
+                2
+            } else if value(0) {
+                1
+            } else {
+                0
+            };
+            let yv = if value(3) {
+                3
+            } else if value(2) {
+                1
+            } else {
+                0
+            };
+            let active = (0..4).filter(|&i| value(4 + i)).collect::<Vec<_>>();
+            let expected = (value(0) || !value(1))
+                && (value(2) || !value(3))
+                && 2 * xv - yv > 0
+                && active.windows(2).all(|w| w[0] + 1 == w[1]);
+
+            assert_eq!(
+                sat2.solve_without_model_under_assumptions(&assumptions),
+                expected
+            );
+        }
+    }
+
+    fn count_models(sat: &mut SAT, lits: &[Lit]) -> usize {
+        let mut count = 0;
+        while let Some(model) = sat.solve() {
+            let blocking = lits
+                .iter()
+                .map(|&l| if model.assignment_lit(l) { !l } else { l })
+                .collect::<Vec<_>>();
+            sat.add_clause(&blocking);
+            count += 1;
+        }
+        count
+    }
+
+    fn binomial(n: usize, k: usize) -> usize {
+        if k > n {
+            return 0;
+        }
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn test_truncate_default_phase() {
+        let mut sat = SAT::new();
+        sat.enable_recording();
+
+        let x = new_lits(&mut sat, 2);
+        sat.add_clause(&[x[0], x[1]]);
+        sat.set_default_phase(x[0], false);
+        sat.set_default_phase(x[1], true);
+        let assignment = |sat: &mut SAT| {
+            let model = sat.solve().unwrap();
+            (model.assignment_lit(x[0]), model.assignment_lit(x[1]))
+        };
+        assert_eq!(assignment(&mut sat), (false, true));
+
+        // hints given before the mark survive `truncate`, but those given after it are discarded
+        let mark = sat.mark();
+        let y = new_lits(&mut sat, 1);
+        sat.add_clause(&[!x[0], y[0]]);
+        sat.set_default_phase(x[0], true);
+        sat.set_default_phase(x[1], false);
+
+        sat.truncate(mark);
+        assert_eq!(assignment(&mut sat), (false, true));
+    }
+
+    #[test]
+    fn test_cardinality_constraints() {
+        for n in 1..=6 {
+            for k in 0..=(n + 1) {
+                {
+                    let mut sat = SAT::new();
+                    let lits = new_lits(&mut sat, n);
+                    sat.add_at_most_k(&lits, k);
+                    let expected = (0..=k).map(|i| binomial(n, i)).sum::<usize>();
+                    assert_eq!(count_models(&mut sat, &lits), expected, "n={} k={}", n, k);
+                }
+                {
+                    let mut sat = SAT::new();
+                    let lits = new_lits(&mut sat, n);
+                    sat.add_at_least_k(&lits, k);
+                    let expected = (k..=n).map(|i| binomial(n, i)).sum::<usize>();
+                    assert_eq!(count_models(&mut sat, &lits), expected, "n={} k={}", n, k);
+                }
+                {
+                    let mut sat = SAT::new();
+                    let lits = new_lits(&mut sat, n);
+                    sat.add_at_most_k(&lits, k);
+                    sat.add_at_least_k(&lits, k);
+                    assert_eq!(
+                        count_models(&mut sat, &lits),
+                        binomial(n, k),
+                        "n={} k={}",
+                        n,
+                        k
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "drat")]
+    #[test]
+    fn test_drat_proof() {
+        let path =
+            std::env::temp_dir().join(format!("enigma_csp_test_{}.drat", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut sat = SAT::new();
+        sat.enable_drat(path).unwrap();
+
+        // pigeonhole principle with 3 pigeons and 2 holes
+        let x = (0..3).map(|_| new_lits(&mut sat, 2)).collect::<Vec<_>>();
+        for xi in &x {
+            sat.add_clause(xi);
+        }
+        for j in 0..2 {
+            for i in 0..3 {
+                for k in 0..i {
+                    sat.add_clause(&[!x[i][j], !x[k][j]]);
+                }
+            }
+        }
+        assert!(sat.solve().is_none());
+
+        let proof = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let lines = proof.lines().collect::<Vec<_>>();
+        assert!(!lines.is_empty());
+        assert_eq!(lines.last(), Some(&"0"));
+    }
+}
@@ -61,6 +61,86 @@ pub fn check_graph_active_vertices_connected(is_active: &[bool], edges: &[(usize
     n_connected_components <= 1
 }
 
+pub fn check_graph_directed_reachable(
+    source: usize,
+    is_active: &[bool],
+    edges: &[(usize, usize)],
+) -> bool {
+    let n = is_active.len();
+    let mut graph = vec![vec![]; n];
+    for &(u, v) in edges {
+        graph[u].push(v);
+    }
+
+    let mut visited = vec![false; n];
+    fn visit(graph: &[Vec<usize>], is_active: &[bool], visited: &mut [bool], p: usize) {
+        if visited[p] || !is_active[p] {
+            return;
+        }
+        visited[p] = true;
+        for &q in &graph[p] {
+            visit(graph, is_active, visited, q);
+        }
+    }
+    visit(&graph, is_active, &mut visited, source);
+
+    (0..n).all(|u| !is_active[u] || visited[u])
+}
+
+pub fn check_single_cycle(is_active: &[bool], edges: &[(usize, usize)]) -> bool {
+    let n = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+    let mut degree = vec![0; n];
+    for (&(u, v), &a) in edges.iter().zip(is_active) {
+        if a {
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+    }
+    if degree.iter().any(|&d| d != 0 && d != 2) || !is_active.iter().any(|&a| a) {
+        return false;
+    }
+
+    // the line graph of the active edges must be connected
+    let mut line_graph_edges = vec![];
+    for i in 0..edges.len() {
+        for j in 0..i {
+            let (a, b) = edges[i];
+            let (c, d) = edges[j];
+            if a == c || a == d || b == c || b == d {
+                line_graph_edges.push((i, j));
+            }
+        }
+    }
+    check_graph_active_vertices_connected(is_active, &line_graph_edges)
+}
+
+pub fn check_acyclic(is_active: &[bool], edges: &[(usize, usize)]) -> bool {
+    let n = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+    let mut parent = (0..n).collect::<Vec<_>>();
+    fn root(parent: &mut [usize], p: usize) -> usize {
+        if parent[p] == p {
+            p
+        } else {
+            let r = root(parent, parent[p]);
+            parent[p] = r;
+            r
+        }
+    }
+
+    for (&(u, v), &a) in edges.iter().zip(is_active) {
+        if !a {
+            continue;
+        }
+        let ru = root(&mut parent, u);
+        let rv = root(&mut parent, v);
+        if ru == rv {
+            return false;
+        }
+        parent[ru] = rv;
+    }
+    true
+}
+
 pub fn check_circuit(values: &[i32]) -> bool {
     let n = values.len();
     if values.iter().any(|&x| x < 0 || x >= n as i32) {
@@ -32,6 +32,7 @@ pub trait ConvertMapIndex {
     fn to_index(&self) -> usize;
 }
 
+#[derive(Clone)]
 pub struct ConvertMap<K: ConvertMapIndex, V> {
     data: Vec<Option<V>>,
     key_type: PhantomData<K>,